@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+/// Directory stored attachments are published under, next to the frontend.
+pub const PUBLISHED_ATTACHMENTS_DIR: &str = "attachments";
+
+/// Kind of document linked from an auction page.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum AttachmentKind {
+    /// Auction edict (edicto).
+    Edict,
+    /// Charges certificate (certificación de cargas).
+    ChargesCertificate,
+    /// Photo of the asset.
+    Photo,
+    /// Any other document.
+    Document,
+}
+
+impl AttachmentKind {
+    /// Kind name in spanish.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Edict => "Edicto",
+            Self::ChargesCertificate => "Certificación de cargas",
+            Self::Photo => "Fotografía",
+            Self::Document => "Documento",
+        }
+    }
+
+    /// Guess the kind of the attachment linked from `url` with `title` text.
+    pub fn from_link(url: &str, title: &str) -> Self {
+        let url = url.to_lowercase();
+        let title = title.to_uppercase();
+
+        if title.contains("FOTOGRAF")
+            || [".jpg", ".jpeg", ".png", ".gif", ".webp"]
+                .iter()
+                .any(|extension| url.ends_with(extension))
+        {
+            Self::Photo
+        } else if title.contains("EDICTO") {
+            Self::Edict
+        } else if title.contains("CARGAS") {
+            Self::ChargesCertificate
+        } else {
+            Self::Document
+        }
+    }
+}
+
+/// Document or photo attached to an auction asset.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Attachment {
+    /// Kind of attachment.
+    pub kind: AttachmentKind,
+    /// Link text in the auction page.
+    pub title: String,
+    /// Where the attachment was downloaded from.
+    pub source_url: String,
+    /// SHA-256 hex digest of the content, its key in the local store.
+    pub hash: String,
+    /// Media type of the content.
+    pub content_type: String,
+    /// Size in bytes.
+    pub size: u64,
+}
+
+impl Attachment {
+    /// If the attachment is an image that can be shown as thumbnail.
+    pub fn is_image(&self) -> bool {
+        self.kind == AttachmentKind::Photo && self.content_type.starts_with("image/")
+    }
+
+    /// Path of the stored copy inside an attachments store, sharded by the hash prefix.
+    pub fn stored_path(&self) -> String {
+        format!("{}/{}", &self.hash[..self.hash.len().min(2)], self.hash)
+    }
+
+    /// Link to the stored copy published along the frontend.
+    pub fn published_url(&self) -> String {
+        format!("{}/{}", PUBLISHED_ATTACHMENTS_DIR, self.stored_path())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attachment_kind_from_link_test() {
+        assert_eq!(
+            AttachmentKind::Photo,
+            AttachmentKind::from_link("https://subastas.boe.es/fotos/1.JPG", "")
+        );
+        assert_eq!(
+            AttachmentKind::Photo,
+            AttachmentKind::from_link(
+                "https://subastas.boe.es/reg/detalleSubasta.php?idSub=SUB-1&ver=4",
+                "VER FOTOGRAFÍAS ANEXAS"
+            )
+        );
+        assert_eq!(
+            AttachmentKind::Edict,
+            AttachmentKind::from_link("https://subastas.boe.es/doc/1.pdf", "Edicto de subasta")
+        );
+        assert_eq!(
+            AttachmentKind::ChargesCertificate,
+            AttachmentKind::from_link(
+                "https://subastas.boe.es/doc/2.pdf",
+                "Certificación de cargas"
+            )
+        );
+        assert_eq!(
+            AttachmentKind::Document,
+            AttachmentKind::from_link("https://subastas.boe.es/doc/3.pdf", "Anexo")
+        );
+    }
+
+    #[test]
+    fn attachment_published_url_test() {
+        let attachment = Attachment {
+            kind: AttachmentKind::Edict,
+            title: "Edicto de subasta".to_owned(),
+            source_url: "https://subastas.boe.es/doc/1.pdf".to_owned(),
+            hash: "ef20817abf0411c00aab054912ba0df82682cd2646be9a3c52ba422e66c4425a".to_owned(),
+            content_type: "application/pdf".to_owned(),
+            size: 15,
+        };
+
+        assert_eq!(
+            "ef/ef20817abf0411c00aab054912ba0df82682cd2646be9a3c52ba422e66c4425a",
+            attachment.stored_path()
+        );
+        assert_eq!(
+            "attachments/ef/ef20817abf0411c00aab054912ba0df82682cd2646be9a3c52ba422e66c4425a",
+            attachment.published_url()
+        );
+    }
+}
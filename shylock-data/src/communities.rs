@@ -0,0 +1,226 @@
+use crate::provinces::Province;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::{error::Error, fmt};
+
+/// Indicates if the autonomous community is invalid.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InvalidCommunity {
+    community: String,
+}
+
+impl InvalidCommunity {
+    fn new(community: &str) -> Self {
+        InvalidCommunity {
+            community: community.to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for InvalidCommunity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Unkown autonomous community: {}", &self.community[..])
+    }
+}
+
+impl Error for InvalidCommunity {}
+
+macro_rules! autonomous_communities {
+    (
+        $(
+            $(#[$docs:meta])*
+            ($konst:ident, $name:expr, $display:expr);
+        )+
+    ) => {
+        /// Type of autonomous communities
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Deserialize, Serialize)]
+        #[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+        pub enum AutonomousCommunity {
+            $(
+                $(#[$docs])*
+                $konst,
+            )+
+        }
+
+        impl FromStr for AutonomousCommunity {
+            type Err = InvalidCommunity;
+
+            #[inline]
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let community: String = s.to_uppercase()
+                   .replace(" ", "")
+                   .chars()
+                   .map(|x| match x {
+                        'Á' => 'A',
+                        'É' => 'E',
+                        'Í' => 'I',
+                        'Ó' => 'O',
+                        'Ú' => 'U',
+                        _ => x,
+                    }).collect();
+
+                match &community[..] {
+                    "ASTURIAS" => Ok(AutonomousCommunity::Asturias),
+                    "BALEARES" => Ok(AutonomousCommunity::Baleares),
+                    "CASTILLA-LAMANCHA" => Ok(AutonomousCommunity::CastillaLaMancha),
+                    "CATALUNYA" => Ok(AutonomousCommunity::Cataluna),
+                    "COMUNITATVALENCIANA" => Ok(AutonomousCommunity::ComunidadValenciana),
+                    "EUSKADI" => Ok(AutonomousCommunity::PaisVasco),
+                    "MADRID" => Ok(AutonomousCommunity::Madrid),
+                    "MURCIA" => Ok(AutonomousCommunity::Murcia),
+                    "NAVARRA" => Ok(AutonomousCommunity::Navarra),
+                    $(
+                    $name => Ok(AutonomousCommunity::$konst) ,
+                    )+
+                    _ => Err(InvalidCommunity::new(s)),
+                }
+            }
+        }
+
+        impl AutonomousCommunity {
+            /// Returns the string representation for this autonomous community
+            pub fn name(&self) -> &str {
+                COMMUNITIES.get(self).unwrap_or(&"Unknown")
+            }
+
+            /// Returns the provinces belonging to this autonomous community.
+            pub fn provinces(&self) -> Vec<Province> {
+                COMMUNITY_PROVINCES.get(self).cloned().unwrap_or_default()
+            }
+        }
+
+        static COMMUNITIES: Lazy<HashMap<AutonomousCommunity, &str>> = Lazy::new(|| {
+            let mut communities: HashMap<AutonomousCommunity, &str> = HashMap::new();
+
+            $(
+            communities.insert(AutonomousCommunity::$konst, $display);
+            )+
+
+            communities
+        });
+
+        #[cfg(test)]
+        const TEST_COMMUNITIES: &'static [(AutonomousCommunity, &'static str, &'static str)] = &[
+            $(
+            (AutonomousCommunity::$konst, $name, $display),
+            )+
+        ];
+
+        #[test]
+        fn test_parse_community() {
+            for &(std, name, _) in TEST_COMMUNITIES {
+                // Test upper case
+                assert_eq!(name.parse::<AutonomousCommunity>().unwrap(), std);
+
+                // Test lower case
+                assert_eq!(name.to_lowercase().parse::<AutonomousCommunity>().unwrap(), std);
+            }
+        }
+
+        #[test]
+        fn test_community_name() {
+            for &(std, _, display) in TEST_COMMUNITIES {
+                assert_eq!(std.name(), display);
+            }
+        }
+
+        #[test]
+        fn test_parse_invalid_community() {
+            let invalid_community = "non-sense";
+            assert_eq!(invalid_community.parse::<AutonomousCommunity>(), Err(InvalidCommunity::new(invalid_community)));
+        }
+    }
+}
+
+static COMMUNITY_PROVINCES: Lazy<HashMap<AutonomousCommunity, Vec<Province>>> = Lazy::new(|| {
+    let mut communities: HashMap<AutonomousCommunity, Vec<Province>> = HashMap::new();
+
+    for code in 1..=52 {
+        if let Ok(province) = Province::from_ine_code(&code.to_string()) {
+            communities
+                .entry(province.community())
+                .or_default()
+                .push(province);
+        }
+    }
+
+    communities
+});
+
+autonomous_communities! {
+    /// Andalucía
+    (Andalucia, "ANDALUCIA", "Andalucía");
+
+    /// Aragón
+    (Aragon, "ARAGON", "Aragón");
+
+    /// Principado de Asturias
+    (Asturias, "PRINCIPADODEASTURIAS", "Principado de Asturias");
+
+    /// Illes Balears
+    (Baleares, "ILLESBALEARS", "Illes Balears");
+
+    /// Canarias
+    (Canarias, "CANARIAS", "Canarias");
+
+    /// Cantabria
+    (Cantabria, "CANTABRIA", "Cantabria");
+
+    /// Castilla-La Mancha
+    (CastillaLaMancha, "CASTILLALAMANCHA", "Castilla-La Mancha");
+
+    /// Castilla y León
+    (CastillaYLeon, "CASTILLAYLEON", "Castilla y León");
+
+    /// Cataluña
+    (Cataluna, "CATALUÑA", "Cataluña");
+
+    /// Comunidad Valenciana
+    (ComunidadValenciana, "COMUNIDADVALENCIANA", "Comunidad Valenciana");
+
+    /// Extremadura
+    (Extremadura, "EXTREMADURA", "Extremadura");
+
+    /// Galicia
+    (Galicia, "GALICIA", "Galicia");
+
+    /// La Rioja
+    (LaRioja, "LARIOJA", "La Rioja");
+
+    /// Comunidad de Madrid
+    (Madrid, "COMUNIDADDEMADRID", "Comunidad de Madrid");
+
+    /// Región de Murcia
+    (Murcia, "REGIONDEMURCIA", "Región de Murcia");
+
+    /// Comunidad Foral de Navarra
+    (Navarra, "COMUNIDADFORALDENAVARRA", "Comunidad Foral de Navarra");
+
+    /// País Vasco
+    (PaisVasco, "PAISVASCO", "País Vasco");
+
+    /// Ceuta autonomous city
+    (Ceuta, "CEUTA", "Ceuta");
+
+    /// Melilla autonomous city
+    (Melilla, "MELILLA", "Melilla");
+
+    /// Unkown
+    (Unknown, "UNKNOWN", "Desconocida");
+
+    /// All
+    (All, "ALL", "All");
+}
+
+#[test]
+fn test_community_provinces() {
+    assert_eq!(
+        AutonomousCommunity::PaisVasco.provinces(),
+        vec![Province::Alava, Province::Guipuzcoa, Province::Vizcaya]
+    );
+    assert_eq!(AutonomousCommunity::Andalucia.provinces().len(), 8);
+    assert!(AutonomousCommunity::Unknown.provinces().is_empty());
+}
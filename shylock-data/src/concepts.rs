@@ -55,6 +55,7 @@ macro_rules! boe_auction_concepts {
                     "FECHA DE MATRICULACIÓN" => Ok(BoeConcept::LicensedDate),
                     "REFERENCIA REGISTRAL" => Ok(BoeConcept::RegisterInscription),
                     "NOMBRE PARAJE" => Ok(BoeConcept::Place),
+                    "PUJA MÁS ALTA" => Ok(BoeConcept::HighestBid),
                     _ => Err(InvalidBoeConcept::new(s)),
                 }
             }
@@ -165,6 +166,9 @@ boe_auction_concepts! {
     /// Header concept.
     (Header, "HEADER");
 
+    /// Highest bid received when the auction concluded.
+    (HighestBid, "PUJA MÁXIMA");
+
     /// Identifier of the concept.
     (Identifier, "IDENTIFICADOR");
 
@@ -198,6 +202,9 @@ boe_auction_concepts! {
     /// BOE Notice.
     (Notice, "ANUNCIO BOE");
 
+    /// How the auction concluded.
+    (Outcome, "RESULTADO");
+
     /// Owner status of the asset.
     (OwnerStatus, "SITUACIÓN POSESORIA");
 
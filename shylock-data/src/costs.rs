@@ -0,0 +1,442 @@
+use crate::categories::{OtherCategory, PropertyCategory, VehicleCategory};
+use crate::communities::AutonomousCommunity;
+use crate::provinces::Province;
+use crate::types::{AuctionKind, BidInfo};
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Kind of asset being acquired, it determines the taxes to pay.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum CostAsset {
+    /// Real state property.
+    Property(PropertyCategory),
+    /// Vehicle.
+    Vehicle(VehicleCategory),
+    /// Any other asset.
+    Other(OtherCategory),
+}
+
+/// Who sells the asset, business sellers charge VAT instead of transfer tax.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Seller {
+    /// Individual, transfer tax (ITP) applies.
+    Individual,
+    /// Company or professional, VAT applies.
+    Business,
+}
+
+/// Tax rates and fees used to estimate the acquisition cost.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CostRates {
+    /// Deposit over the auction value, 5% under BOE rules.
+    pub deposit: Decimal,
+    /// General property transfer tax (ITP) rate by autonomous community.
+    pub property_transfer: HashMap<AutonomousCommunity, Decimal>,
+    /// Transfer tax rate when the community has no specific one.
+    pub default_property_transfer: Decimal,
+    /// Transfer tax rate for vehicles and other movable assets.
+    pub movable_transfer: Decimal,
+    /// Reduced VAT rate for housing.
+    pub housing_vat: Decimal,
+    /// General VAT rate.
+    pub general_vat: Decimal,
+    /// Stamp duty (AJD) rate paid along VAT on properties.
+    pub stamp_duty: Decimal,
+    /// Land registry and notary fees rate over the bid.
+    pub registry_fees: Decimal,
+    /// Minimum land registry and notary fees.
+    pub min_registry_fees: Decimal,
+    /// Traffic department (DGT) transfer fee for vehicles.
+    pub vehicle_transfer_fee: Decimal,
+}
+
+impl Default for CostRates {
+    /// General rates, specific deductions and regional reductions are not considered.
+    fn default() -> Self {
+        let property_transfer = [
+            (AutonomousCommunity::Andalucia, Decimal::new(7, 2)),
+            (AutonomousCommunity::Aragon, Decimal::new(8, 2)),
+            (AutonomousCommunity::Asturias, Decimal::new(8, 2)),
+            (AutonomousCommunity::Baleares, Decimal::new(8, 2)),
+            (AutonomousCommunity::Canarias, Decimal::new(65, 3)),
+            (AutonomousCommunity::Cantabria, Decimal::new(9, 2)),
+            (AutonomousCommunity::CastillaLaMancha, Decimal::new(9, 2)),
+            (AutonomousCommunity::CastillaYLeon, Decimal::new(8, 2)),
+            (AutonomousCommunity::Cataluna, Decimal::new(10, 2)),
+            (
+                AutonomousCommunity::ComunidadValenciana,
+                Decimal::new(10, 2),
+            ),
+            (AutonomousCommunity::Extremadura, Decimal::new(8, 2)),
+            (AutonomousCommunity::Galicia, Decimal::new(9, 2)),
+            (AutonomousCommunity::LaRioja, Decimal::new(7, 2)),
+            (AutonomousCommunity::Madrid, Decimal::new(6, 2)),
+            (AutonomousCommunity::Murcia, Decimal::new(8, 2)),
+            (AutonomousCommunity::Navarra, Decimal::new(6, 2)),
+            (AutonomousCommunity::PaisVasco, Decimal::new(4, 2)),
+            (AutonomousCommunity::Ceuta, Decimal::new(3, 2)),
+            (AutonomousCommunity::Melilla, Decimal::new(3, 2)),
+        ]
+        .into_iter()
+        .collect();
+
+        CostRates {
+            deposit: Decimal::new(5, 2),
+            property_transfer,
+            default_property_transfer: Decimal::new(8, 2),
+            movable_transfer: Decimal::new(4, 2),
+            housing_vat: Decimal::new(10, 2),
+            general_vat: Decimal::new(21, 2),
+            stamp_duty: Decimal::new(15, 3),
+            registry_fees: Decimal::new(2, 3),
+            min_registry_fees: Decimal::new(300, 0),
+            vehicle_transfer_fee: Decimal::new(5570, 2),
+        }
+    }
+}
+
+/// Concept of an acquisition cost item.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum CostConcept {
+    /// Proposed bid.
+    Bid,
+    /// Property transfer tax (ITP).
+    TransferTax,
+    /// Value added tax (IVA).
+    Vat,
+    /// Stamp duty (AJD).
+    StampDuty,
+    /// Land registry and notary fees.
+    RegistryFees,
+    /// Traffic department transfer fee.
+    VehicleTransferFee,
+    /// Previous charges the buyer takes over.
+    PendingCharges,
+}
+
+impl CostConcept {
+    /// Concept name in spanish.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Bid => "Puja",
+            Self::TransferTax => "ITP",
+            Self::Vat => "IVA",
+            Self::StampDuty => "AJD",
+            Self::RegistryFees => "Notaría y registro",
+            Self::VehicleTransferFee => "Tasa de transferencia DGT",
+            Self::PendingCharges => "Cargas previas",
+        }
+    }
+}
+
+/// Itemized acquisition cost line.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub struct CostItem {
+    /// What is paid.
+    pub concept: CostConcept,
+    /// Rate applied over the bid if any.
+    pub rate: Option<Decimal>,
+    /// Amount to pay.
+    pub amount: Decimal,
+}
+
+/// Court approval of the bid in judicial auctions (art. 670 LEC).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum BidApproval {
+    /// Not a judicial auction.
+    NotApplicable,
+    /// Bid reaches 70% of the value, approved right away.
+    Approved,
+    /// Bid reaches 50% of the value or covers the claim, debtor and creditor
+    /// can improve it within ten days.
+    SubjectToImprovement,
+    /// Bid is too low, the court decides.
+    CourtDiscretion,
+}
+
+impl BidApproval {
+    /// Approval name in spanish.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::NotApplicable => "No aplica",
+            Self::Approved => "Aprobación directa",
+            Self::SubjectToImprovement => "Sujeta a mejora",
+            Self::CourtDiscretion => "A criterio del tribunal",
+        }
+    }
+}
+
+/// Bid proposed for an asset.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Acquisition {
+    /// Asset being acquired.
+    pub asset: CostAsset,
+    /// Amount proposed to bid.
+    pub bid: Decimal,
+    /// Bid info of the lot merged with the auction one.
+    pub bidinfo: BidInfo,
+    /// Previous charges stated in the auction.
+    pub charges: Decimal,
+    /// Kind of auction.
+    pub kind: AuctionKind,
+    /// Province the asset is in.
+    pub province: Province,
+    /// Who sells the asset.
+    pub seller: Seller,
+    /// Garage or storage room sold along with a dwelling, taxed as housing.
+    pub with_dwelling: bool,
+}
+
+/// Itemized cost of winning an auction with a bid.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AcquisitionCost {
+    /// Deposit to participate, deducted from the bid when winning.
+    pub deposit: Decimal,
+    /// Cost items including the bid.
+    pub items: Vec<CostItem>,
+    /// Total cost.
+    pub total: Decimal,
+    /// Judicial approval of the bid.
+    pub approval: BidApproval,
+}
+
+fn rated(concept: CostConcept, rate: Decimal, bid: Decimal) -> CostItem {
+    CostItem {
+        concept,
+        rate: Some(rate),
+        amount: (bid * rate).round_dp(2),
+    }
+}
+
+fn fixed(concept: CostConcept, amount: Decimal) -> CostItem {
+    CostItem {
+        concept,
+        rate: None,
+        amount,
+    }
+}
+
+impl Acquisition {
+    fn taxes(&self, rates: &CostRates) -> Vec<CostItem> {
+        match (self.asset, self.seller) {
+            (CostAsset::Property(category), Seller::Business) => {
+                let vat = match category {
+                    PropertyCategory::Apartment => rates.housing_vat,
+                    PropertyCategory::Garage | PropertyCategory::StorageRoom
+                        if self.with_dwelling =>
+                    {
+                        rates.housing_vat
+                    }
+                    _ => rates.general_vat,
+                };
+                vec![
+                    rated(CostConcept::Vat, vat, self.bid),
+                    rated(CostConcept::StampDuty, rates.stamp_duty, self.bid),
+                ]
+            }
+            (CostAsset::Property(_), Seller::Individual) => {
+                let rate = rates
+                    .property_transfer
+                    .get(&self.province.community())
+                    .copied()
+                    .unwrap_or(rates.default_property_transfer);
+                vec![rated(CostConcept::TransferTax, rate, self.bid)]
+            }
+            (_, Seller::Business) => vec![rated(CostConcept::Vat, rates.general_vat, self.bid)],
+            (_, Seller::Individual) => vec![rated(
+                CostConcept::TransferTax,
+                rates.movable_transfer,
+                self.bid,
+            )],
+        }
+    }
+
+    fn fees(&self, rates: &CostRates) -> Vec<CostItem> {
+        match self.asset {
+            CostAsset::Property(_) => vec![fixed(
+                CostConcept::RegistryFees,
+                (self.bid * rates.registry_fees)
+                    .round_dp(2)
+                    .max(rates.min_registry_fees),
+            )],
+            CostAsset::Vehicle(_) => vec![fixed(
+                CostConcept::VehicleTransferFee,
+                rates.vehicle_transfer_fee,
+            )],
+            CostAsset::Other(_) => vec![],
+        }
+    }
+
+    fn approval(&self) -> BidApproval {
+        match self.kind {
+            AuctionKind::JudicialVoluntary
+            | AuctionKind::JudicialUnderPressure
+            | AuctionKind::Bankruptcy => {
+                let value = self.bidinfo.value;
+                let claim = self.bidinfo.claim_quantity;
+
+                if self.bid >= value * Decimal::new(7, 1) {
+                    BidApproval::Approved
+                } else if self.bid >= value * Decimal::new(5, 1)
+                    || (claim > Decimal::ONE && self.bid >= claim)
+                {
+                    BidApproval::SubjectToImprovement
+                } else {
+                    BidApproval::CourtDiscretion
+                }
+            }
+            _ => BidApproval::NotApplicable,
+        }
+    }
+
+    /// Estimate the acquisition cost according to `rates`.
+    pub fn cost(&self, rates: &CostRates) -> AcquisitionCost {
+        let deposit = if self.bidinfo.deposit > Decimal::ZERO {
+            self.bidinfo.deposit
+        } else {
+            (self.bidinfo.value * rates.deposit).round_dp(2)
+        };
+
+        let mut items = vec![fixed(CostConcept::Bid, self.bid)];
+        items.extend(self.taxes(rates));
+        items.extend(self.fees(rates));
+        if self.charges > Decimal::ZERO {
+            items.push(fixed(CostConcept::PendingCharges, self.charges));
+        }
+
+        AcquisitionCost {
+            deposit,
+            total: items.iter().map(|item| item.amount).sum(),
+            items,
+            approval: self.approval(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn acquisition(asset: CostAsset, province: Province, kind: AuctionKind) -> Acquisition {
+        Acquisition {
+            asset,
+            bid: Decimal::new(100_000, 0),
+            bidinfo: BidInfo {
+                appraisal: Decimal::new(200_000, 0),
+                bid_step: Decimal::ZERO,
+                claim_quantity: Decimal::new(90_000, 0),
+                deposit: Decimal::ZERO,
+                minimum_bid: Decimal::ZERO,
+                value: Decimal::new(200_000, 0),
+            },
+            charges: Decimal::new(3_000, 0),
+            kind,
+            province,
+            seller: Seller::Individual,
+            with_dwelling: false,
+        }
+    }
+
+    #[test]
+    fn property_acquisition_cost_test() {
+        let acquisition = acquisition(
+            CostAsset::Property(PropertyCategory::Apartment),
+            Province::Madrid,
+            AuctionKind::JudicialUnderPressure,
+        );
+        let cost = acquisition.cost(&CostRates::default());
+
+        assert_eq!(Decimal::new(10_000, 0), cost.deposit);
+        assert_eq!(
+            vec![
+                fixed(CostConcept::Bid, Decimal::new(100_000, 0)),
+                rated(
+                    CostConcept::TransferTax,
+                    Decimal::new(6, 2),
+                    Decimal::new(100_000, 0)
+                ),
+                fixed(CostConcept::RegistryFees, Decimal::new(300, 0)),
+                fixed(CostConcept::PendingCharges, Decimal::new(3_000, 0)),
+            ],
+            cost.items
+        );
+        assert_eq!(Decimal::new(109_300, 0), cost.total);
+        assert_eq!(BidApproval::SubjectToImprovement, cost.approval);
+
+        let business = Acquisition {
+            seller: Seller::Business,
+            bid: Decimal::new(140_000, 0),
+            ..acquisition
+        }
+        .cost(&CostRates::default());
+        assert_eq!(Decimal::new(14_000, 0), business.items[1].amount);
+        assert_eq!(Decimal::new(2_100, 0), business.items[2].amount);
+        assert_eq!(BidApproval::Approved, business.approval);
+    }
+
+    #[test]
+    fn annex_acquisition_vat_test() {
+        let garage = Acquisition {
+            seller: Seller::Business,
+            ..acquisition(
+                CostAsset::Property(PropertyCategory::Garage),
+                Province::Madrid,
+                AuctionKind::TaxAgency,
+            )
+        };
+        let standalone = garage.cost(&CostRates::default());
+        assert_eq!(
+            rated(
+                CostConcept::Vat,
+                Decimal::new(21, 2),
+                Decimal::new(100_000, 0)
+            ),
+            standalone.items[1]
+        );
+
+        let annex = Acquisition {
+            with_dwelling: true,
+            ..garage
+        }
+        .cost(&CostRates::default());
+        assert_eq!(
+            rated(
+                CostConcept::Vat,
+                Decimal::new(10, 2),
+                Decimal::new(100_000, 0)
+            ),
+            annex.items[1]
+        );
+    }
+
+    #[test]
+    fn vehicle_acquisition_cost_test() {
+        let acquisition = Acquisition {
+            bid: Decimal::new(5_000, 0),
+            charges: Decimal::ZERO,
+            ..acquisition(
+                CostAsset::Vehicle(VehicleCategory::Car),
+                Province::Barcelona,
+                AuctionKind::TaxAgency,
+            )
+        };
+        let cost = acquisition.cost(&CostRates::default());
+
+        assert_eq!(
+            vec![
+                fixed(CostConcept::Bid, Decimal::new(5_000, 0)),
+                rated(
+                    CostConcept::TransferTax,
+                    Decimal::new(4, 2),
+                    Decimal::new(5_000, 0)
+                ),
+                fixed(CostConcept::VehicleTransferFee, Decimal::new(5570, 2)),
+            ],
+            cost.items
+        );
+        assert_eq!(Decimal::new(525_570, 2), cost.total);
+        assert_eq!(BidApproval::NotApplicable, cost.approval);
+    }
+}
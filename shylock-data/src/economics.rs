@@ -0,0 +1,253 @@
+use crate::types::BidInfo;
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+// Claim quantities up to one euro mean the claim is not stated.
+const MIN_CLAIM_QUANTITY: Decimal = Decimal::ONE;
+
+/// Limits and weights used to score an auction opportunity.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub struct OpportunityConfig {
+    /// Claim to value ratio under which the asset is an opportunity.
+    pub max_claim_ratio: Decimal,
+    /// Weight of how low the claim is compared to the value.
+    pub claim_weight: Decimal,
+    /// Weight of the minimum bid discount over the appraisal.
+    pub discount_weight: Decimal,
+    /// Weight of bidding without steps.
+    pub bid_step_weight: Decimal,
+}
+
+impl Default for OpportunityConfig {
+    fn default() -> Self {
+        OpportunityConfig {
+            max_claim_ratio: Decimal::new(7, 1),
+            claim_weight: Decimal::new(6, 1),
+            discount_weight: Decimal::new(25, 2),
+            bid_step_weight: Decimal::new(15, 2),
+        }
+    }
+}
+
+/// Reason contributing to an opportunity score.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize, Serialize)]
+pub enum OpportunityFactor {
+    /// Claim quantity is below the configured share of the value.
+    LowClaim(Decimal),
+    /// Minimum bid is below the appraisal.
+    MinimumBidDiscount(Decimal),
+    /// Auction has no bid steps.
+    NoBidStep,
+}
+
+impl OpportunityFactor {
+    /// Factor explanation in spanish.
+    pub fn description(&self) -> String {
+        match self {
+            Self::LowClaim(ratio) => format!(
+                "cantidad reclamada del {} % del valor",
+                (ratio * Decimal::ONE_HUNDRED).round()
+            ),
+            Self::MinimumBidDiscount(discount) => format!(
+                "puja mínima un {} % bajo tasación",
+                (discount * Decimal::ONE_HUNDRED).round()
+            ),
+            Self::NoBidStep => "sin tramos entre pujas".to_owned(),
+        }
+    }
+}
+
+/// How interesting an auction is according to an `OpportunityConfig`.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct OpportunityScore {
+    /// Weighted score between zero and one.
+    pub score: Decimal,
+    /// If the claim is low enough to be an opportunity.
+    pub is_opportunity: bool,
+    /// Factors adding to the score.
+    pub factors: Vec<OpportunityFactor>,
+}
+
+impl OpportunityScore {
+    /// Factors explanation in spanish.
+    pub fn explanation(&self) -> String {
+        self.factors
+            .iter()
+            .map(OpportunityFactor::description)
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+impl BidInfo {
+    /// Fraction the minimum bid is below the appraisal, `None` without both amounts.
+    pub fn minimum_bid_discount(&self) -> Option<Decimal> {
+        if self.appraisal <= Decimal::ZERO || self.minimum_bid <= Decimal::ZERO {
+            return None;
+        }
+
+        Some((Decimal::ONE - self.minimum_bid / self.appraisal).round_dp(4))
+    }
+
+    /// Claim quantity divided by the auction value, `None` if any is not stated.
+    pub fn claim_to_value_ratio(&self) -> Option<Decimal> {
+        if self.value <= Decimal::ZERO || self.claim_quantity <= MIN_CLAIM_QUANTITY {
+            return None;
+        }
+
+        Some(self.claim_quantity / self.value)
+    }
+
+    /// Deposit as a percentage of the auction value.
+    pub fn deposit_percentage(&self) -> Option<Decimal> {
+        if self.value <= Decimal::ZERO {
+            return None;
+        }
+
+        Some((self.deposit * Decimal::ONE_HUNDRED / self.value).round_dp(2))
+    }
+
+    /// Lot bid info completed with the `auction` amounts the lot does not state.
+    pub fn merged_with(&self, auction: &BidInfo) -> BidInfo {
+        let pick = |lot: Decimal, auction: Decimal| {
+            if lot > Decimal::ONE {
+                lot
+            } else {
+                auction
+            }
+        };
+
+        BidInfo {
+            appraisal: pick(self.appraisal, auction.appraisal),
+            bid_step: pick(self.bid_step, auction.bid_step),
+            claim_quantity: pick(self.claim_quantity, auction.claim_quantity),
+            deposit: pick(self.deposit, auction.deposit),
+            minimum_bid: pick(self.minimum_bid, auction.minimum_bid),
+            value: pick(self.value, auction.value),
+        }
+    }
+
+    /// Score the auction economics according to `config`.
+    pub fn opportunity(&self, config: &OpportunityConfig) -> OpportunityScore {
+        let mut score = Decimal::ZERO;
+        let mut factors = vec![];
+
+        let claim_ratio = self
+            .claim_to_value_ratio()
+            .filter(|ratio| *ratio < config.max_claim_ratio);
+        if let Some(ratio) = claim_ratio {
+            score += config.claim_weight * (Decimal::ONE - ratio / config.max_claim_ratio);
+            factors.push(OpportunityFactor::LowClaim(ratio));
+        }
+
+        if let Some(discount) = self
+            .minimum_bid_discount()
+            .filter(|discount| *discount > Decimal::ZERO)
+        {
+            score += config.discount_weight * discount.min(Decimal::ONE);
+            factors.push(OpportunityFactor::MinimumBidDiscount(discount));
+        }
+
+        if self.bid_step <= Decimal::ZERO {
+            score += config.bid_step_weight;
+            factors.push(OpportunityFactor::NoBidStep);
+        }
+
+        OpportunityScore {
+            score: score.round_dp(2),
+            is_opportunity: claim_ratio.is_some(),
+            factors,
+        }
+    }
+
+    /// If the auction is an opportunity with the default configuration.
+    pub fn is_opportunity(&self) -> bool {
+        self.opportunity(&OpportunityConfig::default())
+            .is_opportunity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DEFAULT_DECIMALS;
+
+    fn bidinfo(claim_quantity: i64, minimum_bid: i64, bid_step: i64) -> BidInfo {
+        BidInfo {
+            appraisal: Decimal::new(10_000_000, DEFAULT_DECIMALS),
+            bid_step: Decimal::new(bid_step, DEFAULT_DECIMALS),
+            claim_quantity: Decimal::new(claim_quantity, DEFAULT_DECIMALS),
+            deposit: Decimal::new(500_000, DEFAULT_DECIMALS),
+            minimum_bid: Decimal::new(minimum_bid, DEFAULT_DECIMALS),
+            value: Decimal::new(10_000_000, DEFAULT_DECIMALS),
+        }
+    }
+
+    #[test]
+    fn bidinfo_ratios_test() {
+        let bidinfo = bidinfo(3_500_000, 7_000_000, 0);
+
+        assert_eq!(Some(Decimal::new(3, 1)), bidinfo.minimum_bid_discount());
+        assert_eq!(Some(Decimal::new(35, 2)), bidinfo.claim_to_value_ratio());
+        assert_eq!(Some(Decimal::new(5, 0)), bidinfo.deposit_percentage());
+
+        let empty = BidInfo {
+            appraisal: Decimal::ZERO,
+            bid_step: Decimal::ZERO,
+            claim_quantity: Decimal::ZERO,
+            deposit: Decimal::ZERO,
+            minimum_bid: Decimal::ZERO,
+            value: Decimal::ZERO,
+        };
+        assert_eq!(None, empty.minimum_bid_discount());
+        assert_eq!(None, empty.claim_to_value_ratio());
+        assert_eq!(None, empty.deposit_percentage());
+    }
+
+    #[test]
+    fn bidinfo_merged_with_test() {
+        let lot = BidInfo {
+            claim_quantity: Decimal::ZERO,
+            ..bidinfo(0, 6_000_000, 100_000)
+        };
+        let auction = bidinfo(2_000_000, 0, 0);
+
+        let merged = lot.merged_with(&auction);
+        assert_eq!(auction.claim_quantity, merged.claim_quantity);
+        assert_eq!(lot.minimum_bid, merged.minimum_bid);
+        assert_eq!(lot.bid_step, merged.bid_step);
+    }
+
+    #[test]
+    fn bidinfo_opportunity_test() {
+        let config = OpportunityConfig::default();
+
+        let opportunity = bidinfo(3_500_000, 7_000_000, 0).opportunity(&config);
+        assert!(opportunity.is_opportunity);
+        assert_eq!(Decimal::new(52, 2), opportunity.score);
+        assert_eq!(
+            vec![
+                OpportunityFactor::LowClaim(Decimal::new(35, 2)),
+                OpportunityFactor::MinimumBidDiscount(Decimal::new(3, 1)),
+                OpportunityFactor::NoBidStep,
+            ],
+            opportunity.factors
+        );
+        assert_eq!(
+            "cantidad reclamada del 35 % del valor, puja mínima un 30 % bajo tasación, sin tramos entre pujas",
+            opportunity.explanation()
+        );
+
+        // Same rule as the former 70% of the value check.
+        assert!(!bidinfo(7_000_000, 0, 100_000).is_opportunity());
+        assert!(bidinfo(6_999_900, 0, 100_000).is_opportunity());
+        assert!(!bidinfo(0, 0, 100_000).is_opportunity());
+
+        let strict = OpportunityConfig {
+            max_claim_ratio: Decimal::new(3, 1),
+            ..config
+        };
+        assert!(!bidinfo(3_500_000, 0, 0).opportunity(&strict).is_opportunity);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::types::parse_measure;
+
+use chrono::NaiveDate;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Kind of charge or lien weighing on an asset.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum EncumbranceKind {
+    /// Mortgage (hipoteca).
+    Mortgage,
+    /// Seizure (embargo), including tax agency diligences.
+    Seizure,
+    /// Tax affection (afección fiscal).
+    TaxAffection,
+    /// Lease (arrendamiento).
+    Lease,
+    /// Usufruct (usufructo).
+    Usufruct,
+    /// Easement (servidumbre).
+    Easement,
+    /// End of an administrative concession.
+    ConcessionExpiry,
+    /// Any other charge.
+    Other,
+}
+
+impl EncumbranceKind {
+    /// Kind name in spanish.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Mortgage => "Hipoteca",
+            Self::Seizure => "Embargo",
+            Self::TaxAffection => "Afección fiscal",
+            Self::Lease => "Arrendamiento",
+            Self::Usufruct => "Usufructo",
+            Self::Easement => "Servidumbre",
+            Self::ConcessionExpiry => "Fin de la concesión",
+            Self::Other => "Otra carga",
+        }
+    }
+}
+
+/// Charge or lien found in an asset text.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Encumbrance {
+    /// Kind of charge.
+    pub kind: EncumbranceKind,
+    /// Who the charge is in favor of.
+    pub creditor: Option<String>,
+    /// Pending amount.
+    pub amount: Option<Decimal>,
+    /// Date the amount refers to or the charge expires.
+    pub date: Option<NaiveDate>,
+}
+
+static CLAUSE_SEPARATOR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\.\s+|\s+-\s+|;\s*").expect("clause separator regex creation failed")
+});
+static COMPANY_SUFFIX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)\bS\.\s?(A|L)\.").expect("company suffix regex creation failed"));
+static CREDITOR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\bA FAVOR (?:DE(?:L)?\s+)(?:(?:LA|EL|LOS|LAS)\s+)?([^,;(.]+?)(?:\s*[,;(.]|\s+(?:CON|POR|EN|PARA|QUE|SEGUN|SEGÚN)\b|$)")
+        .expect("creditor regex creation failed")
+});
+static AMOUNT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(\d{1,3}(?:\.\d{3})+(?:,\d{1,2})?|\d+(?:,\d{1,2})?)\s*(?:€|EUROS?\b)")
+        .expect("amount regex creation failed")
+});
+static DATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\d{1,2})[-/](\d{1,2})[-/](\d{4})\b").expect("date regex creation failed")
+});
+
+// Negation shortly before a keyword: "NO ARRENDADA", "LIBRE DE CARGAS Y ARRENDATARIOS".
+static NEGATION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:NO|NI|SIN|LIBRES?\s+DE|EXENT[OA]S?\s+DE)(?:\s+[^\s,:]+){0,3}\s+$")
+        .expect("negation regex creation failed")
+});
+// Wording of the foreclosure procedure, not of a charge: "EJECUCIÓN HIPOTECARIA".
+static PROCEDURE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:PROCEDIMIENTO|EJECUCI[ÓO]N|EJECUTIVO|DEUDOR(?:ES)?)\s+(?:(?:DE|DEL|LA|EL|SOBRE|BIENES)\s+)*HIPOTECA\w*")
+        .expect("procedure regex creation failed")
+});
+static KIND_KEYWORDS: Lazy<Vec<(EncumbranceKind, Regex)>> = Lazy::new(|| {
+    [
+        (EncumbranceKind::Mortgage, "HIPOTECA"),
+        (EncumbranceKind::Seizure, "EMBARGO"),
+        (EncumbranceKind::TaxAffection, "AFECCI[ÓO]N"),
+        (EncumbranceKind::Lease, "ARRENDAMIENTO|ARRENDAD"),
+        (EncumbranceKind::Usufruct, "USUFRUCTO"),
+        (EncumbranceKind::Easement, "SERVIDUMBRE"),
+    ]
+    .into_iter()
+    .map(|(kind, keyword)| {
+        (
+            kind,
+            Regex::new(keyword).expect("keyword regex creation failed"),
+        )
+    })
+    .collect()
+});
+static OTHER_CHARGE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new("CARGA").expect("other charge regex creation failed"));
+
+// Whether `keyword` appears in `clause` without being negated.
+fn mentions(clause: &str, keyword: &Regex) -> bool {
+    keyword
+        .find_iter(clause)
+        .any(|found| !NEGATION_RE.is_match(&clause[..found.start()]))
+}
+
+fn kind_of(clause: &str) -> Option<EncumbranceKind> {
+    let clause = clause.to_uppercase();
+    let clause = PROCEDURE_RE.replace_all(&clause, "");
+
+    if let Some((kind, _)) = KIND_KEYWORDS
+        .iter()
+        .find(|(_, keyword)| mentions(&clause, keyword))
+    {
+        Some(*kind)
+    } else if clause.contains("CONCESI")
+        && ["FINALIZA", "VENCE", "EXPIRA", "CADUCA"]
+            .iter()
+            .any(|word| clause.contains(word))
+    {
+        Some(EncumbranceKind::ConcessionExpiry)
+    } else if mentions(&clause, &OTHER_CHARGE_RE) && AMOUNT_RE.is_match(&clause) {
+        Some(EncumbranceKind::Other)
+    } else {
+        None
+    }
+}
+
+fn parse_clause(clause: &str) -> Option<Encumbrance> {
+    let kind = kind_of(clause)?;
+
+    Some(Encumbrance {
+        kind,
+        creditor: CREDITOR_RE
+            .captures(clause)
+            .map(|captures| captures[1].trim().to_uppercase())
+            .filter(|creditor| !creditor.is_empty()),
+        amount: AMOUNT_RE
+            .captures(clause)
+            .and_then(|captures| parse_measure(&captures[1])),
+        date: DATE_RE.captures(clause).and_then(|captures| {
+            NaiveDate::from_ymd_opt(
+                captures[3].parse().ok()?,
+                captures[2].parse().ok()?,
+                captures[1].parse().ok()?,
+            )
+        }),
+    })
+}
+
+impl Encumbrance {
+    /// Extract the charges described in a BOE free `text`, one per clause.
+    pub fn from_text(text: &str) -> Vec<Encumbrance> {
+        // Company suffixes as S.A. would split clauses on their dots.
+        let text = COMPANY_SUFFIX_RE.replace_all(text, "S$1");
+
+        CLAUSE_SEPARATOR_RE
+            .split(&text)
+            .filter_map(parse_clause)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encumbrance_from_text_test() {
+        let encumbrances = Encumbrance::from_text(
+            "LAS CONDICIONES DE LA TRASMISIÓN Y LOS REQUISITOS DEL CONCESIONARIO SE ENCUENTRAN REGULADAS EN EL REAL DECRETO 1199/1999, DE 9 DE JULIO, POR EL QUE SE DESARROLLA LA LEY 13/1998, DE 4 DE MAYO, DE ORDENACIÓN DEL MERCADO DE TABACOS Y NORMATIVA TRIBUTARIA, Y SE REGULA EL ESTATUTO CONCESIONAL DE LA RED DE EXPENDURÍAS DE TABACO Y TIMBRE. VER FOTOGRAFÍAS ANEXAS. - LA CONCESIÓN FINALIZA 03/12/2042. - DILIGENCIA DE EMBARGO A FAVOR DE LA AEAT(2111623311338X), CON IMPORTE PENDIENTE A FECHA 17-09-2020 DE 10.347,54€.",
+        );
+
+        assert_eq!(
+            vec![
+                Encumbrance {
+                    kind: EncumbranceKind::ConcessionExpiry,
+                    creditor: None,
+                    amount: None,
+                    date: NaiveDate::from_ymd_opt(2042, 12, 3),
+                },
+                Encumbrance {
+                    kind: EncumbranceKind::Seizure,
+                    creditor: Some(String::from("AEAT")),
+                    amount: Some(Decimal::new(1_034_754, 2)),
+                    date: NaiveDate::from_ymd_opt(2020, 9, 17),
+                },
+            ],
+            encumbrances
+        );
+
+        let encumbrances = Encumbrance::from_text(
+            "Hipoteca a favor de Banco Santander, S.A. por importe de 120.000 euros; \
+             afección fiscal por el impuesto de transmisiones",
+        );
+        assert_eq!(2, encumbrances.len());
+        assert_eq!(EncumbranceKind::Mortgage, encumbrances[0].kind);
+        assert_eq!(Some("BANCO SANTANDER".to_owned()), encumbrances[0].creditor);
+        assert_eq!(Some(Decimal::new(120_000, 0)), encumbrances[0].amount);
+        assert_eq!(EncumbranceKind::TaxAffection, encumbrances[1].kind);
+
+        assert!(Encumbrance::from_text("VER FOTOGRAFÍAS ANEXAS.").is_empty());
+    }
+
+    #[derive(Deserialize)]
+    struct Fixture {
+        text: String,
+        kinds: Vec<EncumbranceKind>,
+    }
+
+    const FIXTURES: &str = include_str!("../fixtures/encumbrance_texts.json");
+
+    #[test]
+    fn encumbrance_corpus_test() {
+        let fixtures: Vec<Fixture> = serde_json::from_str(FIXTURES).unwrap();
+        assert!(!fixtures.is_empty());
+
+        for fixture in fixtures {
+            let kinds: Vec<EncumbranceKind> = Encumbrance::from_text(&fixture.text)
+                .iter()
+                .map(|encumbrance| encumbrance.kind)
+                .collect();
+            assert_eq!(fixture.kinds, kinds, "kinds of {}", fixture.text);
+        }
+    }
+}
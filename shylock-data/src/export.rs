@@ -0,0 +1,475 @@
+use crate::types::{Asset, Auction};
+
+use chrono::{DateTime, FixedOffset};
+use miniz_oxide::deflate::compress_to_vec;
+use miniz_oxide::inflate::decompress_to_vec;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::{error::Error, fmt};
+
+/// Exported structs as they were in version 1 files.
+pub mod v1;
+
+/// Current version of the exported data files, it must be increased
+/// whenever an exported struct changes in a non backwards compatible way.
+pub const EXPORT_SCHEMA_VERSION: u32 = 2;
+
+/// Oldest version of the exported data files still decoded,
+/// version 1 files are the bare payload without header.
+pub const MIN_EXPORT_SCHEMA_VERSION: u32 = 1;
+
+const COMPRESSION_LEVEL: u8 = 7;
+
+/// Errors decoding or encoding exported data files.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ExportError {
+    /// Data is not zlib compressed.
+    Compression,
+    /// Data could not be encoded or decoded as the expected type.
+    Format(String),
+    /// Data was exported with a schema version this build does not support.
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compression => write!(f, "invalid compressed data"),
+            Self::Format(error) => write!(f, "invalid data format: {}", error),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "unsupported schema version {}, supported from {} to {}",
+                version, MIN_EXPORT_SCHEMA_VERSION, EXPORT_SCHEMA_VERSION
+            ),
+        }
+    }
+}
+
+impl Error for ExportError {}
+
+/// Number of items exported.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ExportCounts {
+    /// Auctions.
+    pub auctions: usize,
+    /// Properties.
+    pub properties: usize,
+    /// Vehicles.
+    pub vehicles: usize,
+    /// Other assets.
+    pub others: usize,
+}
+
+/// Description of the exported data.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct ExportHeader {
+    /// Schema version the data was exported with.
+    pub schema_version: u32,
+    /// When the data was exported, unknown for version 1 files.
+    pub generated_at: Option<DateTime<FixedOffset>>,
+    /// Scrape run the data comes from if known.
+    pub scrape_run: Option<i64>,
+    /// Number of items exported along the data.
+    pub counts: ExportCounts,
+}
+
+impl ExportHeader {
+    /// Create a header for the current schema version.
+    pub fn new(
+        generated_at: DateTime<FixedOffset>,
+        scrape_run: Option<i64>,
+        counts: ExportCounts,
+    ) -> Self {
+        ExportHeader {
+            schema_version: EXPORT_SCHEMA_VERSION,
+            generated_at: Some(generated_at),
+            scrape_run,
+            counts,
+        }
+    }
+}
+
+/// Exported data with its header.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Export<T> {
+    /// Data description.
+    pub header: ExportHeader,
+    /// Exported data.
+    pub data: T,
+}
+
+#[derive(Deserialize)]
+struct HeaderOnly {
+    header: ExportHeader,
+}
+
+/// Exported data that can be migrated from version 1 files.
+pub trait ExportData: DeserializeOwned {
+    /// Shape of the data in version 1 files.
+    type V1: DeserializeOwned;
+
+    /// Migrate version 1 data to the current schema.
+    fn from_v1(data: Self::V1) -> Self;
+}
+
+impl ExportData for Auction {
+    type V1 = v1::Auction;
+
+    fn from_v1(data: Self::V1) -> Self {
+        data.into()
+    }
+}
+
+impl ExportData for Asset {
+    type V1 = v1::Asset;
+
+    fn from_v1(data: Self::V1) -> Self {
+        data.into()
+    }
+}
+
+impl<T: ExportData> ExportData for Vec<T> {
+    type V1 = Vec<T::V1>;
+
+    fn from_v1(data: Self::V1) -> Self {
+        data.into_iter().map(T::from_v1).collect()
+    }
+}
+
+impl<K: DeserializeOwned + Eq + Hash, V: ExportData> ExportData for HashMap<K, V> {
+    type V1 = HashMap<K, V::V1>;
+
+    fn from_v1(data: Self::V1) -> Self {
+        data.into_iter()
+            .map(|(key, value)| (key, V::from_v1(value)))
+            .collect()
+    }
+}
+
+impl<K: DeserializeOwned + Ord, V: ExportData> ExportData for BTreeMap<K, V> {
+    type V1 = BTreeMap<K, V::V1>;
+
+    fn from_v1(data: Self::V1) -> Self {
+        data.into_iter()
+            .map(|(key, value)| (key, V::from_v1(value)))
+            .collect()
+    }
+}
+
+impl<T: Serialize> Export<T> {
+    /// Encode as zlib compressed CBOR.
+    pub fn encode(&self) -> Result<Vec<u8>, ExportError> {
+        let mut buffer = vec![];
+        ciborium::ser::into_writer(self, &mut buffer)
+            .map_err(|error| ExportError::Format(error.to_string()))?;
+
+        Ok(compress_to_vec(&buffer, COMPRESSION_LEVEL))
+    }
+}
+
+impl<T: ExportData> Export<T> {
+    /// Decode zlib compressed CBOR data of any supported schema version,
+    /// version 1 data is migrated to the current schema.
+    pub fn decode(bytes: &[u8]) -> Result<Self, ExportError> {
+        let buffer = decompress_to_vec(bytes).map_err(|_| ExportError::Compression)?;
+
+        match ciborium::de::from_reader::<HeaderOnly, _>(&buffer[..]) {
+            Ok(HeaderOnly { header }) => {
+                // Headers were added in version 2, only headerless files are migrated.
+                if header.schema_version != EXPORT_SCHEMA_VERSION {
+                    return Err(ExportError::UnsupportedVersion(header.schema_version));
+                }
+
+                ciborium::de::from_reader(&buffer[..])
+                    .map_err(|error| ExportError::Format(error.to_string()))
+            }
+            Err(_) => {
+                let data: T::V1 = ciborium::de::from_reader(&buffer[..])
+                    .map_err(|error| ExportError::Format(error.to_string()))?;
+
+                Ok(Export {
+                    header: ExportHeader {
+                        schema_version: 1,
+                        generated_at: None,
+                        scrape_run: None,
+                        counts: ExportCounts::default(),
+                    },
+                    data: T::from_v1(data),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::categories::{OtherCategory, PropertyCategory, VehicleCategory};
+    use crate::provinces::Province;
+    use crate::types::{
+        AuctionKind, AuctionState, BidInfo, LotAuctionKind, Management, OwnerStatus, YesNoUnknown,
+    };
+    use chrono::NaiveDate;
+    use rust_decimal::Decimal;
+
+    #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+    struct Lot {
+        id: String,
+        value: u32,
+    }
+
+    impl ExportData for Lot {
+        type V1 = String;
+
+        fn from_v1(id: Self::V1) -> Self {
+            Lot { id, value: 0 }
+        }
+    }
+
+    fn header() -> ExportHeader {
+        ExportHeader::new(
+            DateTime::parse_from_rfc3339("2022-11-07T18:00:00+01:00").unwrap(),
+            Some(3),
+            ExportCounts {
+                auctions: 2,
+                ..ExportCounts::default()
+            },
+        )
+    }
+
+    fn encode_v1<T: Serialize>(data: &T) -> Vec<u8> {
+        let mut buffer = vec![];
+        ciborium::ser::into_writer(data, &mut buffer).unwrap();
+
+        compress_to_vec(&buffer, COMPRESSION_LEVEL)
+    }
+
+    #[test]
+    fn export_roundtrip_test() {
+        let data: BTreeMap<String, Lot> = ["SUB-1", "SUB-2"]
+            .into_iter()
+            .enumerate()
+            .map(|(value, id)| {
+                (
+                    id.to_owned(),
+                    Lot {
+                        id: id.to_owned(),
+                        value: value as u32,
+                    },
+                )
+            })
+            .collect();
+        let export = Export {
+            header: header(),
+            data,
+        };
+
+        let decoded = Export::<BTreeMap<String, Lot>>::decode(&export.encode().unwrap()).unwrap();
+        assert_eq!(export, decoded);
+    }
+
+    #[test]
+    fn export_previous_version_test() {
+        let decoded = Export::<Vec<Lot>>::decode(&encode_v1(&vec!["SUB-1"])).unwrap();
+        assert_eq!(1, decoded.header.schema_version);
+        assert_eq!(None, decoded.header.generated_at);
+        assert_eq!(
+            vec![Lot {
+                id: "SUB-1".to_owned(),
+                value: 0
+            }],
+            decoded.data
+        );
+    }
+
+    #[test]
+    fn export_v1_auctions_test() {
+        let auction = v1::Auction {
+            id: String::from("SUB-NE-2020-465937"),
+            auction_state: AuctionState::Unknown,
+            kind: AuctionKind::NotaryExtraJudicial,
+            claim_quantity: Decimal::new(8_197_157, 2),
+            lots: 0,
+            lot_kind: LotAuctionKind::NotApplicable,
+            management: Management {
+                code: String::from("3003000230"),
+                description: String::from(
+                    "UNIDAD SUBASTAS JUDICIALES MURCIA (MINISTERIO DE JUSTICIA)",
+                ),
+                address: String::from("AV DE LA JUSTICIA S/N S/N   ; 30011 MURCIA"),
+                telephone: String::from("968833360"),
+                fax: String::from("-"),
+                email: String::from("SUBASTAS.MURCIA@JUSTICIA.ES"),
+            },
+            bidinfo: BidInfo {
+                appraisal: Decimal::new(7_512_700, 2),
+                bid_step: Decimal::new(0, 2),
+                claim_quantity: Decimal::new(8_197_157, 2),
+                deposit: Decimal::new(375_635, 2),
+                minimum_bid: Decimal::new(0, 2),
+                value: Decimal::new(7_512_700, 2),
+            },
+            start_date: NaiveDate::from_ymd_opt(2020, 7, 14).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2020, 8, 3).unwrap(),
+            notice: String::from("BOE-B-2020-21708"),
+        };
+        let data: HashMap<String, v1::Auction> = [(auction.id.clone(), auction.clone())]
+            .into_iter()
+            .collect();
+
+        let decoded = Export::<HashMap<String, Auction>>::decode(&encode_v1(&data)).unwrap();
+        assert_eq!(1, decoded.header.schema_version);
+        let migrated = &decoded.data["SUB-NE-2020-465937"];
+        assert_eq!(auction.management, migrated.management);
+        assert_eq!(auction.bidinfo, migrated.bidinfo);
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2020-07-14T00:00:00+02:00").unwrap(),
+            migrated.start_date
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2020-08-03T00:00:00+02:00").unwrap(),
+            migrated.end_date
+        );
+        assert_eq!(None, migrated.account_record);
+        assert!(migrated.state_history.is_empty());
+    }
+
+    #[test]
+    fn export_v1_assets_test() {
+        let bidinfo = BidInfo {
+            appraisal: Decimal::new(0, 2),
+            bid_step: Decimal::new(30_200, 2),
+            claim_quantity: Decimal::new(0, 2),
+            deposit: Decimal::new(75_500, 2),
+            minimum_bid: Decimal::new(0, 2),
+            value: Decimal::new(1_510_000, 2),
+        };
+        let data = vec![
+            v1::Asset::Property(v1::Property {
+                address: String::from("CALLE MARIANO DE LOS COBOS 90"),
+                auction_id: String::from("id"),
+                bidinfo: None,
+                catastro_link: None,
+                catastro_reference: String::from("4110202UM5141A0003HH"),
+                category: PropertyCategory::Apartment,
+                charges: Decimal::new(0, 2),
+                city: String::from("VALLADOLID"),
+                coordinates: None,
+                description: String::from(
+                    "FINCA URBANA SITUADA EN VALLADOLID, CALLE MARIANO DE LOS COBOS NUM. 90, BAJO-1º",
+                ),
+                owner_status: String::from("NO CONSTA"),
+                postal_code: String::from("47014"),
+                primary_residence: String::from("SÍ"),
+                province: Province::Valladolid,
+                register_inscription: String::from("CONSTA EN EL EDICTO"),
+                visitable: String::from("NO CONSTA"),
+            }),
+            v1::Asset::Vehicle(v1::Vehicle {
+                auction_id: String::from("id"),
+                bidinfo: Some(bidinfo),
+                brand: String::from("AUDI"),
+                category: VehicleCategory::Car,
+                charges: Decimal::new(0, 2),
+                description: String::from(
+                    "VEHÍCULO MATRÍCULA 8868CXV, MARCA: AUDI, MODELO A4, Nº BASTIDOR / Nº CHASIS, EN SU CASO: WAUZZZ8E92A267004.",
+                ),
+                frame_number: String::from("WAUZZZ8E92A267004."),
+                licensed_date: NaiveDate::from_ymd_opt(2004, 7, 2).unwrap(),
+                license_plate: String::from("8868CXV"),
+                localization: String::from(
+                    "AVDA. SUAREZ INCLAN, 11, PLAZA DE GARAJE 60 33100 - TRUBIA",
+                ),
+                model: String::from("A4"),
+                visitable: String::from("SÍ"),
+            }),
+            v1::Asset::Other(v1::Other {
+                additional_information: String::from(
+                    "VER FOTOGRAFÍAS ANEXAS. - LA CONCESIÓN FINALIZA 03/12/2042. - DILIGENCIA DE EMBARGO A FAVOR DE LA AEAT(2111623311338X), CON IMPORTE PENDIENTE A FECHA 17-09-2020 DE 10.347,54€.",
+                ),
+                auction_id: String::from("id"),
+                bidinfo: Some(bidinfo),
+                category: OtherCategory::OtherRights,
+                charges: Decimal::new(1_034_754, 2),
+                description: String::from(
+                    "CONCESION EXPENDEDURIA DE TABACO Y TIMBRE ALMONTE-1, CODIGO 210049, SITA EN LA C/ DEL OCIO 105 DE ALMONTE (HUELVA)",
+                ),
+                judicial_title: String::from("OTROS DERECHOS"),
+                visitable: String::from("SÍ"),
+            }),
+        ];
+
+        let decoded = Export::<Vec<Asset>>::decode(&encode_v1(&data)).unwrap();
+        assert_eq!(3, decoded.data.len());
+
+        match &decoded.data[0] {
+            Asset::Property(property) => {
+                assert_eq!(
+                    Some("4110202UM5141A0003HH"),
+                    property.catastro_reference.as_ref().map(|r| r.as_str())
+                );
+                assert_eq!(OwnerStatus::Unknown, property.owner_status);
+                assert_eq!(YesNoUnknown::Yes, property.primary_residence);
+                assert_eq!(YesNoUnknown::Unknown, property.visitable);
+                assert_eq!(Province::Valladolid, property.province);
+            }
+            asset => panic!("Expected property, got {:?}", asset),
+        }
+
+        match &decoded.data[1] {
+            Asset::Vehicle(vehicle) => {
+                assert_eq!(Some(bidinfo), vehicle.bidinfo);
+                assert!(vehicle.plate.is_some());
+                assert_eq!(None, vehicle.registration_period);
+                assert_eq!(YesNoUnknown::Yes, vehicle.visitable);
+            }
+            asset => panic!("Expected vehicle, got {:?}", asset),
+        }
+
+        match &decoded.data[2] {
+            Asset::Other(other) => {
+                assert_eq!(Decimal::new(1_034_754, 2), other.charges);
+                assert_eq!(2, other.encumbrances.len());
+                assert_eq!(YesNoUnknown::Yes, other.visitable);
+            }
+            asset => panic!("Expected other, got {:?}", asset),
+        }
+    }
+
+    #[test]
+    fn export_incompatible_test() {
+        let newer = Export {
+            header: ExportHeader {
+                schema_version: EXPORT_SCHEMA_VERSION + 1,
+                ..header()
+            },
+            data: Lot {
+                id: "SUB-1".to_owned(),
+                value: 1,
+            },
+        };
+        assert_eq!(
+            Err(ExportError::UnsupportedVersion(EXPORT_SCHEMA_VERSION + 1)),
+            Export::<Lot>::decode(&newer.encode().unwrap())
+        );
+
+        let other_type = Export {
+            header: header(),
+            data: "SUB-1",
+        };
+        assert!(matches!(
+            Export::<Lot>::decode(&other_type.encode().unwrap()),
+            Err(ExportError::Format(_))
+        ));
+        assert!(matches!(
+            Export::<Vec<Asset>>::decode(&encode_v1(&vec![1u32])),
+            Err(ExportError::Format(_))
+        ));
+        assert_eq!(
+            Err(ExportError::Compression),
+            Export::<Lot>::decode(b"not compressed")
+        );
+    }
+}
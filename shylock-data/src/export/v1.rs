@@ -0,0 +1,281 @@
+use crate::categories::{OtherCategory, PropertyCategory, VehicleCategory};
+use crate::encumbrances::Encumbrance;
+use crate::features::PropertyFeatures;
+use crate::municipalities::Municipality;
+use crate::provinces::Province;
+use crate::types::{
+    self, default_date, default_datetime, madrid_datetime, AuctionKind, AuctionState, BidInfo,
+    LotAuctionKind, Management, OwnerStatus, YesNoUnknown,
+};
+use crate::vehicles::LicensePlate;
+
+use chrono::NaiveDate;
+use geo_types::Point;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Auction as exported in version 1 files, dates had no time.
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Auction {
+    /// Auction unique identifier.
+    pub id: String,
+    /// Auction state
+    pub auction_state: AuctionState,
+    /// Type of auction classified by entity.
+    pub kind: AuctionKind,
+    /// Quantity that is claimed by creditors.
+    pub claim_quantity: Decimal,
+    /// Number of lots.
+    pub lots: u32,
+    /// Kind of lots (splitted or joined).
+    pub lot_kind: LotAuctionKind,
+    /// Auction management.
+    pub management: Management,
+    /// BidInfo.
+    pub bidinfo: BidInfo,
+    /// Day the auction starts.
+    pub start_date: NaiveDate,
+    /// Day the auction closes.
+    pub end_date: NaiveDate,
+    /// Notice in official bulletin
+    pub notice: String,
+}
+
+/// Property as exported in version 1 files, answers were free text.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct Property {
+    /// Address location.
+    pub address: String,
+    /// Auction is linked to.
+    pub auction_id: String,
+    /// Bid info
+    pub bidinfo: Option<BidInfo>,
+    /// Link to catastro.
+    pub catastro_link: Option<String>,
+    /// Catastro reference as published.
+    pub catastro_reference: String,
+    /// Property category.
+    pub category: PropertyCategory,
+    /// If the property has any previous charges.
+    pub charges: Decimal,
+    /// City.
+    pub city: String,
+    /// Coordinates.
+    pub coordinates: Option<Point<f64>>,
+    /// Description.
+    pub description: String,
+    /// Possession status as published.
+    pub owner_status: String,
+    /// Postal code.
+    pub postal_code: String,
+    /// Primary residence answer as published.
+    pub primary_residence: String,
+    /// Province.
+    pub province: Province,
+    /// Register inscription.
+    pub register_inscription: String,
+    /// Visitable answer as published.
+    pub visitable: String,
+}
+
+impl Eq for Property {}
+
+/// Vehicle as exported in version 1 files.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Vehicle {
+    /// Auction identifier is linked to.
+    pub auction_id: String,
+    /// Bid info
+    pub bidinfo: Option<BidInfo>,
+    /// Vehicle brand.
+    pub brand: String,
+    /// vehicle category, usually: car, motorbike or industrial.
+    pub category: VehicleCategory,
+    /// If vehicle has previous charges.
+    pub charges: Decimal,
+    /// Description.
+    pub description: String,
+    /// Frame number.
+    pub frame_number: String,
+    /// Licensed date.
+    pub licensed_date: NaiveDate,
+    /// License plate number.
+    pub license_plate: String,
+    /// Localization.
+    pub localization: String,
+    /// Model.
+    pub model: String,
+    /// Visitable answer as published.
+    pub visitable: String,
+}
+
+/// Other asset as exported in version 1 files.
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Other {
+    /// Any asset additional information.
+    pub additional_information: String,
+    /// Auction is linked to.
+    pub auction_id: String,
+    /// Bid info
+    pub bidinfo: Option<BidInfo>,
+    /// Other category.
+    pub category: OtherCategory,
+    /// If the asset has any previous charges.
+    pub charges: Decimal,
+    /// Description.
+    pub description: String,
+    /// Type of judicial title if applies.
+    pub judicial_title: String,
+    /// Visitable answer as published.
+    pub visitable: String,
+}
+
+/// Asset as exported in version 1 files.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
+pub enum Asset {
+    /// All except vehicle or property
+    Other(Other),
+    /// Any kind of real state property
+    Property(Property),
+    /// Any kind of vehicle
+    Vehicle(Vehicle),
+}
+
+fn yes_no_unknown(answer: &str) -> YesNoUnknown {
+    answer.parse().unwrap_or(YesNoUnknown::Unknown)
+}
+
+// Version 1 files only kept the day, auctions are placed at Madrid midnight.
+fn day_datetime(date: NaiveDate) -> chrono::DateTime<chrono::FixedOffset> {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|date| madrid_datetime(&date))
+        .unwrap_or_else(default_datetime)
+}
+
+impl From<Auction> for types::Auction {
+    fn from(auction: Auction) -> Self {
+        types::Auction {
+            id: auction.id,
+            auction_state: auction.auction_state,
+            kind: auction.kind,
+            claim_quantity: auction.claim_quantity,
+            lots: auction.lots,
+            lot_kind: auction.lot_kind,
+            management: auction.management,
+            bidinfo: auction.bidinfo,
+            start_date: day_datetime(auction.start_date),
+            end_date: day_datetime(auction.end_date),
+            notice: auction.notice,
+            account_record: None,
+            suspension_reason: None,
+            state_history: Vec::new(),
+        }
+    }
+}
+
+// Version 1 descriptions were cleaned spacing numbers out, so features and
+// encumbrances found here are less complete than the ones parsed from BOE.
+impl From<Property> for types::Property {
+    fn from(property: Property) -> Self {
+        let municipality = Municipality::find(&property.city, property.province)
+            .map(|municipality| municipality.ine_code.clone());
+
+        types::Property {
+            address: property.address,
+            allotment: None,
+            area: None,
+            attachments: Vec::new(),
+            auction_id: property.auction_id,
+            bidinfo: property.bidinfo,
+            catastro_link: property.catastro_link,
+            catastro_reference: property.catastro_reference.parse().ok(),
+            category: property.category,
+            charges: property.charges,
+            city: property.city,
+            coordinates: property.coordinates,
+            encumbrances: Encumbrance::from_text(&property.description),
+            features: PropertyFeatures::from_description(&property.description),
+            description: property.description,
+            idufir: None,
+            lot: None,
+            municipality,
+            owner_status: property
+                .owner_status
+                .parse()
+                .unwrap_or(OwnerStatus::Unknown),
+            place: None,
+            postal_code: property.postal_code,
+            primary_residence: yes_no_unknown(&property.primary_residence),
+            province: property.province,
+            quota: None,
+            register_inscription: property.register_inscription,
+            visitable: yes_no_unknown(&property.visitable),
+        }
+    }
+}
+
+impl From<Vehicle> for types::Vehicle {
+    fn from(vehicle: Vehicle) -> Self {
+        let plate = vehicle.license_plate.parse::<LicensePlate>().ok();
+        let registration_period = if vehicle.licensed_date == default_date() {
+            plate.as_ref().and_then(LicensePlate::registration_period)
+        } else {
+            None
+        };
+
+        types::Vehicle {
+            attachments: Vec::new(),
+            auction_id: vehicle.auction_id,
+            bidinfo: vehicle.bidinfo,
+            brand: vehicle.brand,
+            category: vehicle.category,
+            charges: vehicle.charges,
+            encumbrances: Encumbrance::from_text(&vehicle.description),
+            description: vehicle.description,
+            vin: vehicle.frame_number.parse().ok(),
+            frame_number: vehicle.frame_number,
+            licensed_date: vehicle.licensed_date,
+            license_plate: vehicle.license_plate,
+            localization: vehicle.localization,
+            lot: None,
+            model: vehicle.model,
+            plate,
+            registration_period,
+            visitable: yes_no_unknown(&vehicle.visitable),
+        }
+    }
+}
+
+impl From<Other> for types::Other {
+    fn from(other: Other) -> Self {
+        let mut encumbrances = Encumbrance::from_text(&other.additional_information);
+        encumbrances.extend(Encumbrance::from_text(&other.description));
+
+        types::Other {
+            acquisition_date: None,
+            additional_information: other.additional_information,
+            attachments: Vec::new(),
+            auction_id: other.auction_id,
+            bidinfo: other.bidinfo,
+            category: other.category,
+            charges: other.charges,
+            description: other.description,
+            encumbrances,
+            judicial_title: other.judicial_title,
+            lot: None,
+            quota: None,
+            visitable: yes_no_unknown(&other.visitable),
+        }
+    }
+}
+
+impl From<Asset> for types::Asset {
+    fn from(asset: Asset) -> Self {
+        match asset {
+            Asset::Other(other) => types::Asset::Other(other.into()),
+            Asset::Property(property) => types::Asset::Property(property.into()),
+            Asset::Vehicle(vehicle) => types::Asset::Vehicle(vehicle.into()),
+        }
+    }
+}
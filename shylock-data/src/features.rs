@@ -0,0 +1,459 @@
+use crate::types::parse_measure;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// How reliable an extracted value is.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd, Deserialize, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum Confidence {
+    /// Guessed from loose wording.
+    Low,
+    /// Found next to an ambiguous keyword.
+    Medium,
+    /// Found next to an unambiguous keyword.
+    High,
+}
+
+/// Value extracted from free text along with its confidence.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Extracted<T> {
+    /// Extracted value.
+    pub value: T,
+    /// How reliable the value is.
+    pub confidence: Confidence,
+}
+
+impl<T> Extracted<T> {
+    fn new(value: T, confidence: Confidence) -> Self {
+        Extracted { value, confidence }
+    }
+}
+
+/// Annex auctioned together with a property.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum Annex {
+    /// Garage lot (plaza de garaje).
+    Garage,
+    /// Storage room (trastero).
+    StorageRoom,
+}
+
+/// Property features found in the description text.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PropertyFeatures {
+    /// Annexes like garage lots or storage rooms.
+    pub annexes: Vec<Extracted<Annex>>,
+    /// Number of bathrooms and toilets.
+    pub bathrooms: Option<Extracted<u32>>,
+    /// Built area in m².
+    pub built_area: Option<Extracted<Decimal>>,
+    /// Registry finca number.
+    pub finca_number: Option<Extracted<String>>,
+    /// Floor, a number or BAJO, ATICO, ENTRESUELO, SEMISOTANO or SOTANO.
+    pub floor: Option<Extracted<String>>,
+    /// Number of bedrooms.
+    pub rooms: Option<Extracted<u32>>,
+    /// Usable area in m².
+    pub usable_area: Option<Extracted<Decimal>>,
+}
+
+const NUMBER: &str = r"(\d{1,3}(?:\.\d{3})+(?:,\d+)?|\d+(?:[.,]\d+)?)";
+const AREA_UNIT: &str = r"(?:M2|M\.2|MTS\.?2|METROS CUADRADOS|MTS\.? CUADRADOS)";
+const COUNT: &str = r"(\d{1,2}|UNA?|UNO|DOS|TRES|CUATRO|CINCO|SEIS|SIETE|OCHO|NUEVE|DIEZ)";
+const FLOOR: &str = r"(BAJA|BAJO|PRIMER[AO]?|SEGUND[AO]|TERCER[AO]?|CUART[AO]|QUINT[AO]|SEXT[AO]|SEPTIM[AO]|OCTAV[AO]|NOVEN[AO]|DECIM[AO]|\d{1,2}[ºª]?|SEMISOTANO|SOTANO|ATICO|ENTRESUELO|ENTREPLANTA)";
+
+static BUILT_AREA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"CONSTRUIDAS?(?: TOTAL)?(?: APROXIMADA)?(?: DE)?:? {n} ?{u}|{n} ?{u} CONSTRUIDOS",
+        n = NUMBER,
+        u = AREA_UNIT
+    ))
+    .expect("built area regex creation failed")
+});
+static USABLE_AREA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"\bUTIL(?: APROXIMADA)?(?: DE)?:? {n} ?{u}|{n} ?{u} UTILES",
+        n = NUMBER,
+        u = AREA_UNIT
+    ))
+    .expect("usable area regex creation failed")
+});
+static AREA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"SUPERFICIE(?: DE)?:? {n} ?{u}",
+        n = NUMBER,
+        u = AREA_UNIT
+    ))
+    .expect("area regex creation failed")
+});
+static BARE_AREA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"{n} ?{u}", n = NUMBER, u = AREA_UNIT))
+        .expect("bare area regex creation failed")
+});
+static ROOMS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"\b{c} (DORMITORIOS?|HABITACION(?:ES)?)\b",
+        c = COUNT
+    ))
+    .expect("rooms regex creation failed")
+});
+static BATHROOMS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"\b{c} (?:BANOS?|ASEOS?|CUARTOS? DE BANO)\b",
+        c = COUNT
+    ))
+    .expect("bathrooms regex creation failed")
+});
+static BATHROOM_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bBANO\b").expect("bathroom regex creation failed"));
+static PLANTA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"\bPLANTA {f}(?:\b|$)", f = FLOOR)).expect("planta regex creation failed")
+});
+static PISO_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"\bPISO {f}(?:\b|$)", f = FLOOR)).expect("piso regex creation failed")
+});
+static FLOOR_DOOR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(BAJO|ATICO|ENTRESUELO|\d{1,2}[ºª])\s?-\s?(?:\d{1,2}[ºª]?|[A-Z])\b")
+        .expect("floor door regex creation failed")
+});
+static FLOOR_WORD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(BAJO|ATICO|ENTRESUELO|SEMISOTANO|SOTANO)\b")
+        .expect("floor word regex creation failed")
+});
+static FINCA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bFINCA (?:REGISTRAL |N[º°O]\.? ?|NUMERO |NUM\. ?)+(\d{1,3}(?:\.\d{3})+|\d+)\b")
+        .expect("finca regex creation failed")
+});
+static BARE_FINCA_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bFINCA (\d{1,3}(?:\.\d{3})+|\d+)\b").expect("bare finca regex creation failed")
+});
+static ANNEX_KEYWORD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:ANEJOS?|ANEXOS?|VINCULAD[OA]S?)\b")
+        .expect("annex keyword regex creation failed")
+});
+static ANNEX_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(GARAJE|APARCAMIENTO|COCHERA|TRASTERO)\b").expect("annex regex creation failed")
+});
+static WITH_ANNEX_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:CON|Y) (?:UNA |UN |LA |EL )?(?:PLAZA DE )?(GARAJE|APARCAMIENTO|TRASTERO)\b")
+        .expect("with annex regex creation failed")
+});
+
+// Uppercase, remove accents and collapse whitespace so rules can be simple.
+fn fold(text: &str) -> String {
+    text.to_uppercase()
+        .chars()
+        .map(|c| match c {
+            'Á' | 'À' => 'A',
+            'É' | 'È' => 'E',
+            'Í' | 'Ï' => 'I',
+            'Ó' | 'Ò' => 'O',
+            'Ú' | 'Ü' => 'U',
+            'Ñ' => 'N',
+            '²' => '2',
+            _ => c,
+        })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+}
+
+fn parse_count(count: &str) -> Option<u32> {
+    match count {
+        "UN" | "UNA" | "UNO" => Some(1),
+        "DOS" => Some(2),
+        "TRES" => Some(3),
+        "CUATRO" => Some(4),
+        "CINCO" => Some(5),
+        "SEIS" => Some(6),
+        "SIETE" => Some(7),
+        "OCHO" => Some(8),
+        "NUEVE" => Some(9),
+        "DIEZ" => Some(10),
+        _ => count.parse::<u32>().ok(),
+    }
+}
+
+fn normalize_floor(floor: &str) -> String {
+    match floor {
+        "BAJA" | "BAJO" => "BAJO".to_owned(),
+        "ENTREPLANTA" => "ENTRESUELO".to_owned(),
+        "SEMISOTANO" | "SOTANO" | "ATICO" | "ENTRESUELO" => floor.to_owned(),
+        _ if floor.starts_with("PRIMER") => "1".to_owned(),
+        _ if floor.starts_with("SEGUND") => "2".to_owned(),
+        _ if floor.starts_with("TERCER") => "3".to_owned(),
+        _ if floor.starts_with("CUART") => "4".to_owned(),
+        _ if floor.starts_with("QUINT") => "5".to_owned(),
+        _ if floor.starts_with("SEXT") => "6".to_owned(),
+        _ if floor.starts_with("SEPTIM") => "7".to_owned(),
+        _ if floor.starts_with("OCTAV") => "8".to_owned(),
+        _ if floor.starts_with("NOVEN") => "9".to_owned(),
+        _ if floor.starts_with("DECIM") => "10".to_owned(),
+        _ => floor.trim_end_matches(['º', 'ª']).to_owned(),
+    }
+}
+
+// First capture group matched by `re`, rules have alternatives so any group may hold it.
+fn first_capture<'t>(re: &Regex, text: &'t str) -> Option<&'t str> {
+    re.captures(text).and_then(|captures| {
+        captures
+            .iter()
+            .skip(1)
+            .flatten()
+            .next()
+            .map(|capture| capture.as_str())
+    })
+}
+
+fn extract_area(re: &Regex, text: &str, confidence: Confidence) -> Option<Extracted<Decimal>> {
+    first_capture(re, text)
+        .and_then(parse_measure)
+        .map(|area| Extracted::new(area, confidence))
+}
+
+fn extract_built_area(text: &str) -> Option<Extracted<Decimal>> {
+    extract_area(&BUILT_AREA_RE, text, Confidence::High)
+        .or_else(|| extract_area(&AREA_RE, text, Confidence::Medium))
+        .or_else(|| {
+            // A lonely measure is the built area unless it's the usable one.
+            if USABLE_AREA_RE.is_match(text) {
+                None
+            } else {
+                extract_area(&BARE_AREA_RE, text, Confidence::Low)
+            }
+        })
+}
+
+fn extract_rooms(text: &str) -> Option<Extracted<u32>> {
+    ROOMS_RE.captures(text).and_then(|captures| {
+        let confidence = if captures[2].starts_with("DORMITORIO") {
+            Confidence::High
+        } else {
+            Confidence::Medium
+        };
+        parse_count(&captures[1]).map(|rooms| Extracted::new(rooms, confidence))
+    })
+}
+
+fn extract_bathrooms(text: &str) -> Option<Extracted<u32>> {
+    let bathrooms: u32 = BATHROOMS_RE
+        .captures_iter(text)
+        .filter_map(|captures| parse_count(&captures[1]))
+        .sum();
+
+    // Houses list a bathroom in each floor distribution without a count.
+    let mentions = BATHROOM_RE.find_iter(text).count() as u32;
+
+    if bathrooms > 0 {
+        Some(Extracted::new(bathrooms, Confidence::High))
+    } else if mentions > 0 {
+        Some(Extracted::new(mentions, Confidence::Medium))
+    } else {
+        None
+    }
+}
+
+fn extract_floor(text: &str) -> Option<Extracted<String>> {
+    [
+        (&*PLANTA_RE, Confidence::High),
+        (&*PISO_RE, Confidence::Medium),
+        (&*FLOOR_DOOR_RE, Confidence::Medium),
+        (&*FLOOR_WORD_RE, Confidence::Low),
+    ]
+    .iter()
+    .find_map(|(re, confidence)| {
+        first_capture(re, text).map(|floor| Extracted::new(normalize_floor(floor), *confidence))
+    })
+}
+
+fn extract_finca_number(text: &str) -> Option<Extracted<String>> {
+    [
+        (&*FINCA_RE, Confidence::High),
+        (&*BARE_FINCA_RE, Confidence::Medium),
+    ]
+    .iter()
+    .find_map(|(re, confidence)| {
+        first_capture(re, text).map(|finca| Extracted::new(finca.replace('.', ""), *confidence))
+    })
+}
+
+fn annex_from_word(word: &str) -> Annex {
+    if word == "TRASTERO" {
+        Annex::StorageRoom
+    } else {
+        Annex::Garage
+    }
+}
+
+fn extract_annexes(text: &str) -> Vec<Extracted<Annex>> {
+    let mut annexes: Vec<Extracted<Annex>> = Vec::new();
+    let mut push = |annex: Annex, confidence: Confidence| {
+        if !annexes.iter().any(|extracted| extracted.value == annex) {
+            annexes.push(Extracted::new(annex, confidence));
+        }
+    };
+
+    if let Some(keyword) = ANNEX_KEYWORD_RE.find(text) {
+        ANNEX_RE
+            .captures_iter(&text[keyword.end()..])
+            .for_each(|captures| push(annex_from_word(&captures[1]), Confidence::High));
+    }
+    WITH_ANNEX_RE
+        .captures_iter(text)
+        .for_each(|captures| push(annex_from_word(&captures[1]), Confidence::Medium));
+
+    annexes
+}
+
+impl PropertyFeatures {
+    /// Extract property features from a BOE property `description`.
+    pub fn from_description(description: &str) -> Self {
+        let text = fold(description);
+        // Annexes usually have their own floor, i.e. "ANEJO: TRASTERO EN PLANTA SOTANO".
+        let main_text = match ANNEX_KEYWORD_RE.find(&text) {
+            Some(keyword) => &text[..keyword.start()],
+            None => &text[..],
+        };
+
+        PropertyFeatures {
+            annexes: extract_annexes(&text),
+            bathrooms: extract_bathrooms(&text),
+            built_area: extract_built_area(&text),
+            finca_number: extract_finca_number(&text),
+            floor: extract_floor(main_text),
+            rooms: extract_rooms(&text),
+            usable_area: extract_area(&USABLE_AREA_RE, &text, Confidence::High),
+        }
+    }
+
+    /// Returns true if nothing could be extracted.
+    pub fn is_empty(&self) -> bool {
+        *self == PropertyFeatures::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Fixture {
+        description: String,
+        built_area: Option<String>,
+        usable_area: Option<String>,
+        rooms: Option<u32>,
+        bathrooms: Option<u32>,
+        floor: Option<String>,
+        finca_number: Option<String>,
+        annexes: Vec<Annex>,
+    }
+
+    const FIXTURES: &str = include_str!("../fixtures/property_descriptions.json");
+
+    fn value<T: Clone>(extracted: &Option<Extracted<T>>) -> Option<T> {
+        extracted.as_ref().map(|extracted| extracted.value.clone())
+    }
+
+    #[test]
+    fn property_features_corpus_test() {
+        let fixtures: Vec<Fixture> = serde_json::from_str(FIXTURES).unwrap();
+        assert!(!fixtures.is_empty());
+
+        for fixture in fixtures {
+            let features = PropertyFeatures::from_description(&fixture.description);
+            let description = &fixture.description;
+
+            assert_eq!(
+                fixture
+                    .built_area
+                    .map(|area| area.parse::<Decimal>().unwrap()),
+                value(&features.built_area),
+                "built area of {}",
+                description
+            );
+            assert_eq!(
+                fixture
+                    .usable_area
+                    .map(|area| area.parse::<Decimal>().unwrap()),
+                value(&features.usable_area),
+                "usable area of {}",
+                description
+            );
+            assert_eq!(
+                fixture.rooms,
+                value(&features.rooms),
+                "rooms of {}",
+                description
+            );
+            assert_eq!(
+                fixture.bathrooms,
+                value(&features.bathrooms),
+                "bathrooms of {}",
+                description
+            );
+            assert_eq!(
+                fixture.floor,
+                value(&features.floor),
+                "floor of {}",
+                description
+            );
+            assert_eq!(
+                fixture.finca_number,
+                value(&features.finca_number),
+                "finca number of {}",
+                description
+            );
+            assert_eq!(
+                fixture.annexes,
+                features
+                    .annexes
+                    .iter()
+                    .map(|annex| annex.value)
+                    .collect::<Vec<Annex>>(),
+                "annexes of {}",
+                description
+            );
+        }
+    }
+
+    #[test]
+    fn property_features_confidence_test() {
+        let features = PropertyFeatures::from_description(
+            "FINCA URBANA SITUADA EN VALLADOLID, CALLE MARIANO DE LOS COBOS NUM.90, BAJO-1º",
+        );
+        assert_eq!(
+            Some(Extracted::new("BAJO".to_owned(), Confidence::Medium)),
+            features.floor
+        );
+        assert_eq!(None, features.finca_number);
+        assert_eq!(None, features.built_area);
+
+        let features = PropertyFeatures::from_description(
+            "Vivienda en planta segunda con una superficie de 85,20 m², \
+             dos habitaciones y baño, con plaza de garaje.",
+        );
+        assert_eq!(
+            Some(Extracted::new("2".to_owned(), Confidence::High)),
+            features.floor
+        );
+        assert_eq!(
+            Some(Extracted::new(Decimal::new(8520, 2), Confidence::Medium)),
+            features.built_area
+        );
+        assert_eq!(Some(Extracted::new(2, Confidence::Medium)), features.rooms);
+        assert_eq!(
+            Some(Extracted::new(1, Confidence::Medium)),
+            features.bathrooms
+        );
+        assert_eq!(
+            vec![Extracted::new(Annex::Garage, Confidence::Medium)],
+            features.annexes
+        );
+
+        assert!(PropertyFeatures::from_description("LOCAL COMERCIAL").is_empty());
+    }
+}
@@ -8,19 +8,60 @@
 
 //! Main data structures holding information about spanish auctions.
 
+/// Documents and photos attached to auctions
+pub mod attachments;
+
 /// Different asset categories
 pub mod categories;
 
+/// Spain autonomous communities
+pub mod communities;
+
 /// Auction concepts
 pub mod concepts;
 
+/// Acquisition costs
+pub mod costs;
+
+/// Bid economics
+pub mod economics;
+
+/// Encumbrances extraction
+pub mod encumbrances;
+
+/// Frontend export format
+pub mod export;
+
+/// Property features extraction
+pub mod features;
+
+/// Spain municipalities gazetteer
+pub mod municipalities;
+
+/// Concluded auction outcomes
+pub mod outcomes;
+
 /// Spain provinces
 pub mod provinces;
 
 /// Auction types
 pub mod types;
 
+/// Vehicle identity
+pub mod vehicles;
+
+pub use self::attachments::{Attachment, AttachmentKind};
+pub use self::costs::{
+    Acquisition, AcquisitionCost, BidApproval, CostAsset, CostConcept, CostItem, CostRates, Seller,
+};
+pub use self::economics::{OpportunityConfig, OpportunityFactor, OpportunityScore};
+pub use self::encumbrances::{Encumbrance, EncumbranceKind};
+pub use self::export::{Export, ExportCounts, ExportData, ExportError, ExportHeader};
+pub use self::features::{Annex, Confidence, Extracted, PropertyFeatures};
+pub use self::municipalities::Municipality;
+pub use self::outcomes::{price_statistics, AuctionOutcome, OutcomeKind, PriceStatistics};
 pub use self::types::*;
-pub use chrono::NaiveDate;
+pub use self::vehicles::{LicensePlate, RegistrationPeriod, Vin};
+pub use chrono::{DateTime, FixedOffset, NaiveDate};
 pub use geo_types::Point;
 pub use rust_decimal::Decimal;
@@ -0,0 +1,334 @@
+use crate::provinces::Province;
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Semicolon separated: INE code, official name and `|` separated alternative names.
+const MUNICIPALITIES_CSV: &str = include_str!("../data/municipalities.csv");
+
+// INE dictionary names move the article to the end, i.e. "Rozas de Madrid, Las".
+const INE_TRAILING_ARTICLES: &[&str] = &[
+    "A", "As", "El", "Els", "Es", "L'", "La", "Las", "Les", "Los", "O", "Os", "Sa", "Ses",
+];
+
+// Words ignored when comparing city names: articles, prepositions and conjunctions
+// in spanish, catalan and galician.
+const STOP_WORDS: &[&str] = &[
+    "A", "AS", "D", "DE", "DEL", "EL", "ELS", "ES", "I", "L", "LA", "LAS", "LES", "LOS", "O", "OS",
+    "SA", "SES", "Y",
+];
+
+/// Spanish municipality as registered by INE.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct Municipality {
+    /// Five digits INE code, the first two are the province code.
+    pub ine_code: String,
+    /// Official name, with both languages where co-official.
+    pub name: String,
+    /// Co-official and common alternative names.
+    pub alternative_names: Vec<String>,
+    /// Province the municipality belongs to.
+    pub province: Province,
+}
+
+static MUNICIPALITIES: Lazy<Vec<Municipality>> = Lazy::new(|| {
+    MUNICIPALITIES_CSV
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut fields = line.split(';');
+            let ine_code = fields.next().unwrap_or_default().to_owned();
+            let name = fields.next().unwrap_or_default().to_owned();
+            let alternative_names = fields
+                .next()
+                .unwrap_or_default()
+                .split('|')
+                .filter(|name| !name.is_empty())
+                .map(str::to_owned)
+                .collect();
+            let province = Province::from_ine_code(&ine_code[..2]).unwrap_or(Province::Unknown);
+
+            Municipality {
+                ine_code,
+                name,
+                alternative_names,
+                province,
+            }
+        })
+        .collect()
+});
+
+static MUNICIPALITY_KEYS: Lazy<HashMap<String, Vec<&'static Municipality>>> = Lazy::new(|| {
+    let mut keys: HashMap<String, Vec<&'static Municipality>> = HashMap::new();
+
+    for municipality in MUNICIPALITIES.iter() {
+        let mut names = vec![&municipality.name[..]];
+        names.extend(municipality.name.split('/'));
+        names.extend(municipality.alternative_names.iter().map(|name| &name[..]));
+
+        for name in names {
+            let candidates = keys.entry(normalize_city(name)).or_default();
+            if !candidates.contains(&municipality) {
+                candidates.push(municipality);
+            }
+        }
+    }
+
+    keys
+});
+
+/// Normalize a city name to compare it: upper case without accents,
+/// punctuation or articles.
+pub fn normalize_city(city: &str) -> String {
+    let city: String = city
+        .to_uppercase()
+        .chars()
+        .map(|x| match x {
+            'Á' | 'À' | 'Ä' => 'A',
+            'É' | 'È' | 'Ë' => 'E',
+            'Í' | 'Ì' | 'Ï' => 'I',
+            'Ó' | 'Ò' | 'Ö' => 'O',
+            'Ú' | 'Ù' | 'Ü' => 'U',
+            'Ç' => 'C',
+            _ if x.is_alphanumeric() => x,
+            _ => ' ',
+        })
+        .collect();
+
+    city.split_whitespace()
+        .filter(|word| !STOP_WORDS.contains(word))
+        .collect()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// Put trailing articles of each INE name back in front: "Coruña, A" -> "A Coruña".
+fn ine_display_name(name: &str) -> String {
+    name.split('/')
+        .map(|name| match name.rsplit_once(", ") {
+            Some((base, article)) if INE_TRAILING_ARTICLES.contains(&article) => {
+                if article.ends_with('\'') {
+                    format!("{}{}", article, base)
+                } else {
+                    format!("{} {}", article, base)
+                }
+            }
+            _ => name.to_owned(),
+        })
+        .collect::<Vec<String>>()
+        .join("/")
+}
+
+fn in_province(municipality: &Municipality, province: Province) -> bool {
+    matches!(province, Province::Unknown | Province::All) || municipality.province == province
+}
+
+impl Municipality {
+    /// Returns all known municipalities.
+    pub fn all() -> &'static [Municipality] {
+        &MUNICIPALITIES
+    }
+
+    /// Returns the municipality with INE `code`.
+    pub fn from_ine_code(code: &str) -> Option<&'static Municipality> {
+        MUNICIPALITIES
+            .iter()
+            .find(|municipality| municipality.ine_code == code.trim())
+    }
+
+    /// Parse the municipalities of an INE dictionary (diccionario de municipios)
+    /// saved as semicolon separated CSV with columns CODAUTO, CPRO, CMUN, DC and NOMBRE,
+    /// alternative names of the embedded dataset are kept.
+    pub fn from_ine_dictionary(dictionary: &str) -> Vec<Municipality> {
+        dictionary
+            .lines()
+            .filter_map(|line| {
+                let fields: Vec<&str> = line.split(';').map(str::trim).collect();
+                if fields.len() < 5 || !fields[1].chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+
+                let province = Province::from_ine_code(fields[1]).ok()?;
+                let ine_code = format!("{:0>2}{:0>3}", fields[1], fields[2]);
+                let alternative_names = Municipality::from_ine_code(&ine_code)
+                    .map(|known| known.alternative_names.clone())
+                    .unwrap_or_default();
+
+                Some(Municipality {
+                    name: ine_display_name(fields[4]),
+                    ine_code,
+                    alternative_names,
+                    province,
+                })
+            })
+            .collect()
+    }
+
+    /// Line of the embedded dataset for this municipality.
+    pub fn to_csv_line(&self) -> String {
+        format!(
+            "{};{};{}",
+            self.ine_code,
+            self.name,
+            self.alternative_names.join("|")
+        )
+    }
+
+    /// Find the municipality a BOE `city` refers to inside `province`,
+    /// any province is searched if it is unknown.
+    /// Small typos are tolerated when there is a single close candidate.
+    pub fn find(city: &str, province: Province) -> Option<&'static Municipality> {
+        for name in city.split('/') {
+            let key = normalize_city(name);
+            if key.is_empty() {
+                continue;
+            }
+
+            if let Some(municipality) = MUNICIPALITY_KEYS.get(&key).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .find(|municipality| in_province(municipality, province))
+            }) {
+                return Some(municipality);
+            }
+
+            let max_distance = match key.chars().count() {
+                0..=4 => continue,
+                5..=9 => 1,
+                _ => 2,
+            };
+            let mut close: Vec<&'static Municipality> = MUNICIPALITY_KEYS
+                .iter()
+                .filter(|(candidate, _)| edit_distance(&key, candidate) <= max_distance)
+                .flat_map(|(_, municipalities)| municipalities.iter().copied())
+                .filter(|municipality| in_province(municipality, province))
+                .collect();
+            close.sort_by(|a, b| a.ine_code.cmp(&b.ine_code));
+            close.dedup();
+
+            if close.len() == 1 {
+                return close.pop();
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_city_test() {
+        assert_eq!("VALLADOLID", normalize_city("Valladolid "));
+        assert_eq!("SANTCUGATVALLES", normalize_city("SANT CUGAT DEL VALLÈS"));
+        assert_eq!("CORUÑA", normalize_city("CORUÑA (A)"));
+        assert_eq!(
+            "HOSPITALETLLOBREGAT",
+            normalize_city("L'HOSPITALET DE LLOBREGAT")
+        );
+    }
+
+    #[test]
+    fn municipality_from_ine_dictionary_test() {
+        let dictionary = "CODAUTO;CPRO;CMUN;DC;NOMBRE\n\
+            07;47;186;5;Valladolid\n\
+            13;28;127;3;Rozas de Madrid, Las\n\
+            09;08;101;5;Hospitalet de Llobregat, L'\n\
+            12;15;030;2;Coruña, A\n";
+
+        let municipalities = Municipality::from_ine_dictionary(dictionary);
+        assert_eq!(4, municipalities.len());
+        assert_eq!(
+            Municipality::from_ine_code("47186").unwrap(),
+            &municipalities[0]
+        );
+        assert_eq!("Las Rozas de Madrid", municipalities[1].name);
+        assert_eq!(Province::Madrid, municipalities[1].province);
+        assert_eq!("L'Hospitalet de Llobregat", municipalities[2].name);
+        assert_eq!("A Coruña", municipalities[3].name);
+        assert_eq!(
+            "15030;A Coruña;La Coruña|Coruña",
+            municipalities[3].to_csv_line()
+        );
+    }
+
+    #[test]
+    fn municipality_find_test() {
+        let valladolid = Municipality::from_ine_code("47186").unwrap();
+        assert_eq!(Province::Valladolid, valladolid.province);
+        assert_eq!(
+            Some(valladolid),
+            Municipality::find("VALLADOLID", Province::Valladolid)
+        );
+        assert_eq!(
+            Some(valladolid),
+            Municipality::find("Valladolid ", Province::Unknown)
+        );
+        assert_eq!(None, Municipality::find("VALLADOLID", Province::Madrid));
+
+        let sant_cugat = Municipality::from_ine_code("08205");
+        assert_eq!(
+            sant_cugat,
+            Municipality::find("SANT CUGAT DEL VALLES", Province::Barcelona)
+        );
+        assert_eq!(
+            sant_cugat,
+            Municipality::find("Sant Cugat del Vallés", Province::Barcelona)
+        );
+
+        let coruna = Municipality::from_ine_code("15030");
+        assert_eq!(coruna, Municipality::find("LA CORUÑA", Province::ACorunia));
+        assert_eq!(coruna, Municipality::find("CORUÑA (A)", Province::ACorunia));
+
+        let alicante = Municipality::from_ine_code("03014");
+        assert_eq!(
+            alicante,
+            Municipality::find("ALICANTE/ALACANT", Province::Alicante)
+        );
+        assert_eq!(alicante, Municipality::find("ALACANT", Province::Alicante));
+
+        assert_eq!(
+            Municipality::from_ine_code("45168"),
+            Municipality::find("TOLEDOO", Province::Toledo)
+        );
+        assert_eq!(None, Municipality::find("NA", Province::Unknown));
+    }
+
+    #[test]
+    fn municipality_find_non_capital_test() {
+        for (city, province, ine_code) in [
+            ("TORRENTE", Province::Valencia, "46244"),
+            ("ORIHUELA", Province::Alicante, "03099"),
+            ("PONFERRADA", Province::Leon, "24115"),
+            ("TALAVERA DE LA REINA", Province::Toledo, "45165"),
+            ("ROZAS DE MADRID (LAS)", Province::Madrid, "28127"),
+            ("BARACALDO", Province::Vizcaya, "48013"),
+            ("SAN VICENTE DEL RASPEIG", Province::Alicante, "03122"),
+        ] {
+            assert_eq!(
+                Municipality::from_ine_code(ine_code),
+                Municipality::find(city, province),
+                "{}",
+                city
+            );
+        }
+    }
+}
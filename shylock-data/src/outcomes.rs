@@ -0,0 +1,267 @@
+use chrono::{DateTime, FixedOffset};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+
+use crate::categories::PropertyCategory;
+use crate::concepts::BoeConcept;
+use crate::provinces::Province;
+use crate::types::{get_datetime, get_decimal, BidInfo, DataError, Diagnostics};
+
+/// How a concluded auction or lot ended.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum OutcomeKind {
+    /// Someone bid and the asset was awarded.
+    Adjudicated,
+    /// Someone bid, the award is not published yet.
+    WithBids,
+    /// Nobody bid.
+    Deserted,
+    /// Not stated in the BOE
+    Unknown,
+}
+
+impl OutcomeKind {
+    /// Human readable name.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Adjudicated => "Adjudicada",
+            Self::WithBids => "Con pujas",
+            Self::Deserted => "Desierta",
+            Self::Unknown => "No consta",
+        }
+    }
+}
+
+impl FromStr for OutcomeKind {
+    type Err = ();
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let s = s.to_uppercase();
+
+        if s.contains("DESIERTA") || s.contains("SIN PUJAS") {
+            Ok(OutcomeKind::Deserted)
+        } else if s.contains("ADJUDICADA") {
+            Ok(OutcomeKind::Adjudicated)
+        } else if s.contains("CON PUJAS") {
+            Ok(OutcomeKind::WithBids)
+        } else {
+            Ok(OutcomeKind::Unknown)
+        }
+    }
+}
+
+/// Final outcome of a concluded auction or one of its lots.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AuctionOutcome {
+    /// Auction concluded.
+    pub auction_id: String,
+    /// Lot concluded, `None` for auctions without lots.
+    pub lot: Option<u32>,
+    /// How the auction ended.
+    pub kind: OutcomeKind,
+    /// Highest bid received, if any.
+    pub highest_bid: Option<Decimal>,
+    /// When the auction concluded.
+    pub date: DateTime<FixedOffset>,
+}
+
+impl AuctionOutcome {
+    /// Create the outcome of `auction` `lot` from its BOE bids concepts,
+    /// `end_date` is used when the conclusion date is not published.
+    pub fn try_new(
+        auction: &str,
+        lot: Option<u32>,
+        end_date: DateTime<FixedOffset>,
+        data: &HashMap<BoeConcept, String>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<AuctionOutcome, DataError> {
+        let highest_bid = match data.get(&BoeConcept::HighestBid) {
+            Some(_) => Some(diagnostics.check(
+                get_decimal(auction, data, &BoeConcept::HighestBid),
+                Decimal::ZERO,
+            )?),
+            None => None,
+        }
+        .filter(|bid| *bid > Decimal::ZERO);
+
+        let kind = match data.get(&BoeConcept::Outcome) {
+            Some(outcome) => outcome
+                .parse::<OutcomeKind>()
+                .unwrap_or(OutcomeKind::Unknown),
+            None => OutcomeKind::Unknown,
+        };
+        let kind = match (kind, highest_bid) {
+            (OutcomeKind::Unknown, Some(_)) => OutcomeKind::WithBids,
+            (kind, _) => kind,
+        };
+
+        let date = match data.get(&BoeConcept::EndDate) {
+            Some(_) => {
+                diagnostics.check(get_datetime(auction, data, &BoeConcept::EndDate), end_date)?
+            }
+            None => end_date,
+        };
+
+        Ok(AuctionOutcome {
+            auction_id: auction.to_owned(),
+            lot,
+            kind,
+            highest_bid,
+            date,
+        })
+    }
+
+    /// Highest bid over the appraisal of `bidinfo`, or its value without appraisal.
+    pub fn price_ratio(&self, bidinfo: &BidInfo) -> Option<Decimal> {
+        let reference = if bidinfo.appraisal > Decimal::ZERO {
+            bidinfo.appraisal
+        } else {
+            bidinfo.value
+        };
+
+        match (self.kind, self.highest_bid) {
+            (OutcomeKind::Adjudicated | OutcomeKind::WithBids, Some(bid))
+                if reference > Decimal::ZERO =>
+            {
+                Some(bid / reference)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Achieved prices of concluded property auctions in a province and category.
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct PriceStatistics {
+    /// Province of the properties.
+    pub province: Province,
+    /// Category of the properties.
+    pub category: PropertyCategory,
+    /// Concluded auctions.
+    pub auctions: u32,
+    /// Deserted auctions.
+    pub deserted: u32,
+    /// Average highest bid over appraisal of auctions with bids.
+    pub average_ratio: Option<Decimal>,
+}
+
+/// Group property `outcomes` with their bid information by province and category.
+pub fn price_statistics<'a>(
+    outcomes: impl IntoIterator<Item = (Province, PropertyCategory, &'a AuctionOutcome, BidInfo)>,
+) -> Vec<PriceStatistics> {
+    let mut groups: HashMap<(Province, PropertyCategory), (u32, u32, Vec<Decimal>)> =
+        HashMap::new();
+
+    for (province, category, outcome, bidinfo) in outcomes {
+        let (auctions, deserted, ratios) = groups.entry((province, category)).or_default();
+        *auctions += 1;
+        if outcome.kind == OutcomeKind::Deserted {
+            *deserted += 1;
+        }
+        ratios.extend(outcome.price_ratio(&bidinfo));
+    }
+
+    let mut statistics: Vec<PriceStatistics> = groups
+        .into_iter()
+        .map(
+            |((province, category), (auctions, deserted, ratios))| PriceStatistics {
+                province,
+                category,
+                auctions,
+                deserted,
+                average_ratio: if ratios.is_empty() {
+                    None
+                } else {
+                    Some((ratios.iter().sum::<Decimal>() / Decimal::from(ratios.len())).round_dp(4))
+                },
+            },
+        )
+        .collect();
+    statistics.sort_by_key(|statistics| (statistics.province, statistics.category));
+
+    statistics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DEFAULT_DECIMALS;
+
+    fn outcome(highest_bid: &str, outcome: &str) -> AuctionOutcome {
+        let data: HashMap<BoeConcept, String> = [
+            (BoeConcept::HighestBid, highest_bid.to_owned()),
+            (BoeConcept::Outcome, outcome.to_owned()),
+        ]
+        .into_iter()
+        .collect();
+
+        AuctionOutcome::try_new(
+            "SUB-NE-2020-465937",
+            None,
+            DateTime::parse_from_rfc3339("2020-08-03T18:00:00+02:00").unwrap(),
+            &data,
+            &mut Diagnostics::strict(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn auction_outcome_test() {
+        let with_bids = outcome("60.101,60 €", "Concluida con pujas");
+        assert_eq!(OutcomeKind::WithBids, with_bids.kind);
+        assert_eq!(
+            Some(Decimal::new(6_010_160, DEFAULT_DECIMALS)),
+            with_bids.highest_bid
+        );
+        assert_eq!(
+            OutcomeKind::Adjudicated,
+            outcome("60.101,60 €", "Subasta adjudicada").kind
+        );
+        assert_eq!(OutcomeKind::WithBids, outcome("60.101,60 €", "").kind);
+
+        let deserted = outcome("Sin pujas", "Subasta desierta");
+        assert_eq!(OutcomeKind::Deserted, deserted.kind);
+        assert_eq!(None, deserted.highest_bid);
+
+        let bidinfo = BidInfo {
+            appraisal: Decimal::new(7_512_700, DEFAULT_DECIMALS),
+            bid_step: Decimal::ZERO,
+            claim_quantity: Decimal::ZERO,
+            deposit: Decimal::ZERO,
+            minimum_bid: Decimal::ZERO,
+            value: Decimal::new(7_512_700, DEFAULT_DECIMALS),
+        };
+        assert_eq!(
+            Some(Decimal::new(8, 1)),
+            with_bids
+                .price_ratio(&bidinfo)
+                .map(|ratio| ratio.round_dp(2))
+        );
+        assert_eq!(None, deserted.price_ratio(&bidinfo));
+
+        let statistics = price_statistics([
+            (
+                Province::Valladolid,
+                PropertyCategory::Apartment,
+                &with_bids,
+                bidinfo,
+            ),
+            (
+                Province::Valladolid,
+                PropertyCategory::Apartment,
+                &deserted,
+                bidinfo,
+            ),
+        ]);
+        assert_eq!(1, statistics.len());
+        assert_eq!(2, statistics[0].auctions);
+        assert_eq!(1, statistics[0].deserted);
+        assert_eq!(
+            Some(Decimal::new(8, 1)),
+            statistics[0].average_ratio.map(|ratio| ratio.round_dp(2))
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::communities::AutonomousCommunity;
+
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -30,7 +32,7 @@ macro_rules! auction_provinces {
     (
         $(
             $(#[$docs:meta])*
-            ($konst:ident, $name:expr, $display:expr);
+            ($konst:ident, $name:expr, $display:expr, $community:ident, $ine:expr);
         )+
     ) => {
         /// Type of provinces
@@ -87,8 +89,60 @@ macro_rules! auction_provinces {
             pub fn name(&self) -> &str {
                 PROVINCES.get(self).unwrap_or(&"Unknown")
             }
+
+            /// Returns the autonomous community this province belongs to.
+            pub fn community(&self) -> AutonomousCommunity {
+                match *self {
+                    $(
+                    Province::$konst => AutonomousCommunity::$community,
+                    )+
+                }
+            }
+
+            /// Returns the two digits INE code, `None` for unknown and all.
+            pub fn ine_code(&self) -> Option<&'static str> {
+                match *self {
+                    $(
+                    Province::$konst => $ine,
+                    )+
+                }
+            }
+
+            /// Parse a province from its INE code, leading zero can be omitted.
+            pub fn from_ine_code(code: &str) -> Result<Province, InvalidProvince> {
+                let code = format!("{:0>2}", code.trim());
+
+                INE_PROVINCES
+                    .get(&code[..])
+                    .copied()
+                    .ok_or_else(|| InvalidProvince::new(&code))
+            }
+
+            /// Returns the province a spanish postal code belongs to,
+            /// its first two digits are the province INE code.
+            pub fn from_postal_code(postal_code: &str) -> Option<Province> {
+                let postal_code = postal_code.trim();
+
+                if postal_code.len() != 5 || !postal_code.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+
+                Province::from_ine_code(&postal_code[..2]).ok()
+            }
         }
 
+        static INE_PROVINCES: Lazy<HashMap<&str, Province>> = Lazy::new(|| {
+            let mut provinces: HashMap<&str, Province> = HashMap::new();
+
+            $(
+            if let Some(code) = $ine {
+                provinces.insert(code, Province::$konst);
+            }
+            )+
+
+            provinces
+        });
+
         static PROVINCES: Lazy<HashMap<Province, &str>> = Lazy::new(|| {
             let mut provinces: HashMap<Province, &str> = HashMap::new();
 
@@ -100,15 +154,15 @@ macro_rules! auction_provinces {
         });
 
         #[cfg(test)]
-        const TEST_PROVINCES: &'static [(Province, &'static str, &'static str)] = &[
+        const TEST_PROVINCES: &'static [(Province, &'static str, &'static str, Option<&'static str>)] = &[
             $(
-            (Province::$konst, $name, $display),
+            (Province::$konst, $name, $display, $ine),
             )+
         ];
 
         #[test]
         fn test_parse_province() {
-            for &(std, name, _) in TEST_PROVINCES {
+            for &(std, name, _, _) in TEST_PROVINCES {
                 // Test upper case
                 assert_eq!(name.parse::<Province>().unwrap(), std);
 
@@ -119,11 +173,40 @@ macro_rules! auction_provinces {
 
         #[test]
         fn test_province_name() {
-            for &(std, _, display) in TEST_PROVINCES {
+            for &(std, _, display, _) in TEST_PROVINCES {
                 assert_eq!(std.name(), display);
             }
         }
 
+        #[test]
+        fn test_province_ine_code() {
+            for &(std, _, _, ine) in TEST_PROVINCES {
+                assert_eq!(std.ine_code(), ine);
+                if let Some(code) = ine {
+                    assert_eq!(Province::from_ine_code(code).unwrap(), std);
+                }
+            }
+            assert_eq!(Province::from_ine_code("1").unwrap(), Province::Alava);
+            assert_eq!(Province::from_ine_code("53"), Err(InvalidProvince::new("53")));
+        }
+
+        #[test]
+        fn test_province_from_postal_code() {
+            assert_eq!(Province::from_postal_code("47010"), Some(Province::Valladolid));
+            assert_eq!(Province::from_postal_code("08001"), Some(Province::Barcelona));
+            assert_eq!(Province::from_postal_code(" 52006 "), Some(Province::Melilla));
+            assert_eq!(Province::from_postal_code("99001"), None);
+            assert_eq!(Province::from_postal_code("NA"), None);
+        }
+
+        #[test]
+        fn test_province_community() {
+            assert_eq!(Province::Vizcaya.community(), AutonomousCommunity::PaisVasco);
+            assert_eq!(Province::LasPalmas.community(), AutonomousCommunity::Canarias);
+            assert_eq!(Province::Unknown.community(), AutonomousCommunity::Unknown);
+            assert_eq!(Province::All.community(), AutonomousCommunity::All);
+        }
+
         #[test]
         fn test_parse_invalid_province() {
             let invalid_province = "non-sense";
@@ -134,164 +217,164 @@ macro_rules! auction_provinces {
 
 auction_provinces! {
     /// A Coruña province
-    (ACorunia, "ACORUÑA", "A Coruña");
+    (ACorunia, "ACORUÑA", "A Coruña", Galicia, Some("15"));
 
     /// Alava province
-    (Alava, "ALAVA", "Álava");
+    (Alava, "ALAVA", "Álava", PaisVasco, Some("01"));
 
     /// Albacete province
-    (Albacete, "ALBACETE", "Albacete");
+    (Albacete, "ALBACETE", "Albacete", CastillaLaMancha, Some("02"));
 
     /// Alicante province
-    (Alicante, "ALICANTE", "Alicante");
+    (Alicante, "ALICANTE", "Alicante", ComunidadValenciana, Some("03"));
 
     /// Almería province
-    (Almeria, "ALMERIA", "Almería");
+    (Almeria, "ALMERIA", "Almería", Andalucia, Some("04"));
 
     /// Asturias
-    (Asturias, "ASTURIAS", "Asturias");
+    (Asturias, "ASTURIAS", "Asturias", Asturias, Some("33"));
 
     /// Ávila province
-    (Avila, "AVILA", "Ávila");
+    (Avila, "AVILA", "Ávila", CastillaYLeon, Some("05"));
 
     /// Badajoz province
-    (Badajoz, "BADAJOZ", "Badajoz");
+    (Badajoz, "BADAJOZ", "Badajoz", Extremadura, Some("06"));
 
     /// Baleares province
-    (Baleares, "BALEARES", "Baleares");
+    (Baleares, "BALEARES", "Baleares", Baleares, Some("07"));
 
     /// Barcelona province
-    (Barcelona, "BARCELONA", "Barcelona");
+    (Barcelona, "BARCELONA", "Barcelona", Cataluna, Some("08"));
 
     /// Burgos province
-    (Burgos, "BURGOS", "Burgos");
+    (Burgos, "BURGOS", "Burgos", CastillaYLeon, Some("09"));
 
     /// Cáceres province
-    (Caceres, "CACERES", "Cáceres");
+    (Caceres, "CACERES", "Cáceres", Extremadura, Some("10"));
 
     /// Cádiz province
-    (Cadiz, "CADIZ", "Cádiz");
+    (Cadiz, "CADIZ", "Cádiz", Andalucia, Some("11"));
 
     /// Cantabria province
-    (Cantabria, "CANTABRIA", "Cantabria");
+    (Cantabria, "CANTABRIA", "Cantabria", Cantabria, Some("39"));
 
     /// Castellón province
-    (Castellon, "CASTELLON", "Castellón");
+    (Castellon, "CASTELLON", "Castellón", ComunidadValenciana, Some("12"));
 
     /// Ciudad Real province
-    (CiudadReal, "CIUDADREAL", "Ciudad Real");
+    (CiudadReal, "CIUDADREAL", "Ciudad Real", CastillaLaMancha, Some("13"));
 
     /// Córdoba province
-    (Cordoba, "CORDOBA", "Córdoba");
+    (Cordoba, "CORDOBA", "Córdoba", Andalucia, Some("14"));
 
     /// Cuenca province
-    (Cuenca, "CUENCA", "Cuenca");
+    (Cuenca, "CUENCA", "Cuenca", CastillaLaMancha, Some("16"));
 
     /// Girona province
-    (Gerona, "GERONA", "Gerona");
+    (Gerona, "GERONA", "Gerona", Cataluna, Some("17"));
 
     /// Granada province
-    (Granada, "GRANADA", "Granada");
+    (Granada, "GRANADA", "Granada", Andalucia, Some("18"));
 
     /// Guadalajara province
-    (Guadalajara, "GUADALAJARA", "Guadalajara");
+    (Guadalajara, "GUADALAJARA", "Guadalajara", CastillaLaMancha, Some("19"));
 
     /// Guipúzcoa province
-    (Guipuzcoa, "GUIPUZCOA", "Guipúzcoa");
+    (Guipuzcoa, "GUIPUZCOA", "Guipúzcoa", PaisVasco, Some("20"));
 
     /// Huelva province
-    (Huelva, "HUELVA", "Huelva");
+    (Huelva, "HUELVA", "Huelva", Andalucia, Some("21"));
 
     /// Huesca province
-    (Huesca, "HUESCA", "Huesca");
+    (Huesca, "HUESCA", "Huesca", Aragon, Some("22"));
 
     /// Jaén province
-    (Jaen, "JAEN", "Jaén");
+    (Jaen, "JAEN", "Jaén", Andalucia, Some("23"));
 
     /// León province
-    (Leon, "LEON", "León");
+    (Leon, "LEON", "León", CastillaYLeon, Some("24"));
 
     /// Lleida province
-    (Lerida, "LERIDA", "Lérida");
+    (Lerida, "LERIDA", "Lérida", Cataluna, Some("25"));
 
     /// La Rioja province
-    (LaRioja, "LARIOJA", "La Rioja");
+    (LaRioja, "LARIOJA", "La Rioja", LaRioja, Some("26"));
 
     /// Lugo province
-    (Lugo, "LUGO", "Lugo");
+    (Lugo, "LUGO", "Lugo", Galicia, Some("27"));
 
     /// Madrid province
-    (Madrid, "MADRID", "Madrid");
+    (Madrid, "MADRID", "Madrid", Madrid, Some("28"));
 
     /// Málaga province
-    (Malaga, "MALAGA", "Málaga");
+    (Malaga, "MALAGA", "Málaga", Andalucia, Some("29"));
 
     /// Murcia province
-    (Murcia, "MURCIA", "Murcia");
+    (Murcia, "MURCIA", "Murcia", Murcia, Some("30"));
 
     /// Navarra province
-    (Navarra, "NAVARRA", "Navarra");
+    (Navarra, "NAVARRA", "Navarra", Navarra, Some("31"));
 
     /// Ourense province
-    (Orense, "ORENSE", "Orense");
+    (Orense, "ORENSE", "Orense", Galicia, Some("32"));
 
     /// Palencia province
-    (Palencia, "PALENCIA", "Palencia");
+    (Palencia, "PALENCIA", "Palencia", CastillaYLeon, Some("34"));
 
     /// Las Palmas province
-    (LasPalmas, "LASPALMAS", "Las Palmas");
+    (LasPalmas, "LASPALMAS", "Las Palmas", Canarias, Some("35"));
 
     /// Pontevedra province
-    (Pontevedra, "PONTEVEDRA", "Pontevedra");
+    (Pontevedra, "PONTEVEDRA", "Pontevedra", Galicia, Some("36"));
 
     /// Salamanca province
-    (Salamanca, "SALAMANCA", "Salamanca");
+    (Salamanca, "SALAMANCA", "Salamanca", CastillaYLeon, Some("37"));
 
     /// Santa Cruz de Tenerife province
-    (SantaCruzDeTenerife, "SANTACRUZDETENERIFE", "Santa Cruz de Tenerife");
+    (SantaCruzDeTenerife, "SANTACRUZDETENERIFE", "Santa Cruz de Tenerife", Canarias, Some("38"));
 
     /// Segovia province
-    (Segovia, "SEGOVIA", "Segovia");
+    (Segovia, "SEGOVIA", "Segovia", CastillaYLeon, Some("40"));
 
     /// Sevilla province
-    (Sevilla, "SEVILLA", "Sevilla");
+    (Sevilla, "SEVILLA", "Sevilla", Andalucia, Some("41"));
 
     /// Soria province
-    (Soria, "SORIA", "Soria");
+    (Soria, "SORIA", "Soria", CastillaYLeon, Some("42"));
 
     /// Tarragona province
-    (Tarragona, "TARRAGONA", "Tarragona");
+    (Tarragona, "TARRAGONA", "Tarragona", Cataluna, Some("43"));
 
     /// Teruel province
-    (Teruel, "TERUEL", "Teruel");
+    (Teruel, "TERUEL", "Teruel", Aragon, Some("44"));
 
     ///  Toledo province
-    (Toledo, "TOLEDO", "Toledo");
+    (Toledo, "TOLEDO", "Toledo", CastillaLaMancha, Some("45"));
 
     /// Valencia province
-    (Valencia, "VALENCIA", "Valencia");
+    (Valencia, "VALENCIA", "Valencia", ComunidadValenciana, Some("46"));
 
     /// Valladolid province
-    (Valladolid, "VALLADOLID", "Valladolid");
+    (Valladolid, "VALLADOLID", "Valladolid", CastillaYLeon, Some("47"));
 
     /// Vizcaya province
-    (Vizcaya, "VIZCAYA", "Vizcaya");
+    (Vizcaya, "VIZCAYA", "Vizcaya", PaisVasco, Some("48"));
 
     /// Zamora province
-    (Zamora, "ZAMORA", "Zamora");
+    (Zamora, "ZAMORA", "Zamora", CastillaYLeon, Some("49"));
 
     /// Zaragoza province
-    (Zaragoza, "ZARAGOZA", "Zaragoza");
+    (Zaragoza, "ZARAGOZA", "Zaragoza", Aragon, Some("50"));
 
     /// Ceuta autonomous city
-    (Ceuta, "CEUTA", "Ceuta");
+    (Ceuta, "CEUTA", "Ceuta", Ceuta, Some("51"));
 
     /// Melilla autonomous city
-    (Melilla, "MELILLA", "Melilla");
+    (Melilla, "MELILLA", "Melilla", Melilla, Some("52"));
 
     /// Unkown
-    (Unknown, "UNKNOWN", "Desconocido");
+    (Unknown, "UNKNOWN", "Desconocido", Unknown, None);
 
     /// All
-    (All, "ALL", "All");
+    (All, "ALL", "All", All, None);
 }
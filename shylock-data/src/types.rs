@@ -1,12 +1,19 @@
+use crate::attachments::Attachment;
 use crate::categories::{OtherCategory, PropertyCategory, VehicleCategory};
 use crate::concepts::BoeConcept;
+use crate::encumbrances::Encumbrance;
+use crate::features::PropertyFeatures;
+use crate::municipalities::Municipality;
 use crate::provinces::Province;
+use crate::vehicles::{LicensePlate, RegistrationPeriod, Vin};
 
-use chrono::NaiveDate;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+use chrono_tz::Europe::Madrid;
 use geo_types::Point;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
@@ -14,6 +21,149 @@ use std::str::FromStr;
 pub const DEFAULT_DECIMALS: u32 = 2;
 const NOT_APPLICABLE: &str = "NA";
 
+/// Kind of problem found in a BOE concept value.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum DataErrorKind {
+    /// A mandatory concept is not present.
+    Missing,
+    /// The value is not a valid amount.
+    InvalidDecimal,
+    /// The value is not a valid date.
+    InvalidDate,
+    /// The value is not a valid registry identifier (IDUFIR).
+    InvalidIdufir,
+    /// The value is not a valid catastro reference.
+    InvalidCatastroReference,
+    /// The value looks like a VIN but it is not valid.
+    InvalidVin,
+    /// The asset header doesn't follow the "BIEN X - CATEGORY (SUBCATEGORY)" format.
+    InvalidHeader,
+    /// The value is not a known province.
+    UnknownProvince,
+    /// The value is not a known asset category.
+    UnknownCategory,
+}
+
+impl Display for DataErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Missing => write!(f, "missing value"),
+            Self::InvalidDecimal => write!(f, "invalid amount"),
+            Self::InvalidDate => write!(f, "invalid date"),
+            Self::InvalidIdufir => write!(f, "invalid IDUFIR"),
+            Self::InvalidCatastroReference => write!(f, "invalid catastro reference"),
+            Self::InvalidVin => write!(f, "invalid VIN"),
+            Self::InvalidHeader => write!(f, "invalid asset header"),
+            Self::UnknownProvince => write!(f, "unknown province"),
+            Self::UnknownCategory => write!(f, "unknown category"),
+        }
+    }
+}
+
+/// Error building auction types from BOE concepts.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DataError {
+    /// Auction the concept belongs to.
+    pub auction_id: String,
+    /// Offending concept.
+    pub concept: BoeConcept,
+    /// Raw value found in the BOE page.
+    pub value: String,
+    /// Kind of problem.
+    pub kind: DataErrorKind,
+}
+
+impl DataError {
+    fn new(auction_id: &str, concept: BoeConcept, value: &str, kind: DataErrorKind) -> Self {
+        DataError {
+            auction_id: auction_id.to_owned(),
+            concept,
+            value: value.to_owned(),
+            kind,
+        }
+    }
+}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "auction {}: {} in {:?}: ->{}<-",
+            self.auction_id, self.kind, self.concept, self.value
+        )
+    }
+}
+
+impl Error for DataError {}
+
+/// How strict the constructors are with malformed BOE concepts.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ParseMode {
+    /// Fail on the first malformed concept.
+    Strict,
+    /// Use a default value for malformed concepts and record a warning.
+    Lenient,
+}
+
+/// Problems found while building auction types in a given `ParseMode`.
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    mode: ParseMode,
+    warnings: Vec<DataError>,
+}
+
+impl Diagnostics {
+    /// Create empty diagnostics for `mode`.
+    pub fn new(mode: ParseMode) -> Self {
+        Diagnostics {
+            mode,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Create empty diagnostics failing on the first malformed concept.
+    pub fn strict() -> Self {
+        Self::new(ParseMode::Strict)
+    }
+
+    /// Create empty diagnostics recording malformed concepts as warnings.
+    pub fn lenient() -> Self {
+        Self::new(ParseMode::Lenient)
+    }
+
+    /// Parse mode in use.
+    pub fn mode(&self) -> ParseMode {
+        self.mode
+    }
+
+    /// Warnings recorded so far.
+    pub fn warnings(&self) -> &[DataError] {
+        &self.warnings
+    }
+
+    /// Consume the diagnostics returning the recorded warnings.
+    pub fn into_warnings(self) -> Vec<DataError> {
+        self.warnings
+    }
+
+    pub(crate) fn check<T>(
+        &mut self,
+        result: Result<T, DataError>,
+        fallback: T,
+    ) -> Result<T, DataError> {
+        match (result, self.mode) {
+            (Ok(value), _) => Ok(value),
+            (Err(error), ParseMode::Strict) => Err(error),
+            (Err(error), ParseMode::Lenient) => {
+                log::warn!("{}", error);
+                self.warnings.push(error);
+                Ok(fallback)
+            }
+        }
+    }
+}
+
 fn get_clean_text(data: &HashMap<BoeConcept, String>, field: &BoeConcept) -> String {
     if let Some(field_str) = data.get(field) {
         field_str
@@ -27,45 +177,238 @@ fn get_clean_text(data: &HashMap<BoeConcept, String>, field: &BoeConcept) -> Str
     }
 }
 
-fn get_date(data: &HashMap<BoeConcept, String>, field: &BoeConcept) -> NaiveDate {
+// Field text as published, `get_clean_text` splits numbers like "10.347,54".
+fn get_raw_text<'a>(data: &'a HashMap<BoeConcept, String>, field: &BoeConcept) -> &'a str {
+    data.get(field).map(String::as_str).unwrap_or_default()
+}
+
+// BOE dates look like "14-07-2020 18:00:00 CET  (ISO: 2020-07-14T18:00:00+02:00)".
+const ISO_DATE_TAG: &str = "ISO:";
+
+fn parse_datetime(value: &str) -> Option<DateTime<FixedOffset>> {
+    if let Some(iso_offset) = value.find(ISO_DATE_TAG) {
+        let iso_str = value[iso_offset + ISO_DATE_TAG.len()..]
+            .trim()
+            .trim_end_matches(')');
+        return DateTime::parse_from_rfc3339(iso_str).ok();
+    }
+
+    // Without ISO date the local time is labeled as CET even in summer, use Madrid rules.
+    let local_str = value.trim().trim_end_matches("CET").trim();
+    NaiveDateTime::parse_from_str(local_str, "%d-%m-%Y %H:%M:%S")
+        .ok()
+        .and_then(|date| madrid_datetime(&date))
+}
+
+pub(crate) fn get_datetime(
+    auction_id: &str,
+    data: &HashMap<BoeConcept, String>,
+    field: &BoeConcept,
+) -> Result<DateTime<FixedOffset>, DataError> {
     if let Some(date_str) = data.get(field) {
-        let space_offset = date_str.find(' ').unwrap_or(date_str.len());
-        NaiveDate::parse_from_str(&date_str[..space_offset], "%d-%m-%Y").unwrap()
+        parse_datetime(date_str)
+            .ok_or_else(|| DataError::new(auction_id, *field, date_str, DataErrorKind::InvalidDate))
     } else {
-        NaiveDate::parse_from_str("01-01-2000", "%d-%m-%Y").unwrap()
+        Ok(default_datetime())
     }
 }
 
-fn get_vehicle_date(data: &HashMap<BoeConcept, String>, field: &BoeConcept) -> NaiveDate {
+// Madrid local `date` with its offset, the earliest one when the clock goes back.
+pub(crate) fn madrid_datetime(date: &NaiveDateTime) -> Option<DateTime<FixedOffset>> {
+    Madrid
+        .from_local_datetime(date)
+        .earliest()
+        .map(|date| date.fixed_offset())
+}
+
+pub(crate) fn default_datetime() -> DateTime<FixedOffset> {
+    madrid_datetime(&default_date().and_hms_opt(0, 0, 0).expect("valid midnight"))
+        .expect("valid default datetime")
+}
+
+fn get_vehicle_date(
+    auction_id: &str,
+    data: &HashMap<BoeConcept, String>,
+    field: &BoeConcept,
+) -> Result<NaiveDate, DataError> {
     if let Some(date_str) = data.get(field) {
-        let date_str = date_str.replace('/', "-");
-        match NaiveDate::parse_from_str(&date_str[..], "%Y-%m-%d") {
-            Ok(date) => date,
-            Err(_) => match NaiveDate::parse_from_str(&date_str[..], "%d-%m-%Y") {
-                Ok(date) => date,
-                Err(error) => {
-                    log::warn!(
-                        "Unable to parse date {}: {}",
-                        &date_str[..],
-                        error.to_string()
-                    );
-                    NaiveDate::parse_from_str("01-01-2000", "%d-%m-%Y").unwrap()
-                }
-            },
+        let normalized_date = date_str.replace('/', "-");
+        match NaiveDate::parse_from_str(&normalized_date[..], "%Y-%m-%d") {
+            Ok(date) => Ok(date),
+            Err(_) => NaiveDate::parse_from_str(&normalized_date[..], "%d-%m-%Y").map_err(|_| {
+                DataError::new(auction_id, *field, date_str, DataErrorKind::InvalidDate)
+            }),
         }
     } else {
-        NaiveDate::parse_from_str("01-01-2000", "%d-%m-%Y").unwrap()
+        Ok(default_date())
     }
 }
 
-fn get_decimal(data: &HashMap<BoeConcept, String>, field: &BoeConcept) -> Decimal {
+pub(crate) fn default_date() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2000, 1, 1).expect("valid default date")
+}
+
+fn parse_decimal(value: &str) -> Option<Decimal> {
+    let value = value.trim();
+    let digits: String = value
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .filter(char::is_ascii_digit)
+        .collect();
+
+    if !digits.is_empty() {
+        digits
+            .parse::<i64>()
+            .ok()
+            .map(|number| Decimal::new(number, DEFAULT_DECIMALS))
+    } else if value.to_uppercase().starts_with("SIN") || value.to_uppercase() == "NO CONSTA" {
+        // BOE writes "Sin puja mínima", "Sin tramos", ... when there is no amount.
+        Some(Decimal::new(0, DEFAULT_DECIMALS))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn get_decimal(
+    auction_id: &str,
+    data: &HashMap<BoeConcept, String>,
+    field: &BoeConcept,
+) -> Result<Decimal, DataError> {
     if let Some(decimal_str) = data.get(field) {
-        let space_offset = decimal_str.find(' ').unwrap_or(decimal_str.len());
-        let result = str::replace(&decimal_str[..space_offset], ".", "");
-        let result = str::replace(&result[..], ",", "");
-        Decimal::new(result.parse::<i64>().unwrap_or(0), DEFAULT_DECIMALS)
+        parse_decimal(decimal_str).ok_or_else(|| {
+            DataError::new(
+                auction_id,
+                *field,
+                decimal_str,
+                DataErrorKind::InvalidDecimal,
+            )
+        })
+    } else {
+        Ok(Decimal::new(0, DEFAULT_DECIMALS))
+    }
+}
+
+fn is_not_stated(value: &str) -> bool {
+    let value = value.trim().to_uppercase();
+    value.is_empty() || value == "NO CONSTA" || value == "-"
+}
+
+pub(crate) fn parse_measure(value: &str) -> Option<Decimal> {
+    let number: String = value
+        .trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+
+    // Spanish notation uses '.' as thousands separator and ',' for decimals,
+    // but some pages write decimals with '.' too, i.e. "105.73 m²".
+    let number = if number.contains(',') {
+        number.replace('.', "").replace(',', ".")
+    } else if matches!(number.rsplit_once('.'), Some((_, decimals)) if decimals.len() == 3) {
+        number.replace('.', "")
     } else {
-        Decimal::new(0, DEFAULT_DECIMALS)
+        number
+    };
+
+    Decimal::from_str(&number).ok()
+}
+
+fn get_measure(
+    auction_id: &str,
+    data: &HashMap<BoeConcept, String>,
+    field: &BoeConcept,
+) -> Result<Option<Decimal>, DataError> {
+    match data.get(field) {
+        Some(measure_str) if !is_not_stated(measure_str) => {
+            parse_measure(measure_str).map(Some).ok_or_else(|| {
+                DataError::new(
+                    auction_id,
+                    *field,
+                    measure_str,
+                    DataErrorKind::InvalidDecimal,
+                )
+            })
+        }
+        _ => Ok(None),
+    }
+}
+
+fn get_optional_date(
+    auction_id: &str,
+    data: &HashMap<BoeConcept, String>,
+    field: &BoeConcept,
+) -> Result<Option<NaiveDate>, DataError> {
+    match data.get(field) {
+        Some(date_str) if !is_not_stated(date_str) => {
+            get_vehicle_date(auction_id, data, field).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+fn get_optional_text(data: &HashMap<BoeConcept, String>, field: &BoeConcept) -> Option<String> {
+    data.get(field)
+        .filter(|value| !is_not_stated(value))
+        .map(|value| value.trim().to_owned())
+}
+
+fn get_idufir(
+    auction_id: &str,
+    data: &HashMap<BoeConcept, String>,
+) -> Result<Option<Idufir>, DataError> {
+    match data.get(&BoeConcept::Idufir) {
+        Some(idufir_str) if !is_not_stated(idufir_str) => {
+            idufir_str.parse::<Idufir>().map(Some).map_err(|_| {
+                DataError::new(
+                    auction_id,
+                    BoeConcept::Idufir,
+                    idufir_str,
+                    DataErrorKind::InvalidIdufir,
+                )
+            })
+        }
+        _ => Ok(None),
+    }
+}
+
+fn get_catastro_reference(
+    auction_id: &str,
+    data: &HashMap<BoeConcept, String>,
+) -> Result<Option<CatastroReference>, DataError> {
+    match data.get(&BoeConcept::CatastroReference) {
+        Some(reference_str) if !is_not_stated(reference_str) => reference_str
+            .parse::<CatastroReference>()
+            .map(Some)
+            .map_err(|_| {
+                DataError::new(
+                    auction_id,
+                    BoeConcept::CatastroReference,
+                    reference_str,
+                    DataErrorKind::InvalidCatastroReference,
+                )
+            }),
+        _ => Ok(None),
+    }
+}
+
+fn get_vin(auction_id: &str, data: &HashMap<BoeConcept, String>) -> Result<Option<Vin>, DataError> {
+    match data.get(&BoeConcept::FrameNumber) {
+        Some(vin_str) if !is_not_stated(vin_str) => match vin_str.parse::<Vin>() {
+            Ok(vin) => Ok(Some(vin)),
+            // Frame numbers of old vehicles are shorter and have no standard format.
+            Err(_)
+                if vin_str.chars().filter(char::is_ascii_alphanumeric).count() == Vin::LENGTH =>
+            {
+                Err(DataError::new(
+                    auction_id,
+                    BoeConcept::FrameNumber,
+                    vin_str,
+                    DataErrorKind::InvalidVin,
+                ))
+            }
+            Err(_) => Ok(None),
+        },
+        _ => Ok(None),
     }
 }
 
@@ -146,6 +489,15 @@ impl Management {
                 .to_string(),
         }
     }
+
+    /// Province of the contact address postal code.
+    pub fn province(&self) -> Province {
+        self.address
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|token| token.len() == 5)
+            .find_map(Province::from_postal_code)
+            .unwrap_or(Province::Unknown)
+    }
 }
 
 /// Bid information struct
@@ -167,19 +519,53 @@ pub struct BidInfo {
 }
 
 impl BidInfo {
-    /// Create a new Auction
+    /// Create a new BidInfo, malformed amounts are set to zero.
     pub fn new(data: &HashMap<BoeConcept, String>) -> BidInfo {
-        BidInfo {
-            appraisal: get_decimal(data, &BoeConcept::Appraisal),
-            bid_step: get_decimal(data, &BoeConcept::BidStep),
-            claim_quantity: get_decimal(data, &BoeConcept::ClaimQuantity),
-            deposit: get_decimal(data, &BoeConcept::DepositAmount),
-            minimum_bid: get_decimal(data, &BoeConcept::MinimumBid),
-            value: get_decimal(data, &BoeConcept::AuctionValue),
+        BidInfo::try_new(NOT_APPLICABLE, data, &mut Diagnostics::lenient())
+            .expect("lenient parsing never fails")
+    }
+
+    /// Create a new BidInfo reporting malformed amounts according to `diagnostics` mode.
+    pub fn try_new(
+        auction_id: &str,
+        data: &HashMap<BoeConcept, String>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<BidInfo, DataError> {
+        let zero = Decimal::new(0, DEFAULT_DECIMALS);
+        let mut decimal =
+            |field: BoeConcept| diagnostics.check(get_decimal(auction_id, data, &field), zero);
+
+        Ok(BidInfo {
+            appraisal: decimal(BoeConcept::Appraisal)?,
+            bid_step: decimal(BoeConcept::BidStep)?,
+            claim_quantity: decimal(BoeConcept::ClaimQuantity)?,
+            deposit: decimal(BoeConcept::DepositAmount)?,
+            minimum_bid: decimal(BoeConcept::MinimumBid)?,
+            value: decimal(BoeConcept::AuctionValue)?,
+        })
+    }
+
+    fn try_new_if_valued(
+        auction_id: &str,
+        data: &HashMap<BoeConcept, String>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Option<BidInfo>, DataError> {
+        if data.get(&BoeConcept::AuctionValue).is_some() {
+            BidInfo::try_new(auction_id, data, diagnostics).map(Some)
+        } else {
+            Ok(None)
         }
     }
 }
 
+impl TryFrom<&HashMap<BoeConcept, String>> for BidInfo {
+    type Error = DataError;
+
+    fn try_from(data: &HashMap<BoeConcept, String>) -> Result<Self, Self::Error> {
+        BidInfo::try_new(NOT_APPLICABLE, data, &mut Diagnostics::strict())
+    }
+}
+
 impl ToString for BidInfo {
     fn to_string(&self) -> String {
         format!(
@@ -202,13 +588,14 @@ impl FromStr for BidInfo {
         if v.len() != 6 {
             return Err(());
         }
+        let decimal = |s: &str| s.parse::<Decimal>().map_err(|_| ());
         Ok(BidInfo {
-            appraisal: v[0].parse::<Decimal>().unwrap(),
-            bid_step: v[1].parse::<Decimal>().unwrap(),
-            claim_quantity: v[2].parse::<Decimal>().unwrap(),
-            deposit: v[3].parse::<Decimal>().unwrap(),
-            minimum_bid: v[4].parse::<Decimal>().unwrap(),
-            value: v[5].parse::<Decimal>().unwrap(),
+            appraisal: decimal(v[0])?,
+            bid_step: decimal(v[1])?,
+            claim_quantity: decimal(v[2])?,
+            deposit: decimal(v[3])?,
+            minimum_bid: decimal(v[4])?,
+            value: decimal(v[5])?,
         })
     }
 }
@@ -278,6 +665,20 @@ impl Display for AuctionState {
     }
 }
 
+impl AuctionState {
+    /// Human readable name.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Cancelled => "Cancelada",
+            Self::Finished => "Concluida",
+            Self::ToBeOpened => "Próxima apertura",
+            Self::Ongoing => "Celebrándose",
+            Self::Suspended => "Suspendida",
+            Self::Unknown => "Desconocido",
+        }
+    }
+}
+
 impl FromStr for AuctionState {
     type Err = ();
 
@@ -311,6 +712,281 @@ impl FromStr for AuctionState {
     }
 }
 
+/// Transition of an auction between two states.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Deserialize, Serialize)]
+pub struct AuctionStateChange {
+    /// State before the transition, `None` when the auction was first stored.
+    pub previous: Option<AuctionState>,
+    /// State after the transition.
+    pub state: AuctionState,
+    /// When the transition was detected.
+    pub date: DateTime<FixedOffset>,
+}
+
+fn normalize_answer(s: &str) -> String {
+    s.trim()
+        .to_uppercase()
+        .chars()
+        .map(|x| match x {
+            'Á' => 'A',
+            'É' => 'E',
+            'Í' => 'I',
+            'Ó' => 'O',
+            'Ú' => 'U',
+            _ => x,
+        })
+        .collect()
+}
+
+/// Answer to BOE yes/no questions like visitable or primary residence.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum YesNoUnknown {
+    /// Yes
+    Yes,
+    /// No
+    No,
+    /// Not stated in the BOE
+    Unknown,
+}
+
+impl YesNoUnknown {
+    /// Human readable name.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Yes => "Sí",
+            Self::No => "No",
+            Self::Unknown => "No consta",
+        }
+    }
+}
+
+impl FromStr for YesNoUnknown {
+    type Err = ();
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, ()> {
+        match &normalize_answer(s)[..] {
+            "SI" => Ok(YesNoUnknown::Yes),
+            "NO" => Ok(YesNoUnknown::No),
+            _ => Ok(YesNoUnknown::Unknown),
+        }
+    }
+}
+
+/// Possession status of a property.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "sqlx", derive(sqlx::Type))]
+pub enum OwnerStatus {
+    /// Someone is living in or using the property.
+    Occupied,
+    /// The property is free of occupants.
+    Vacant,
+    /// Not stated in the BOE
+    Unknown,
+}
+
+impl OwnerStatus {
+    /// Human readable name.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Occupied => "Ocupado",
+            Self::Vacant => "Libre",
+            Self::Unknown => "No consta",
+        }
+    }
+}
+
+impl FromStr for OwnerStatus {
+    type Err = ();
+
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, ()> {
+        let s = normalize_answer(s);
+
+        if s.contains("DESOCUPAD")
+            || s.contains("SIN OCUPANTE")
+            || s.contains("LIBRE")
+            || s.contains("VACI")
+        {
+            Ok(OwnerStatus::Vacant)
+        } else if s.contains("OCUPAD") || s.contains("OCUPANTE") || s.contains("ARRENDAD") {
+            Ok(OwnerStatus::Occupied)
+        } else {
+            Ok(OwnerStatus::Unknown)
+        }
+    }
+}
+
+fn get_yes_no_unknown(data: &HashMap<BoeConcept, String>, field: &BoeConcept) -> YesNoUnknown {
+    data.get(field)
+        .and_then(|value| value.parse::<YesNoUnknown>().ok())
+        .unwrap_or(YesNoUnknown::Unknown)
+}
+
+/// Unique identifier of a property in the land registry (IDUFIR / CRU).
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Idufir(String);
+
+impl Idufir {
+    /// Number of digits of an IDUFIR.
+    pub const LENGTH: usize = 14;
+
+    /// IDUFIR digits.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Idufir {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Idufir {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let idufir: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '.' && *c != '-')
+            .collect();
+
+        if idufir.len() == Idufir::LENGTH && idufir.chars().all(|c| c.is_ascii_digit()) {
+            Ok(Idufir(idufir))
+        } else {
+            Err(())
+        }
+    }
+}
+
+/// Kind of land a catastro reference belongs to.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum CatastroReferenceKind {
+    /// Urban land (urbana).
+    Urban,
+    /// Rustic land (rústica).
+    Rustic,
+}
+
+impl CatastroReferenceKind {
+    /// Kind name in spanish.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Urban => "urbana",
+            Self::Rustic => "rústica",
+        }
+    }
+}
+
+/// Catastro reference (referencia catastral) of a property.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct CatastroReference(String);
+
+impl CatastroReference {
+    /// Number of characters of a catastro reference.
+    pub const LENGTH: usize = 20;
+
+    const PARCEL_LENGTH: usize = 14;
+    const UNIT_LENGTH: usize = 4;
+    const CONTROL_LETTERS: &'static [u8] = b"MQWERTYUIOPASDFGHJKLBZX";
+    const CONTROL_WEIGHTS: [u32; 11] = [13, 15, 12, 5, 4, 17, 9, 21, 3, 7, 1];
+
+    /// Catastro reference characters.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Urban references have a numeric 7 digit block, rustic ones
+    /// a sector letter after province and municipality codes.
+    pub fn kind(&self) -> CatastroReferenceKind {
+        if self.0.as_bytes()[5].is_ascii_alphabetic() {
+            CatastroReferenceKind::Rustic
+        } else {
+            CatastroReferenceKind::Urban
+        }
+    }
+
+    /// Parcel identifier, the first 14 characters.
+    pub fn parcel(&self) -> &str {
+        &self.0[..Self::PARCEL_LENGTH]
+    }
+
+    /// Unit (cargo) inside the parcel.
+    pub fn unit(&self) -> &str {
+        &self.0[Self::PARCEL_LENGTH..Self::PARCEL_LENGTH + Self::UNIT_LENGTH]
+    }
+
+    /// The two control letters.
+    pub fn control(&self) -> &str {
+        &self.0[Self::PARCEL_LENGTH + Self::UNIT_LENGTH..]
+    }
+
+    // Letters count as their position in the spanish alphabet, Ñ included.
+    fn char_value(c: u8) -> u32 {
+        match c {
+            b'0'..=b'9' => (c - b'0') as u32,
+            b'A'..=b'N' => (c - b'A') as u32 + 1,
+            _ => (c - b'A') as u32 + 2,
+        }
+    }
+
+    fn control_letter(chars: impl Iterator<Item = u8>) -> u8 {
+        let sum: u32 = chars
+            .zip(Self::CONTROL_WEIGHTS.iter())
+            .map(|(c, weight)| Self::char_value(c) * weight)
+            .sum();
+
+        Self::CONTROL_LETTERS[(sum % 23) as usize]
+    }
+
+    fn control_letters(reference: &[u8]) -> [u8; 2] {
+        let unit = &reference[Self::PARCEL_LENGTH..Self::PARCEL_LENGTH + Self::UNIT_LENGTH];
+
+        [
+            Self::control_letter(reference[..7].iter().chain(unit).copied()),
+            Self::control_letter(
+                reference[7..Self::PARCEL_LENGTH]
+                    .iter()
+                    .chain(unit)
+                    .copied(),
+            ),
+        ]
+    }
+}
+
+impl Display for CatastroReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for CatastroReference {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let reference: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+
+        if reference.len() != CatastroReference::LENGTH
+            || !reference.chars().all(|c| c.is_ascii_alphanumeric())
+        {
+            return Err(());
+        }
+
+        let bytes = reference.as_bytes();
+        if CatastroReference::control_letters(bytes)[..] == bytes[CatastroReference::LENGTH - 2..] {
+            Ok(CatastroReference(reference))
+        } else {
+            Err(())
+        }
+    }
+}
+
 /// Auction struct
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
@@ -332,45 +1008,86 @@ pub struct Auction {
     /// BidInfo.
     pub bidinfo: BidInfo,
     /// When the auction starts.
-    pub start_date: NaiveDate,
-    /// End date for auction.
-    pub end_date: NaiveDate,
+    pub start_date: DateTime<FixedOffset>,
+    /// When the auction closes.
+    pub end_date: DateTime<FixedOffset>,
     /// Notice in official bulletin
     pub notice: String,
+    /// Account where deposits must be made, if any.
+    pub account_record: Option<String>,
+    /// Why the auction is suspended, as published in its page.
+    #[serde(default)]
+    pub suspension_reason: Option<String>,
+    /// State transitions detected, oldest first.
+    #[serde(default)]
+    pub state_history: Vec<AuctionStateChange>,
 }
 
 impl Auction {
-    /// Create a new Auction
+    /// Create a new Auction, malformed concepts are set to default values.
     pub fn new(
         data: &HashMap<BoeConcept, String>,
         management: Management,
         auction_state: AuctionState,
     ) -> Auction {
+        Auction::try_new(data, management, auction_state, &mut Diagnostics::lenient())
+            .expect("lenient parsing never fails")
+    }
+
+    /// Create a new Auction reporting malformed concepts according to `diagnostics` mode.
+    pub fn try_new(
+        data: &HashMap<BoeConcept, String>,
+        management: Management,
+        auction_state: AuctionState,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Auction, DataError> {
+        let id = diagnostics.check(
+            data.get(&BoeConcept::Identifier).cloned().ok_or_else(|| {
+                DataError::new(
+                    NOT_APPLICABLE,
+                    BoeConcept::Identifier,
+                    "",
+                    DataErrorKind::Missing,
+                )
+            }),
+            String::from(NOT_APPLICABLE),
+        )?;
+
         let lots: u32 = data
             .get(&BoeConcept::Lots)
             .unwrap_or(&"0".to_owned())
             .parse::<u32>()
             .unwrap_or(0);
 
-        Auction {
-            id: data
-                .get(&BoeConcept::Identifier)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
+        let bidinfo = BidInfo::try_new(&id, data, diagnostics)?;
+        let start_date = diagnostics.check(
+            get_datetime(&id, data, &BoeConcept::StartDate),
+            default_datetime(),
+        )?;
+        let end_date = diagnostics.check(
+            get_datetime(&id, data, &BoeConcept::EndDate),
+            default_datetime(),
+        )?;
+
+        Ok(Auction {
+            id,
             auction_state,
             kind: get_auction_kind(data),
-            claim_quantity: get_decimal(data, &BoeConcept::ClaimQuantity),
+            claim_quantity: bidinfo.claim_quantity,
             lots,
             lot_kind: get_lot_auction_kind(data),
             management,
-            bidinfo: BidInfo::new(data),
-            start_date: get_date(data, &BoeConcept::StartDate),
-            end_date: get_date(data, &BoeConcept::EndDate),
+            bidinfo,
+            start_date,
+            end_date,
             notice: data
                 .get(&BoeConcept::Notice)
                 .unwrap_or(&String::from("BOE"))
                 .to_string(),
-        }
+            account_record: get_optional_text(data, &BoeConcept::AccountRecord),
+            suspension_reason: None,
+            state_history: Vec::new(),
+        })
     }
 }
 
@@ -380,14 +1097,21 @@ impl Auction {
 pub struct Property {
     /// Address location.
     pub address: String,
+    /// Allotment (parcela) for rustic properties.
+    pub allotment: Option<String>,
+    /// Surface area in m².
+    pub area: Option<Decimal>,
+    /// Documents and photos linked from the auction.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
     /// Unique identifier is linked to.
     pub auction_id: String,
     /// Bid info
     pub bidinfo: Option<BidInfo>,
     /// Catastro link.
     pub catastro_link: Option<String>,
-    /// Catastro reference.
-    pub catastro_reference: String,
+    /// Catastro reference, `None` if not stated or invalid.
+    pub catastro_reference: Option<CatastroReference>,
     /// category, usually: industrial, garage or apartment.
     pub category: PropertyCategory,
     /// If the property has any previous charges.
@@ -398,83 +1122,138 @@ pub struct Property {
     pub coordinates: Option<Point<f64>>,
     /// Description.
     pub description: String,
+    /// Charges and liens found in the description.
+    pub encumbrances: Vec<Encumbrance>,
+    /// Features extracted from the description.
+    pub features: PropertyFeatures,
+    /// Land registry identifier.
+    pub idufir: Option<Idufir>,
+    /// Lot number inside the auction if the auction has lots.
+    pub lot: Option<u32>,
+    /// INE code of the municipality matching the city.
+    pub municipality: Option<String>,
     /// Owner staus.
-    pub owner_status: String,
+    pub owner_status: OwnerStatus,
+    /// Place name (paraje) for rustic properties.
+    pub place: Option<String>,
     /// Postal code.
     pub postal_code: String,
     /// Indicates if it is primary residence.
-    pub primary_residence: String,
+    pub primary_residence: YesNoUnknown,
     /// Province.
     pub province: Province,
+    /// Percentage of ownership auctioned.
+    pub quota: Option<Decimal>,
     /// Register inscription.
     pub register_inscription: String,
     /// If someone can visit the property or not.
-    pub visitable: String,
+    pub visitable: YesNoUnknown,
 }
 
 impl Eq for Property {}
 
 impl Property {
-    /// Create a new property asset.
+    /// Create a new property asset, malformed concepts are set to default values.
     pub fn new(
         auction: &str,
         category: PropertyCategory,
         data: &HashMap<BoeConcept, String>,
     ) -> Property {
+        Property::try_new(auction, category, data, &mut Diagnostics::lenient())
+            .expect("lenient parsing never fails")
+    }
+
+    /// Create a new property asset reporting malformed concepts according to `diagnostics` mode.
+    pub fn try_new(
+        auction: &str,
+        category: PropertyCategory,
+        data: &HashMap<BoeConcept, String>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Property, DataError> {
         let city = data
             .get(&BoeConcept::City)
             .unwrap_or(&String::from(NOT_APPLICABLE))
             .to_string();
-        let province = data
-            .get(&BoeConcept::Province)
-            .unwrap_or(&String::from("Unknown"))
-            .parse::<Province>()
-            .unwrap();
         let postal_code = data
             .get(&BoeConcept::PostalCode)
             .unwrap_or(&String::from(NOT_APPLICABLE))
             .to_string();
-        let bidinfo = if data.get(&BoeConcept::AuctionValue).is_some() {
-            Some(BidInfo::new(data))
-        } else {
-            None
+        // Postal code prefix is the province INE code, used when province is missing.
+        let postal_code_province =
+            Province::from_postal_code(&postal_code).unwrap_or(Province::Unknown);
+        let province = match data.get(&BoeConcept::Province) {
+            Some(province) => diagnostics.check(
+                province.parse::<Province>().map_err(|_| {
+                    DataError::new(
+                        auction,
+                        BoeConcept::Province,
+                        province,
+                        DataErrorKind::UnknownProvince,
+                    )
+                }),
+                postal_code_province,
+            )?,
+            None => postal_code_province,
         };
-        Property {
+        let municipality =
+            Municipality::find(&city, province).map(|municipality| municipality.ine_code.clone());
+        if municipality.is_none() && city != NOT_APPLICABLE {
+            log::warn!(
+                "City ->{}<- of auction {} not in municipalities",
+                city,
+                auction
+            );
+        }
+        let bidinfo = BidInfo::try_new_if_valued(auction, data, diagnostics)?;
+        let charges = diagnostics.check(
+            get_decimal(auction, data, &BoeConcept::Charges),
+            Decimal::new(0, DEFAULT_DECIMALS),
+        )?;
+        let area = diagnostics.check(get_measure(auction, data, &BoeConcept::Area), None)?;
+        let quota = diagnostics.check(get_measure(auction, data, &BoeConcept::Quota), None)?;
+        let idufir = diagnostics.check(get_idufir(auction, data), None)?;
+        let catastro_reference = diagnostics.check(get_catastro_reference(auction, data), None)?;
+        let raw_description = get_raw_text(data, &BoeConcept::Description);
+        let encumbrances = Encumbrance::from_text(raw_description);
+        let features = PropertyFeatures::from_description(raw_description);
+        let description = get_clean_text(data, &BoeConcept::Description);
+        Ok(Property {
             address: data
                 .get(&BoeConcept::Address)
                 .unwrap_or(&String::from(NOT_APPLICABLE))
                 .to_string(),
+            allotment: get_optional_text(data, &BoeConcept::Allotment),
+            area,
+            attachments: Vec::new(),
             auction_id: auction.to_string(),
             bidinfo,
             catastro_link: None,
-            catastro_reference: data
-                .get(&BoeConcept::CatastroReference)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
+            catastro_reference,
             category,
-            charges: get_decimal(data, &BoeConcept::Charges),
+            charges,
             city,
             coordinates: None,
-            description: get_clean_text(data, &BoeConcept::Description),
+            description,
+            encumbrances,
+            features,
+            idufir,
+            lot: None,
+            municipality,
             owner_status: data
                 .get(&BoeConcept::OwnerStatus)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
+                .and_then(|status| status.parse::<OwnerStatus>().ok())
+                .unwrap_or(OwnerStatus::Unknown),
+            place: get_optional_text(data, &BoeConcept::Place),
             postal_code,
-            primary_residence: data
-                .get(&BoeConcept::PrimaryResidence)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
+            primary_residence: get_yes_no_unknown(data, &BoeConcept::PrimaryResidence),
             province,
+            quota,
             register_inscription: data
                 .get(&BoeConcept::RegisterInscription)
                 .unwrap_or(&String::from(NOT_APPLICABLE))
                 .to_string(),
-            visitable: data
-                .get(&BoeConcept::Visitable)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
-        }
+            visitable: get_yes_no_unknown(data, &BoeConcept::Visitable),
+        })
     }
 }
 
@@ -482,6 +1261,9 @@ impl Property {
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
 pub struct Vehicle {
+    /// Documents and photos linked from the auction.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
     /// Auction identifier is linked to.
     pub auction_id: String,
     /// Bid info
@@ -494,6 +1276,8 @@ pub struct Vehicle {
     pub charges: Decimal,
     /// Description.
     pub description: String,
+    /// Charges and liens found in the description.
+    pub encumbrances: Vec<Encumbrance>,
     /// Frame number.
     pub frame_number: String, // Número de bastidor
     /// Licensed date.
@@ -502,25 +1286,61 @@ pub struct Vehicle {
     pub license_plate: String,
     /// Localization.
     pub localization: String,
+    /// Lot number inside the auction if the auction has lots.
+    pub lot: Option<u32>,
     /// Model.
     pub model: String,
+    /// Parsed license plate.
+    pub plate: Option<LicensePlate>,
+    /// Approximate registration period inferred from the plate
+    /// when the licensed date is not stated.
+    pub registration_period: Option<RegistrationPeriod>,
+    /// Validated vehicle identification number.
+    pub vin: Option<Vin>,
     /// Indicates if someone can inspect the vehicle.
-    pub visitable: String,
+    pub visitable: YesNoUnknown,
 }
 
 impl Vehicle {
-    /// Create a new vehicle asset.
+    /// Create a new vehicle asset, malformed concepts are set to default values.
     pub fn new(
         auction: &str,
         category: VehicleCategory,
         data: &HashMap<BoeConcept, String>,
     ) -> Vehicle {
-        let bidinfo = if data.get(&BoeConcept::AuctionValue).is_some() {
-            Some(BidInfo::new(data))
+        Vehicle::try_new(auction, category, data, &mut Diagnostics::lenient())
+            .expect("lenient parsing never fails")
+    }
+
+    /// Create a new vehicle asset reporting malformed concepts according to `diagnostics` mode.
+    pub fn try_new(
+        auction: &str,
+        category: VehicleCategory,
+        data: &HashMap<BoeConcept, String>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Vehicle, DataError> {
+        let bidinfo = BidInfo::try_new_if_valued(auction, data, diagnostics)?;
+        let charges = diagnostics.check(
+            get_decimal(auction, data, &BoeConcept::Charges),
+            Decimal::new(0, DEFAULT_DECIMALS),
+        )?;
+        let licensed_date = diagnostics.check(
+            get_vehicle_date(auction, data, &BoeConcept::LicensedDate),
+            default_date(),
+        )?;
+        let vin = diagnostics.check(get_vin(auction, data), None)?;
+        let plate = data
+            .get(&BoeConcept::LicensePlate)
+            .and_then(|plate| plate.parse::<LicensePlate>().ok());
+        let registration_period = if licensed_date == default_date() {
+            plate.as_ref().and_then(LicensePlate::registration_period)
         } else {
             None
         };
-        Vehicle {
+        let encumbrances = Encumbrance::from_text(get_raw_text(data, &BoeConcept::Description));
+        let description = get_clean_text(data, &BoeConcept::Description);
+        Ok(Vehicle {
+            attachments: Vec::new(),
             auction_id: auction.to_string(),
             bidinfo,
             brand: data
@@ -528,13 +1348,14 @@ impl Vehicle {
                 .unwrap_or(&String::from(NOT_APPLICABLE))
                 .to_string(),
             category,
-            charges: get_decimal(data, &BoeConcept::Charges),
-            description: get_clean_text(data, &BoeConcept::Description),
+            charges,
+            description,
+            encumbrances,
             frame_number: data
                 .get(&BoeConcept::FrameNumber)
                 .unwrap_or(&String::from(NOT_APPLICABLE))
                 .to_string(),
-            licensed_date: get_vehicle_date(data, &BoeConcept::LicensedDate),
+            licensed_date,
             license_plate: data
                 .get(&BoeConcept::LicensePlate)
                 .unwrap_or(&String::from(NOT_APPLICABLE))
@@ -543,15 +1364,16 @@ impl Vehicle {
                 .get(&BoeConcept::Localization)
                 .unwrap_or(&String::from(NOT_APPLICABLE))
                 .to_string(),
+            lot: None,
             model: data
                 .get(&BoeConcept::Model)
                 .unwrap_or(&String::from(NOT_APPLICABLE))
                 .to_string(),
-            visitable: data
-                .get(&BoeConcept::Visitable)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
-        }
+            plate,
+            registration_period,
+            vin,
+            visitable: get_yes_no_unknown(data, &BoeConcept::Visitable),
+        })
     }
 }
 
@@ -559,8 +1381,13 @@ impl Vehicle {
 #[derive(Debug, Eq, PartialEq, Deserialize, Serialize)]
 #[cfg_attr(feature = "sqlx", derive(sqlx::FromRow))]
 pub struct Other {
+    /// When the current owner acquired the asset.
+    pub acquisition_date: Option<NaiveDate>,
     /// Any asset additional information.
     pub additional_information: String,
+    /// Documents and photos linked from the auction.
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
     /// Auction is linked to.
     pub auction_id: String,
     /// Bid info
@@ -571,43 +1398,76 @@ pub struct Other {
     pub charges: Decimal,
     /// Description.
     pub description: String,
+    /// Charges and liens found in the additional information and description.
+    pub encumbrances: Vec<Encumbrance>,
     /// Type of judicial title if applies.
     pub judicial_title: String,
+    /// Lot number inside the auction if the auction has lots.
+    pub lot: Option<u32>,
+    /// Percentage of ownership auctioned.
+    pub quota: Option<Decimal>,
     /// If someone can visit the asset if applies.
-    pub visitable: String,
+    pub visitable: YesNoUnknown,
 }
 
 impl Other {
-    /// Create an asset that is not a vehicle or real state property.
+    /// Create an asset that is not a vehicle or real state property,
+    /// malformed concepts are set to default values.
     pub fn new(
         auction: &str,
         category: OtherCategory,
         data: &HashMap<BoeConcept, String>,
     ) -> Other {
-        let bidinfo = if data.get(&BoeConcept::AuctionValue).is_some() {
-            Some(BidInfo::new(data))
-        } else {
-            None
-        };
-        Other {
-            additional_information: data
-                .get(&BoeConcept::AdditionalInformation)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
+        Other::try_new(auction, category, data, &mut Diagnostics::lenient())
+            .expect("lenient parsing never fails")
+    }
+
+    /// Create an asset that is not a vehicle or real state property
+    /// reporting malformed concepts according to `diagnostics` mode.
+    pub fn try_new(
+        auction: &str,
+        category: OtherCategory,
+        data: &HashMap<BoeConcept, String>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Other, DataError> {
+        let bidinfo = BidInfo::try_new_if_valued(auction, data, diagnostics)?;
+        let charges = diagnostics.check(
+            get_decimal(auction, data, &BoeConcept::Charges),
+            Decimal::new(0, DEFAULT_DECIMALS),
+        )?;
+        let acquisition_date = diagnostics.check(
+            get_optional_date(auction, data, &BoeConcept::AcquisitionDate),
+            None,
+        )?;
+        let quota = diagnostics.check(get_measure(auction, data, &BoeConcept::Quota), None)?;
+        let additional_information = data
+            .get(&BoeConcept::AdditionalInformation)
+            .unwrap_or(&String::from(NOT_APPLICABLE))
+            .to_string();
+        let mut encumbrances = Encumbrance::from_text(&additional_information);
+        encumbrances.extend(Encumbrance::from_text(get_raw_text(
+            data,
+            &BoeConcept::Description,
+        )));
+        let description = get_clean_text(data, &BoeConcept::Description);
+        Ok(Other {
+            acquisition_date,
+            additional_information,
+            attachments: Vec::new(),
             auction_id: auction.to_string(),
             bidinfo,
             category,
-            charges: get_decimal(data, &BoeConcept::Charges),
-            description: get_clean_text(data, &BoeConcept::Description),
+            charges,
+            description,
+            encumbrances,
             judicial_title: data
                 .get(&BoeConcept::JudicialTitle)
                 .unwrap_or(&String::from(NOT_APPLICABLE))
                 .to_string(),
-            visitable: data
-                .get(&BoeConcept::Visitable)
-                .unwrap_or(&String::from(NOT_APPLICABLE))
-                .to_string(),
-        }
+            lot: None,
+            quota,
+            visitable: get_yes_no_unknown(data, &BoeConcept::Visitable),
+        })
     }
 }
 
@@ -623,82 +1483,156 @@ pub enum Asset {
 }
 
 impl Asset {
-    fn parse_header(header: &str) -> (String, String) {
-        let begin_cat = header.find('-').unwrap() + 2;
+    fn parse_header(header: &str) -> Option<(String, String)> {
+        let begin_cat = header.find('-')? + 2;
         if let Some(separator) = header.find('(') {
-            let end_cat = separator - 1;
+            let end_cat = separator.checked_sub(1)?;
             let begin_subcat = separator + 1;
-            let end_subcat = header.find(')').unwrap();
+            let end_subcat = header.find(')')?;
 
-            (
-                header[begin_cat..end_cat].to_owned(),
-                header[begin_subcat..end_subcat].to_owned(),
-            )
+            Some((
+                header.get(begin_cat..end_cat)?.to_owned(),
+                header.get(begin_subcat..end_subcat)?.to_owned(),
+            ))
         } else {
-            (header[begin_cat..].to_owned(), "".to_owned())
+            Some((header.get(begin_cat..)?.to_owned(), "".to_owned()))
         }
     }
 
-    /// Create a new Asset
+    /// Create a new Asset, malformed concepts are set to default values.
     pub fn new(auction: &str, data: &HashMap<BoeConcept, String>) -> Asset {
-        let header = data.get(&BoeConcept::Header).unwrap().to_string();
-        let (category, subcategory) = Asset::parse_header(&header);
+        Asset::try_new(auction, data, &mut Diagnostics::lenient())
+            .expect("lenient parsing never fails")
+    }
+
+    /// Create a new Asset reporting malformed concepts according to `diagnostics` mode.
+    pub fn try_new(
+        auction: &str,
+        data: &HashMap<BoeConcept, String>,
+        diagnostics: &mut Diagnostics,
+    ) -> Result<Asset, DataError> {
+        let header = data
+            .get(&BoeConcept::Header)
+            .map(String::as_str)
+            .unwrap_or("");
+        let (category, subcategory) = diagnostics.check(
+            Asset::parse_header(header).ok_or_else(|| {
+                DataError::new(
+                    auction,
+                    BoeConcept::Header,
+                    header,
+                    DataErrorKind::InvalidHeader,
+                )
+            }),
+            ("".to_owned(), "".to_owned()),
+        )?;
+        let unknown_category = || {
+            DataError::new(
+                auction,
+                BoeConcept::Header,
+                header,
+                DataErrorKind::UnknownCategory,
+            )
+        };
+
         match &category[..] {
             "INMUEBLE" => {
-                let property_category = match subcategory.parse::<PropertyCategory>() {
-                    Ok(cat) => cat,
-                    Err(err) => {
-                        log::warn!("Unable to parse property category: {}", err);
-                        PropertyCategory::Apartment
-                    }
-                };
-                Asset::Property(Property::new(auction, property_category, data))
+                let property_category = diagnostics.check(
+                    subcategory
+                        .parse::<PropertyCategory>()
+                        .map_err(|_| unknown_category()),
+                    PropertyCategory::Apartment,
+                )?;
+                Property::try_new(auction, property_category, data, diagnostics)
+                    .map(Asset::Property)
             }
             "VEHÍCULO" => {
-                let vehicle_category = match subcategory.parse::<VehicleCategory>() {
-                    Ok(cat) => cat,
-                    Err(err) => {
-                        log::warn!("Unable to parse vehicle category: {}", err);
-                        VehicleCategory::Car
-                    }
-                };
-                Asset::Vehicle(Vehicle::new(auction, vehicle_category, data))
+                let vehicle_category = diagnostics.check(
+                    subcategory
+                        .parse::<VehicleCategory>()
+                        .map_err(|_| unknown_category()),
+                    VehicleCategory::Car,
+                )?;
+                Vehicle::try_new(auction, vehicle_category, data, diagnostics).map(Asset::Vehicle)
             }
             _ => {
-                let other_category = match subcategory.parse::<OtherCategory>() {
-                    Ok(cat) => cat,
-                    Err(err) => {
-                        log::warn!("Unable to parse other category: {}", err);
-                        OtherCategory::Other
-                    }
-                };
-                Asset::Other(Other::new(auction, other_category, data))
+                let other_category = diagnostics.check(
+                    subcategory
+                        .parse::<OtherCategory>()
+                        .map_err(|_| unknown_category()),
+                    OtherCategory::Other,
+                )?;
+                Other::try_new(auction, other_category, data, diagnostics).map(Asset::Other)
             }
         }
     }
+
+    /// Auction identifier the asset belongs to.
+    pub fn auction_id(&self) -> &str {
+        match self {
+            Asset::Other(other) => &other.auction_id,
+            Asset::Property(property) => &property.auction_id,
+            Asset::Vehicle(vehicle) => &vehicle.auction_id,
+        }
+    }
+
+    /// Lot number of the asset inside its auction, if any.
+    pub fn lot(&self) -> Option<u32> {
+        match self {
+            Asset::Other(other) => other.lot,
+            Asset::Property(property) => property.lot,
+            Asset::Vehicle(vehicle) => vehicle.lot,
+        }
+    }
+
+    /// Set the `lot` number of the asset inside its auction.
+    pub fn with_lot(mut self, lot: Option<u32>) -> Asset {
+        match &mut self {
+            Asset::Other(other) => other.lot = lot,
+            Asset::Property(property) => property.lot = lot,
+            Asset::Vehicle(vehicle) => vehicle.lot = lot,
+        }
+        self
+    }
+}
+
+impl TryFrom<(&str, &HashMap<BoeConcept, String>)> for Asset {
+    type Error = DataError;
+
+    fn try_from(
+        (auction, data): (&str, &HashMap<BoeConcept, String>),
+    ) -> Result<Self, Self::Error> {
+        Asset::try_new(auction, data, &mut Diagnostics::strict())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encumbrances::EncumbranceKind;
+    use crate::features::{Confidence, Extracted};
+    use chrono::Datelike;
 
     #[test]
     fn asset_parse_header_test() {
-        let (cat, subcat) = Asset::parse_header("BIEN 0 - INMUEBLE (VIVIENDA)");
+        let (cat, subcat) = Asset::parse_header("BIEN 0 - INMUEBLE (VIVIENDA)").unwrap();
         assert_eq!("INMUEBLE", cat);
         assert_eq!("VIVIENDA", subcat);
 
-        let (cat, subcat) = Asset::parse_header("BIEN 1 - VEHÍCULO (INDUSTRIALES)");
+        let (cat, subcat) = Asset::parse_header("BIEN 1 - VEHÍCULO (INDUSTRIALES)").unwrap();
         assert_eq!("VEHÍCULO", cat);
         assert_eq!("INDUSTRIALES", subcat);
 
-        let (cat, subcat) = Asset::parse_header("BIEN 1 - BIEN MUEBLE (OTROS BIENES Y DERECHOS)");
+        let (cat, subcat) =
+            Asset::parse_header("BIEN 1 - BIEN MUEBLE (OTROS BIENES Y DERECHOS)").unwrap();
         assert_eq!("BIEN MUEBLE", cat);
         assert_eq!("OTROS BIENES Y DERECHOS", subcat);
 
-        let (cat, subcat) = Asset::parse_header("BIEN 0 - INMUEBLE");
+        let (cat, subcat) = Asset::parse_header("BIEN 0 - INMUEBLE").unwrap();
         assert_eq!("INMUEBLE", cat);
         assert_eq!("", subcat);
+
+        assert_eq!(None, Asset::parse_header("BIEN 0 INMUEBLE"));
     }
 
     #[test]
@@ -718,13 +1652,26 @@ mod tests {
         .collect();
 
         assert_eq!(
-            NaiveDate::from_ymd(2020, 7, 14),
-            get_date(&data, &BoeConcept::StartDate)
+            DateTime::parse_from_rfc3339("2020-07-14T18:00:00+02:00").unwrap(),
+            get_datetime("id", &data, &BoeConcept::StartDate).unwrap()
+        );
+        assert_eq!(
+            DateTime::parse_from_rfc3339("2020-08-03T18:00:00+02:00").unwrap(),
+            get_datetime("id", &data, &BoeConcept::EndDate).unwrap()
+        );
+        assert_eq!(
+            Some(DateTime::parse_from_rfc3339("2020-08-03T18:00:00+02:00").unwrap()),
+            parse_datetime("03-08-2020 18:00:00 CET")
         );
         assert_eq!(
-            NaiveDate::from_ymd(2020, 8, 3),
-            get_date(&data, &BoeConcept::EndDate)
+            Some(DateTime::parse_from_rfc3339("2020-12-03T18:00:00+01:00").unwrap()),
+            parse_datetime("03-12-2020 18:00:00 CET")
         );
+        assert_eq!(
+            Some(DateTime::parse_from_rfc3339("2020-10-25T02:30:00+02:00").unwrap()),
+            parse_datetime("25-10-2020 02:30:00 CET")
+        );
+        assert_eq!(None, parse_datetime("29-03-2020 02:30:00 CET"));
     }
 
     #[test]
@@ -739,12 +1686,166 @@ mod tests {
 
         assert_eq!(
             Decimal::new(8197157, DEFAULT_DECIMALS),
-            get_decimal(&data, &BoeConcept::ClaimQuantity)
+            get_decimal("id", &data, &BoeConcept::ClaimQuantity).unwrap()
         );
         assert_eq!(
             Decimal::new(7512700, DEFAULT_DECIMALS),
-            get_decimal(&data, &BoeConcept::Appraisal)
+            get_decimal("id", &data, &BoeConcept::Appraisal).unwrap()
+        );
+    }
+
+    #[test]
+    fn get_malformed_values_test() {
+        let data: HashMap<BoeConcept, String> = [
+            (BoeConcept::StartDate, String::from("14/07/2020")),
+            (BoeConcept::ClaimQuantity, String::from("A consultar")),
+            (BoeConcept::MinimumBid, String::from("SIN PUJA MÍNIMA")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        assert_eq!(
+            Err(DataError::new(
+                "id",
+                BoeConcept::StartDate,
+                "14/07/2020",
+                DataErrorKind::InvalidDate
+            )),
+            get_datetime("id", &data, &BoeConcept::StartDate)
+        );
+        assert_eq!(
+            Err(DataError::new(
+                "id",
+                BoeConcept::ClaimQuantity,
+                "A consultar",
+                DataErrorKind::InvalidDecimal
+            )),
+            get_decimal("id", &data, &BoeConcept::ClaimQuantity)
+        );
+        assert_eq!(
+            Ok(Decimal::new(0, DEFAULT_DECIMALS)),
+            get_decimal("id", &data, &BoeConcept::MinimumBid)
+        );
+    }
+
+    #[test]
+    fn diagnostics_mode_test() {
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - INMUEBLE (VIVIENDA)"),
+            ),
+            (BoeConcept::Province, String::from("ATLANTIS")),
+            (BoeConcept::Charges, String::from("A consultar")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        let error = Asset::try_from(("id", &data)).unwrap_err();
+        assert_eq!("id", error.auction_id);
+        assert_eq!(BoeConcept::Province, error.concept);
+        assert_eq!("ATLANTIS", error.value);
+        assert_eq!(DataErrorKind::UnknownProvince, error.kind);
+
+        let mut diagnostics = Diagnostics::lenient();
+        match Asset::try_new("id", &data, &mut diagnostics).unwrap() {
+            Asset::Property(property) => {
+                assert_eq!(Province::Unknown, property.province);
+                assert_eq!(Decimal::new(0, DEFAULT_DECIMALS), property.charges);
+            }
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+        let kinds: Vec<DataErrorKind> = diagnostics.warnings().iter().map(|w| w.kind).collect();
+        assert_eq!(
+            vec![
+                DataErrorKind::UnknownProvince,
+                DataErrorKind::InvalidDecimal
+            ],
+            kinds
+        );
+
+        let mut diagnostics = Diagnostics::lenient();
+        let data: HashMap<BoeConcept, String> = [(BoeConcept::Header, String::from("BIEN 1"))]
+            .iter()
+            .cloned()
+            .collect();
+        assert!(matches!(
+            Asset::try_new("id", &data, &mut diagnostics),
+            Ok(Asset::Other(_))
+        ));
+        assert_eq!(DataErrorKind::InvalidHeader, diagnostics.warnings()[0].kind);
+        assert!(Asset::try_from(("id", &data)).is_err());
+
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - INMUEBLE (VIVIENDA)"),
+            ),
+            (BoeConcept::Province, String::from("ATLANTIS")),
+            (BoeConcept::PostalCode, String::from("47014")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        match Asset::new("id", &data) {
+            Asset::Property(property) => assert_eq!(Province::Valladolid, property.province),
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+
+        let data: HashMap<BoeConcept, String> = [
+            (BoeConcept::Identifier, String::from("SUB-NE-2020-465937")),
+            (BoeConcept::ClaimQuantity, String::from("A consultar")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let mut diagnostics = Diagnostics::lenient();
+        let auction = Auction::try_new(
+            &data,
+            Management::new(&data),
+            AuctionState::Unknown,
+            &mut diagnostics,
+        )
+        .unwrap();
+        assert_eq!(Decimal::new(0, DEFAULT_DECIMALS), auction.claim_quantity);
+        let warnings: Vec<(BoeConcept, DataErrorKind)> = diagnostics
+            .warnings()
+            .iter()
+            .map(|w| (w.concept, w.kind))
+            .collect();
+        assert_eq!(
+            vec![(BoeConcept::ClaimQuantity, DataErrorKind::InvalidDecimal)],
+            warnings
+        );
+    }
+
+    #[test]
+    fn yes_no_unknown_and_owner_status_test() {
+        assert_eq!(Ok(YesNoUnknown::Yes), "SÍ".parse::<YesNoUnknown>());
+        assert_eq!(Ok(YesNoUnknown::Yes), "Sí".parse::<YesNoUnknown>());
+        assert_eq!(Ok(YesNoUnknown::No), " No ".parse::<YesNoUnknown>());
+        assert_eq!(
+            Ok(YesNoUnknown::Unknown),
+            "NO CONSTA".parse::<YesNoUnknown>()
+        );
+
+        assert_eq!(Ok(OwnerStatus::Vacant), "Desocupado".parse::<OwnerStatus>());
+        assert_eq!(
+            Ok(OwnerStatus::Vacant),
+            "LIBRE DE OCUPANTES".parse::<OwnerStatus>()
         );
+        assert_eq!(
+            Ok(OwnerStatus::Vacant),
+            "Sin ocupantes".parse::<OwnerStatus>()
+        );
+        assert_eq!(Ok(OwnerStatus::Occupied), "OCUPADO".parse::<OwnerStatus>());
+        assert_eq!(
+            Ok(OwnerStatus::Occupied),
+            "Arrendado".parse::<OwnerStatus>()
+        );
+        assert_eq!(Ok(OwnerStatus::Unknown), "NO CONSTA".parse::<OwnerStatus>());
     }
 
     #[test]
@@ -803,9 +1904,12 @@ mod tests {
             lot_kind: LotAuctionKind::NotApplicable,
             management: mgm,
             bidinfo: bid,
-            start_date: NaiveDate::parse_from_str("14-07-2020", "%d-%m-%Y").unwrap(),
-            end_date: NaiveDate::parse_from_str("03-08-2020", "%d-%m-%Y").unwrap(),
+            start_date: DateTime::parse_from_rfc3339("2020-07-14T18:00:00+02:00").unwrap(),
+            end_date: DateTime::parse_from_rfc3339("2020-08-03T18:00:00+02:00").unwrap(),
             notice: String::from("BOE-B-2020-21708"),
+            account_record: None,
+            suspension_reason: None,
+            state_history: Vec::new(),
         };
 
         let mgm = Management {
@@ -853,6 +1957,13 @@ mod tests {
         };
 
         assert_eq!(management, Management::new(&mgm));
+        assert_eq!(Province::Murcia, management.province());
+
+        let management = Management {
+            address: String::from("CALLE MARIANO DE LOS COBOS 90"),
+            ..management
+        };
+        assert_eq!(Province::Unknown, management.province());
     }
 
     #[test]
@@ -907,10 +2018,13 @@ mod tests {
 
         let asset_property = Asset::Property(Property {
             address: String::from("CALLE MARIANO DE LOS COBOS 90"),
+            allotment: None,
+            area: None,
+            attachments: vec![],
             auction_id: id.to_string(),
             bidinfo: None,
             catastro_link: None,
-            catastro_reference: String::from("4110202UM5141A0003HH"),
+            catastro_reference: "4110202UM5141A0003HH".parse::<CatastroReference>().ok(),
             category: PropertyCategory::Apartment,
             charges: Decimal::new(0, DEFAULT_DECIMALS),
             city: String::from("VALLADOLID"),
@@ -918,12 +2032,25 @@ mod tests {
             description: String::from(
                 "FINCA URBANA SITUADA EN VALLADOLID, CALLE MARIANO DE LOS COBOS NUM. 90, BAJO-1º",
             ),
-            owner_status: String::from("NO CONSTA"),
+            encumbrances: vec![],
+            features: PropertyFeatures {
+                floor: Some(Extracted {
+                    value: String::from("BAJO"),
+                    confidence: Confidence::Medium,
+                }),
+                ..Default::default()
+            },
+            idufir: None,
+            lot: None,
+            municipality: Some(String::from("47186")),
+            owner_status: OwnerStatus::Unknown,
+            place: None,
             postal_code: String::from("47014"),
-            primary_residence: String::from("SÍ"),
+            primary_residence: YesNoUnknown::Yes,
             province: Province::Valladolid,
+            quota: None,
             register_inscription: String::from("CONSTA EN EL EDICTO"),
-            visitable: String::from("NO CONSTA"),
+            visitable: YesNoUnknown::Unknown,
         });
 
         assert_eq!(asset_property, Asset::new(id, &asset_property_map));
@@ -985,6 +2112,9 @@ mod tests {
 
         let asset_property = Asset::Property(Property {
             address: String::from("CALLE MARIANO DE LOS COBOS 90"),
+            allotment: None,
+            area: None,
+            attachments: vec![],
             auction_id: id.to_string(),
             bidinfo: Some(BidInfo {
                 appraisal: Decimal::new(0, DEFAULT_DECIMALS),
@@ -995,7 +2125,7 @@ mod tests {
                 value: Decimal::new(15_100_00, DEFAULT_DECIMALS),
             }),
             catastro_link: None,
-            catastro_reference: String::from("4110202UM5141A0003HH"),
+            catastro_reference: "4110202UM5141A0003HH".parse::<CatastroReference>().ok(),
             category: PropertyCategory::Apartment,
             charges: Decimal::new(0, DEFAULT_DECIMALS),
             city: String::from("VALLADOLID"),
@@ -1003,15 +2133,32 @@ mod tests {
             description: String::from(
                 "FINCA URBANA SITUADA EN VALLADOLID, CALLE MARIANO DE LOS COBOS NUM. 90, BAJO-1º",
             ),
-            owner_status: String::from("NO CONSTA"),
+            encumbrances: vec![],
+            features: PropertyFeatures {
+                floor: Some(Extracted {
+                    value: String::from("BAJO"),
+                    confidence: Confidence::Medium,
+                }),
+                ..Default::default()
+            },
+            idufir: None,
+            lot: None,
+            municipality: Some(String::from("47186")),
+            owner_status: OwnerStatus::Unknown,
+            place: None,
             postal_code: String::from("47014"),
-            primary_residence: String::from("SÍ"),
+            primary_residence: YesNoUnknown::Yes,
             province: Province::Valladolid,
+            quota: None,
             register_inscription: String::from("CONSTA EN EL EDICTO"),
-            visitable: String::from("NO CONSTA"),
+            visitable: YesNoUnknown::Unknown,
         });
 
         assert_eq!(asset_property, Asset::new(id, &asset_property_map));
+
+        let asset = Asset::new(id, &asset_property_map).with_lot(Some(2));
+        assert_eq!(id, asset.auction_id());
+        assert_eq!(Some(2), asset.lot());
     }
 
     #[test]
@@ -1061,6 +2208,7 @@ mod tests {
         let id = "id";
 
         let asset_vehicle = Asset::Vehicle(Vehicle {
+            attachments: vec![],
             auction_id: id.to_string(),
             bidinfo: Some(BidInfo {
                 appraisal: Decimal::new(0, DEFAULT_DECIMALS),
@@ -1076,17 +2224,76 @@ mod tests {
             description: String::from(
                 "VEHÍCULO MATRÍCULA 8868CXV, MARCA: AUDI, MODELO A4, Nº BASTIDOR / Nº CHASIS, EN SU CASO: WAUZZZ8E92A267004."
               ),
+            encumbrances: vec![],
             frame_number: String::from("WAUZZZ8E92A267004."), // Número de bastidor
             licensed_date: NaiveDate::parse_from_str("2004-07-02", "%Y-%m-%d").unwrap(),
             license_plate: String::from("8868CXV"),
             localization: String::from("AVDA. SUAREZ INCLAN, 11, PLAZA DE GARAJE 60 33100 - TRUBIA"),
+            lot: None,
             model: String::from("A4"),
-            visitable: String::from("SÍ"),
+            plate: "8868CXV".parse::<LicensePlate>().ok(),
+            registration_period: None,
+            vin: "WAUZZZ8E92A267004".parse::<Vin>().ok(),
+            visitable: YesNoUnknown::Yes,
         });
 
         assert_eq!(asset_vehicle, Asset::new(id, &asset_vehicle_map));
     }
 
+    #[test]
+    fn asset_new_vehicle_identity_test() {
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - VEHÍCULO (TURISMOS)"),
+            ),
+            (BoeConcept::LicensePlate, String::from("1234 CBB")),
+            (BoeConcept::FrameNumber, String::from("VSSZZZ6KZ1R123456")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        match Asset::try_from(("id", &data)).unwrap() {
+            Asset::Vehicle(vehicle) => {
+                assert_eq!(
+                    Some("SEAT"),
+                    vehicle.vin.as_ref().and_then(Vin::manufacturer)
+                );
+                assert_eq!(
+                    None,
+                    vehicle.plate.as_ref().and_then(LicensePlate::province)
+                );
+                assert_eq!(
+                    Some(2003),
+                    vehicle.registration_period.map(|period| period.from.year())
+                );
+            }
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - VEHÍCULO (TURISMOS)"),
+            ),
+            (BoeConcept::FrameNumber, String::from("1M8GDM9A1KP042788")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let error = Asset::try_from(("id", &data)).unwrap_err();
+        assert_eq!(DataErrorKind::InvalidVin, error.kind);
+
+        match Asset::new("id", &data) {
+            Asset::Vehicle(vehicle) => {
+                assert_eq!(None, vehicle.vin);
+                assert_eq!(None, vehicle.registration_period);
+            }
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+    }
+
     #[test]
     fn asset_new_other_test() {
         let asset_other_map: HashMap<BoeConcept, String> = [
@@ -1126,7 +2333,9 @@ mod tests {
     .collect();
         let id = "id";
         let asset_other = Asset::Other(Other {
+            acquisition_date: None,
             additional_information: String::from("LAS CONDICIONES DE LA TRASMISIÓN Y LOS REQUISITOS DEL CONCESIONARIO SE ENCUENTRAN REGULADAS EN EL REAL DECRETO 1199/1999, DE 9 DE JULIO, POR EL QUE SE DESARROLLA LA LEY 13/1998, DE 4 DE MAYO, DE ORDENACIÓN DEL MERCADO DE TABACOS Y NORMATIVA TRIBUTARIA, Y SE REGULA EL ESTATUTO CONCESIONAL DE LA RED DE EXPENDURÍAS DE TABACO Y TIMBRE. VER FOTOGRAFÍAS ANEXAS. - LA CONCESIÓN FINALIZA 03/12/2042. - DILIGENCIA DE EMBARGO A FAVOR DE LA AEAT(2111623311338X), CON IMPORTE PENDIENTE A FECHA 17-09-2020 DE 10.347,54€."),
+            attachments: vec![],
             auction_id: id.to_string(),
             bidinfo: Some(BidInfo {
                 appraisal: Decimal::new(0, DEFAULT_DECIMALS),
@@ -1141,10 +2350,232 @@ mod tests {
             description: String::from(
                 "CONCESION EXPENDEDURIA DE TABACO Y TIMBRE ALMONTE-1, CODIGO 210049, SITA EN LA C/ DEL OCIO 105 DE ALMONTE (HUELVA)"
               ),
+            encumbrances: vec![
+                Encumbrance {
+                    kind: EncumbranceKind::ConcessionExpiry,
+                    creditor: None,
+                    amount: None,
+                    date: NaiveDate::from_ymd_opt(2042, 12, 3),
+                },
+                Encumbrance {
+                    kind: EncumbranceKind::Seizure,
+                    creditor: Some(String::from("AEAT")),
+                    amount: Some(Decimal::new(1_034_754, DEFAULT_DECIMALS)),
+                    date: NaiveDate::from_ymd_opt(2020, 9, 17),
+                },
+            ],
             judicial_title: String::from("OTROS DERECHOS"),
-            visitable: String::from("SÍ"),
+            lot: None,
+            quota: None,
+            visitable: YesNoUnknown::Yes,
         });
 
         assert_eq!(asset_other, Asset::new(id, &asset_other_map));
     }
+
+    #[test]
+    fn asset_new_property_details_test() {
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - INMUEBLE (FINCA RÚSTICA)"),
+            ),
+            (BoeConcept::Area, String::from("1.250,50 m²")),
+            (BoeConcept::Quota, String::from("50 %")),
+            (BoeConcept::Idufir, String::from("47001000123456")),
+            (BoeConcept::Allotment, String::from("123")),
+            (BoeConcept::Place, String::from("LOS LLANOS")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        match Asset::try_from(("id", &data)).unwrap() {
+            Asset::Property(property) => {
+                assert_eq!(Some(Decimal::new(125_050, 2)), property.area);
+                assert_eq!(Some(Decimal::new(50, 0)), property.quota);
+                assert_eq!(
+                    Some("47001000123456"),
+                    property.idufir.as_ref().map(Idufir::as_str)
+                );
+                assert_eq!(Some("123".to_owned()), property.allotment);
+                assert_eq!(Some("LOS LLANOS".to_owned()), property.place);
+            }
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+
+        assert_eq!(Some(Decimal::new(10573, 2)), parse_measure("105.73 m2"));
+        assert_eq!(Some(Decimal::new(1200, 0)), parse_measure("1.200 m²"));
+        assert_eq!(None, parse_measure("m²"));
+        assert!("4700100012345".parse::<Idufir>().is_err());
+        assert!("4700 1000 1234 56".parse::<Idufir>().is_ok());
+
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - INMUEBLE (VIVIENDA)"),
+            ),
+            (BoeConcept::Area, String::from("NO CONSTA")),
+            (BoeConcept::Idufir, String::from("CONSTA EN EL EDICTO")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let error = Asset::try_from(("id", &data)).unwrap_err();
+        assert_eq!(DataErrorKind::InvalidIdufir, error.kind);
+    }
+
+    #[test]
+    fn asset_new_property_features_test() {
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - INMUEBLE (VIVIENDA)"),
+            ),
+            (
+                BoeConcept::Description,
+                String::from(
+                    "VIVIENDA EN PLANTA SEGUNDA CON UNA SUPERFICIE ÚTIL DE 85,20 M2 Y TRES DORMITORIOS. FINCA REGISTRAL 23.456.",
+                ),
+            ),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        match Asset::try_from(("id", &data)).unwrap() {
+            Asset::Property(property) => {
+                let features = &property.features;
+                assert_eq!(
+                    Some(Decimal::new(8520, 2)),
+                    features.usable_area.as_ref().map(|area| area.value)
+                );
+                assert_eq!(
+                    Some("23456"),
+                    features
+                        .finca_number
+                        .as_ref()
+                        .map(|finca| finca.value.as_str())
+                );
+                assert_eq!(Some(3), features.rooms.as_ref().map(|rooms| rooms.value));
+                assert_eq!(
+                    "VIVIENDA EN PLANTA SEGUNDA CON UNA SUPERFICIE ÚTIL DE 85, 20 M2 Y TRES DORMITORIOS. FINCA REGISTRAL 23. 456.",
+                    property.description
+                );
+            }
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+    }
+
+    #[test]
+    fn catastro_reference_test() {
+        let urban = "4110202UM5141A0003HH".parse::<CatastroReference>().unwrap();
+        assert_eq!(CatastroReferenceKind::Urban, urban.kind());
+        assert_eq!("4110202UM5141A", urban.parcel());
+        assert_eq!("0003", urban.unit());
+        assert_eq!("HH", urban.control());
+
+        let urban = "9872023 vh5797s 0001 wx"
+            .parse::<CatastroReference>()
+            .unwrap();
+        assert_eq!("9872023VH5797S0001WX", urban.as_str());
+
+        let rustic = "13077A018000390000MS".parse::<CatastroReference>().unwrap();
+        assert_eq!(CatastroReferenceKind::Rustic, rustic.kind());
+        assert_eq!("13077A01800039", rustic.parcel());
+
+        assert!("4110202UM5141A0003HJ".parse::<CatastroReference>().is_err());
+        assert!("4110202UM5141A0004HH".parse::<CatastroReference>().is_err());
+        assert!("4110202UM5141A0003".parse::<CatastroReference>().is_err());
+        assert!("4110202UM5141A0003H-".parse::<CatastroReference>().is_err());
+
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - INMUEBLE (VIVIENDA)"),
+            ),
+            (
+                BoeConcept::CatastroReference,
+                String::from("4110202UM5141A0003XX"),
+            ),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        let error = Asset::try_from(("id", &data)).unwrap_err();
+        assert_eq!(DataErrorKind::InvalidCatastroReference, error.kind);
+
+        match Asset::new("id", &data) {
+            Asset::Property(property) => assert_eq!(None, property.catastro_reference),
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+    }
+
+    #[test]
+    fn asset_new_other_details_test() {
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - BIEN MUEBLE (OTROS BIENES Y DERECHOS)"),
+            ),
+            (BoeConcept::AcquisitionDate, String::from("17/09/2020")),
+            (BoeConcept::Quota, String::from("33,33 %")),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+
+        match Asset::try_from(("id", &data)).unwrap() {
+            Asset::Other(other) => {
+                assert_eq!(NaiveDate::from_ymd_opt(2020, 9, 17), other.acquisition_date);
+                assert_eq!(Some(Decimal::new(3333, 2)), other.quota);
+            }
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+    }
+
+    #[test]
+    fn asset_new_encumbrances_test() {
+        let seizure = Encumbrance {
+            kind: EncumbranceKind::Seizure,
+            creditor: Some(String::from("AEAT")),
+            amount: Some(Decimal::new(1_034_754, DEFAULT_DECIMALS)),
+            date: NaiveDate::from_ymd_opt(2020, 9, 17),
+        };
+        let description = String::from(
+            "GARAJE SITO EN LOGROÑO. DILIGENCIA DE EMBARGO A FAVOR DE LA AEAT(2111623311338X), CON IMPORTE PENDIENTE A FECHA 17-09-2020 DE 10.347,54€.",
+        );
+
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - INMUEBLE (GARAJE)"),
+            ),
+            (BoeConcept::Description, description.clone()),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        match Asset::new("id", &data) {
+            Asset::Property(property) => {
+                assert_eq!(vec![seizure.clone()], property.encumbrances)
+            }
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+
+        let data: HashMap<BoeConcept, String> = [
+            (
+                BoeConcept::Header,
+                String::from("BIEN 1 - VEHÍCULO (TURISMOS)"),
+            ),
+            (BoeConcept::Description, description),
+        ]
+        .iter()
+        .cloned()
+        .collect();
+        match Asset::new("id", &data) {
+            Asset::Vehicle(vehicle) => assert_eq!(vec![seizure], vehicle.encumbrances),
+            asset => panic!("unexpected asset {:?}", asset),
+        }
+    }
 }
@@ -0,0 +1,504 @@
+use crate::provinces::Province;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// Vehicle identification number (número de bastidor).
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct Vin(String);
+
+// World manufacturer identifiers, three characters entries take precedence.
+const WMI_MANUFACTURERS: &[(&str, &str)] = &[
+    ("1FA", "FORD"),
+    ("1FT", "FORD"),
+    ("1G", "GENERAL MOTORS"),
+    ("1HG", "HONDA"),
+    ("1J", "JEEP"),
+    ("1N", "NISSAN"),
+    ("2HG", "HONDA"),
+    ("2T", "TOYOTA"),
+    ("3VW", "VOLKSWAGEN"),
+    ("4T", "TOYOTA"),
+    ("5YJ", "TESLA"),
+    ("JF", "SUBARU"),
+    ("JH", "HONDA"),
+    ("JK", "KAWASAKI"),
+    ("JM", "MAZDA"),
+    ("JN", "NISSAN"),
+    ("JS", "SUZUKI"),
+    ("JT", "TOYOTA"),
+    ("JY", "YAMAHA"),
+    ("KL", "DAEWOO"),
+    ("KM", "HYUNDAI"),
+    ("KN", "KIA"),
+    ("KPT", "SSANGYONG"),
+    ("LRW", "TESLA"),
+    ("MA1", "MAHINDRA"),
+    ("MAL", "HYUNDAI"),
+    ("MR0", "TOYOTA"),
+    ("NMT", "TOYOTA"),
+    ("NM0", "FORD"),
+    ("SAJ", "JAGUAR"),
+    ("SAL", "LAND ROVER"),
+    ("SB1", "TOYOTA"),
+    ("SCC", "LOTUS"),
+    ("SJN", "NISSAN"),
+    ("TMB", "SKODA"),
+    ("TMA", "HYUNDAI"),
+    ("TRU", "AUDI"),
+    ("TSM", "SUZUKI"),
+    ("U5Y", "KIA"),
+    ("UU", "DACIA"),
+    ("VF1", "RENAULT"),
+    ("VF3", "PEUGEOT"),
+    ("VF6", "RENAULT TRUCKS"),
+    ("VF7", "CITROEN"),
+    ("VF8", "MATRA"),
+    ("VFA", "ALPINE"),
+    ("VNK", "TOYOTA"),
+    ("VR1", "DS"),
+    ("VR3", "PEUGEOT"),
+    ("VR7", "CITROEN"),
+    ("VSE", "SUZUKI"),
+    ("VSK", "NISSAN"),
+    ("VSS", "SEAT"),
+    ("VSX", "OPEL"),
+    ("VS6", "FORD"),
+    ("VS7", "CITROEN"),
+    ("VV9", "TAURO"),
+    ("VWV", "VOLKSWAGEN"),
+    ("VX1", "ZASTAVA"),
+    ("WAU", "AUDI"),
+    ("WBA", "BMW"),
+    ("WBS", "BMW"),
+    ("WB1", "BMW MOTORRAD"),
+    ("WDB", "MERCEDES-BENZ"),
+    ("WDC", "MERCEDES-BENZ"),
+    ("WDD", "MERCEDES-BENZ"),
+    ("WDF", "MERCEDES-BENZ"),
+    ("WF0", "FORD"),
+    ("WJM", "IVECO"),
+    ("WMA", "MAN"),
+    ("WME", "SMART"),
+    ("WMW", "MINI"),
+    ("WP0", "PORSCHE"),
+    ("WP1", "PORSCHE"),
+    ("WUA", "AUDI"),
+    ("WVG", "VOLKSWAGEN"),
+    ("WVW", "VOLKSWAGEN"),
+    ("WV1", "VOLKSWAGEN"),
+    ("WV2", "VOLKSWAGEN"),
+    ("W0L", "OPEL"),
+    ("W0V", "OPEL"),
+    ("XLR", "DAF"),
+    ("XTA", "LADA"),
+    ("YS2", "SCANIA"),
+    ("YS3", "SAAB"),
+    ("YV1", "VOLVO"),
+    ("YV2", "VOLVO TRUCKS"),
+    ("ZAM", "MASERATI"),
+    ("ZAP", "PIAGGIO"),
+    ("ZAR", "ALFA ROMEO"),
+    ("ZCF", "IVECO"),
+    ("ZDM", "DUCATI"),
+    ("ZFA", "FIAT"),
+    ("ZFF", "FERRARI"),
+    ("ZHW", "LAMBORGHINI"),
+    ("ZLA", "LANCIA"),
+];
+
+impl Vin {
+    /// Number of characters of a VIN.
+    pub const LENGTH: usize = 17;
+
+    const CHECK_DIGIT_POSITION: usize = 8;
+    const WEIGHTS: [u32; Vin::LENGTH] = [8, 7, 6, 5, 4, 3, 2, 10, 0, 9, 8, 7, 6, 5, 4, 3, 2];
+
+    /// VIN characters.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// World manufacturer identifier, the first three characters.
+    pub fn wmi(&self) -> &str {
+        &self.0[..3]
+    }
+
+    /// Manufacturer decoded from the world manufacturer identifier.
+    pub fn manufacturer(&self) -> Option<&'static str> {
+        let wmi = self.wmi();
+
+        WMI_MANUFACTURERS
+            .iter()
+            .find(|(prefix, _)| prefix.len() == 3 && wmi == *prefix)
+            .or_else(|| {
+                WMI_MANUFACTURERS
+                    .iter()
+                    .find(|(prefix, _)| prefix.len() == 2 && wmi.starts_with(prefix))
+            })
+            .map(|(_, manufacturer)| *manufacturer)
+    }
+
+    /// Only north american VINs are required to carry a check digit.
+    pub fn has_check_digit(&self) -> bool {
+        matches!(self.0.as_bytes()[0], b'1'..=b'5')
+    }
+
+    fn transliterate(c: u8) -> u32 {
+        match c {
+            b'0'..=b'9' => (c - b'0') as u32,
+            b'A'..=b'H' => (c - b'A') as u32 + 1,
+            b'J'..=b'N' => (c - b'J') as u32 + 1,
+            b'P' => 7,
+            b'R' => 9,
+            _ => (c - b'S') as u32 + 2,
+        }
+    }
+
+    fn check_digit(vin: &[u8]) -> u8 {
+        let sum: u32 = vin
+            .iter()
+            .zip(Vin::WEIGHTS.iter())
+            .map(|(c, weight)| Vin::transliterate(*c) * weight)
+            .sum();
+
+        match sum % 11 {
+            10 => b'X',
+            remainder => b'0' + remainder as u8,
+        }
+    }
+}
+
+impl Display for Vin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Vin {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let vin: String = s
+            .trim_end_matches('.')
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect::<String>()
+            .to_ascii_uppercase();
+
+        if vin.len() != Vin::LENGTH
+            || !vin
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() && c != 'I' && c != 'O' && c != 'Q')
+        {
+            return Err(());
+        }
+
+        let vin = Vin(vin);
+        if vin.has_check_digit()
+            && Vin::check_digit(vin.0.as_bytes()) != vin.0.as_bytes()[Vin::CHECK_DIGIT_POSITION]
+        {
+            return Err(());
+        }
+
+        Ok(vin)
+    }
+}
+
+/// Registration dates range a vehicle was licensed in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub struct RegistrationPeriod {
+    /// First possible registration date.
+    pub from: NaiveDate,
+    /// Last possible registration date, `None` if plates are still issued.
+    pub to: Option<NaiveDate>,
+}
+
+fn ymd(date: (i32, u32, u32)) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.0, date.1, date.2).expect("valid registration date")
+}
+
+// Approximate date each letter series of the current plates started to be issued.
+const CURRENT_PLATE_SERIES: &[(char, (i32, u32, u32))] = &[
+    ('B', (2000, 9, 18)),
+    ('C', (2003, 2, 1)),
+    ('D', (2005, 5, 1)),
+    ('F', (2007, 1, 1)),
+    ('G', (2009, 10, 1)),
+    ('H', (2012, 8, 1)),
+    ('J', (2015, 1, 1)),
+    ('K', (2017, 4, 1)),
+    ('L', (2019, 8, 1)),
+    ('M', (2022, 8, 1)),
+    ('N', (2025, 5, 1)),
+];
+
+// Letters used by current plates, vowels, Ñ and Q are excluded.
+const CURRENT_PLATE_LETTERS: &str = "BCDFGHJKLMNPRSTVWXYZ";
+
+const PROVINCE_CODES: &[(&str, Province)] = &[
+    ("A", Province::Alicante),
+    ("AB", Province::Albacete),
+    ("AL", Province::Almeria),
+    ("AV", Province::Avila),
+    ("B", Province::Barcelona),
+    ("BA", Province::Badajoz),
+    ("BI", Province::Vizcaya),
+    ("BU", Province::Burgos),
+    ("C", Province::ACorunia),
+    ("CA", Province::Cadiz),
+    ("CC", Province::Caceres),
+    ("CE", Province::Ceuta),
+    ("CO", Province::Cordoba),
+    ("CR", Province::CiudadReal),
+    ("CS", Province::Castellon),
+    ("CU", Province::Cuenca),
+    ("GC", Province::LasPalmas),
+    ("GE", Province::Gerona),
+    ("GI", Province::Gerona),
+    ("GR", Province::Granada),
+    ("GU", Province::Guadalajara),
+    ("H", Province::Huelva),
+    ("HU", Province::Huesca),
+    ("IB", Province::Baleares),
+    ("J", Province::Jaen),
+    ("L", Province::Lerida),
+    ("LE", Province::Leon),
+    ("LO", Province::LaRioja),
+    ("LU", Province::Lugo),
+    ("M", Province::Madrid),
+    ("MA", Province::Malaga),
+    ("ML", Province::Melilla),
+    ("MU", Province::Murcia),
+    ("NA", Province::Navarra),
+    ("O", Province::Asturias),
+    ("OR", Province::Orense),
+    ("OU", Province::Orense),
+    ("P", Province::Palencia),
+    ("PM", Province::Baleares),
+    ("PO", Province::Pontevedra),
+    ("S", Province::Cantabria),
+    ("SA", Province::Salamanca),
+    ("SE", Province::Sevilla),
+    ("SG", Province::Segovia),
+    ("SO", Province::Soria),
+    ("SS", Province::Guipuzcoa),
+    ("T", Province::Tarragona),
+    ("TE", Province::Teruel),
+    ("TF", Province::SantaCruzDeTenerife),
+    ("TO", Province::Toledo),
+    ("V", Province::Valencia),
+    ("VA", Province::Valladolid),
+    ("VI", Province::Alava),
+    ("Z", Province::Zaragoza),
+    ("ZA", Province::Zamora),
+];
+
+/// Spanish license plate (matrícula).
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
+pub enum LicensePlate {
+    /// National format used since september 2000: 1234 BCD.
+    Current {
+        /// Plate number.
+        number: u16,
+        /// Plate letters.
+        letters: String,
+    },
+    /// Provincial format used until september 2000: M-1234-AB or M-123456.
+    Provincial {
+        /// Province code.
+        code: String,
+        /// Plate number.
+        number: u32,
+        /// Plate letters, older plates only have numbers.
+        letters: Option<String>,
+    },
+}
+
+impl LicensePlate {
+    /// Province the vehicle was registered in, only known for provincial plates.
+    pub fn province(&self) -> Option<Province> {
+        match self {
+            Self::Current { .. } => None,
+            Self::Provincial { code, .. } => PROVINCE_CODES
+                .iter()
+                .find(|(province_code, _)| province_code == code)
+                .map(|(_, province)| *province),
+        }
+    }
+
+    /// Approximate period the plate was issued in, `None` for provincial plates
+    /// as each province issued its series at its own pace.
+    pub fn registration_period(&self) -> Option<RegistrationPeriod> {
+        match self {
+            Self::Current { letters, .. } => {
+                let first = letters.chars().next().unwrap_or('B');
+                let position = CURRENT_PLATE_SERIES
+                    .iter()
+                    .rposition(|(series, _)| *series <= first)
+                    .unwrap_or(0);
+
+                Some(RegistrationPeriod {
+                    from: ymd(CURRENT_PLATE_SERIES[position].1),
+                    to: CURRENT_PLATE_SERIES
+                        .get(position + 1)
+                        .and_then(|(_, next)| ymd(*next).pred_opt()),
+                })
+            }
+            Self::Provincial { .. } => None,
+        }
+    }
+}
+
+impl Display for LicensePlate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Current { number, letters } => write!(f, "{:04} {}", number, letters),
+            Self::Provincial {
+                code,
+                number,
+                letters: Some(letters),
+            } => write!(f, "{}-{:04}-{}", code, number, letters),
+            Self::Provincial {
+                code,
+                number,
+                letters: None,
+            } => write!(f, "{}-{:06}", code, number),
+        }
+    }
+}
+
+impl FromStr for LicensePlate {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let plate: String = s
+            .trim_end_matches('.')
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '-')
+            .collect::<String>()
+            .to_uppercase();
+
+        let code: String = plate
+            .chars()
+            .take_while(char::is_ascii_alphabetic)
+            .collect();
+        let number: String = plate[code.len()..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect();
+        let letters = &plate[code.len() + number.len()..];
+
+        if number.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(());
+        }
+
+        if code.is_empty() {
+            if number.len() == 4
+                && letters.len() == 3
+                && letters.chars().all(|c| CURRENT_PLATE_LETTERS.contains(c))
+            {
+                return Ok(Self::Current {
+                    number: number.parse::<u16>().map_err(|_| ())?,
+                    letters: letters.to_owned(),
+                });
+            }
+            return Err(());
+        }
+
+        if !PROVINCE_CODES
+            .iter()
+            .any(|(province_code, _)| *province_code == code)
+        {
+            return Err(());
+        }
+
+        match letters.len() {
+            0 if number.len() <= 6 => Ok(Self::Provincial {
+                code,
+                number: number.parse::<u32>().map_err(|_| ())?,
+                letters: None,
+            }),
+            1 | 2 if number.len() == 4 => Ok(Self::Provincial {
+                code,
+                number: number.parse::<u32>().map_err(|_| ())?,
+                letters: Some(letters.to_owned()),
+            }),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vin_test() {
+        let vin = "WAUZZZ8E92A267004.".parse::<Vin>().unwrap();
+        assert_eq!("WAUZZZ8E92A267004", vin.as_str());
+        assert_eq!("WAU", vin.wmi());
+        assert_eq!(Some("AUDI"), vin.manufacturer());
+        assert!(!vin.has_check_digit());
+
+        let vin = "1m8gdm9axkp042788".parse::<Vin>().unwrap();
+        assert!(vin.has_check_digit());
+        assert_eq!(None, vin.manufacturer());
+        assert_eq!(
+            Some("NISSAN"),
+            "JN1TANT31U0000001".parse::<Vin>().unwrap().manufacturer()
+        );
+
+        assert!("1M8GDM9A1KP042788".parse::<Vin>().is_err());
+        assert!("WAUZZZ8E92A26700".parse::<Vin>().is_err());
+        assert!("WAUZZZ8E92A2670O4".parse::<Vin>().is_err());
+        assert!("NO CONSTA".parse::<Vin>().is_err());
+    }
+
+    #[test]
+    fn license_plate_test() {
+        let plate = "8868CXV".parse::<LicensePlate>().unwrap();
+        assert_eq!(
+            LicensePlate::Current {
+                number: 8868,
+                letters: String::from("CXV")
+            },
+            plate
+        );
+        assert_eq!("8868 CXV", plate.to_string());
+        assert_eq!(None, plate.province());
+        assert_eq!(
+            Some(RegistrationPeriod {
+                from: ymd((2003, 2, 1)),
+                to: Some(ymd((2005, 4, 30)))
+            }),
+            plate.registration_period()
+        );
+
+        let plate = "M-1234-AB".parse::<LicensePlate>().unwrap();
+        assert_eq!(Some(Province::Madrid), plate.province());
+        assert_eq!("M-1234-AB", plate.to_string());
+        assert_eq!(None, plate.registration_period());
+
+        let plate = "SS 123456".parse::<LicensePlate>().unwrap();
+        assert_eq!(Some(Province::Guipuzcoa), plate.province());
+        assert_eq!(None, plate.registration_period());
+
+        assert_eq!(
+            None,
+            "0001NBB"
+                .parse::<LicensePlate>()
+                .unwrap()
+                .registration_period()
+                .unwrap()
+                .to
+        );
+
+        assert!("8868CAV".parse::<LicensePlate>().is_err());
+        assert!("XX-1234-AB".parse::<LicensePlate>().is_err());
+        assert!("M-12-AB".parse::<LicensePlate>().is_err());
+        assert!("NO CONSTA".parse::<LicensePlate>().is_err());
+    }
+}
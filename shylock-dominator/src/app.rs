@@ -14,6 +14,7 @@ use crate::vehicle_view::VehicleView;
 use dominator::{clone, html, link, routing, stylesheet, Dom};
 use futures_signals::signal::{Mutable, Signal, SignalExt};
 use futures_signals::signal_vec::MutableVec;
+use shylock_data::ExportError;
 use std::sync::Arc;
 
 pub struct App {
@@ -195,3 +196,23 @@ impl App {
         })
     }
 }
+
+pub(crate) fn render_data_error(error: &ExportError) -> Dom {
+    html!("section", {
+        .class(&*ROOT_CLASS)
+        .children(&mut [
+            html!("h2", {
+                .text("No se han podido cargar las subastas")
+            }),
+            html!("p", {
+                .text("Los datos publicados no son compatibles con esta versión de la aplicación, ")
+                .text("vuelve a intentarlo más tarde.")
+            }),
+            html!("p", {
+                .text("Detalle: ")
+                .text(&error.to_string())
+            }),
+            crate::footer::render_footer(),
+        ])
+    })
+}
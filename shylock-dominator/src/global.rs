@@ -1,20 +1,25 @@
 use dominator::{class, pseudo};
-use miniz_oxide::inflate::decompress_to_vec;
 use once_cell::sync::{Lazy, OnceCell};
 use rust_decimal::Decimal;
+use shylock_data::communities::AutonomousCommunity;
+use shylock_data::costs::CostRates;
 use shylock_data::provinces::Province;
 use shylock_data::types::{Asset, Auction};
+use shylock_data::{Export, ExportError};
 use std::cmp::max;
 use std::collections::{BTreeSet, HashMap};
-use wasm_bindgen::JsValue;
+
+use crate::util::property_city;
 
 pub static ASSETS: OnceCell<Vec<Asset>> = OnceCell::new();
 pub static AUCTIONS: OnceCell<HashMap<String, Auction>> = OnceCell::new();
 pub static MAX_AUCTION_VALUE: OnceCell<Decimal> = OnceCell::new();
+pub static COMMUNITIES: OnceCell<BTreeSet<AutonomousCommunity>> = OnceCell::new();
 pub static PROVINCES: OnceCell<BTreeSet<Province>> = OnceCell::new();
 pub static CITIES_PROVINCES: OnceCell<BTreeSet<(&str, Province)>> = OnceCell::new();
 pub static CAR_BRANDS: OnceCell<BTreeSet<&str>> = OnceCell::new();
 pub static CAR_BRAND_MODELS: OnceCell<BTreeSet<(&str, &str)>> = OnceCell::new();
+pub static COST_RATES: Lazy<CostRates> = Lazy::new(CostRates::default);
 
 pub const DEFAULT_ICON_COLOR: &str = "black";
 pub const DEFAULT_ICON_SIZE: &str = "12";
@@ -157,25 +162,22 @@ pub static NAV_SELECTED_CLASS: Lazy<String> = Lazy::new(|| {
     }
 });
 
-pub(crate) async fn set_global_info() -> Result<(), JsValue> {
-    let hm: HashMap<String, Auction> = ciborium::de::from_reader(
-        &decompress_to_vec(include_bytes!("../auctions.cbor.zlib")).expect("Failed to decompress!")
-            [..],
-    )
-    .unwrap();
+pub(crate) async fn set_global_info() -> Result<(), ExportError> {
+    let auctions: Export<HashMap<String, Auction>> =
+        Export::decode(include_bytes!("../auctions.cbor.zlib"))?;
+    let assets: Export<Vec<Asset>> = Export::decode(include_bytes!("../assets.cbor.zlib"))?;
+
+    log::info!(
+        "Loaded data schema version {} generated at {:?}",
+        assets.header.schema_version,
+        assets.header.generated_at
+    );
 
-    if AUCTIONS.set(hm).is_err() {
+    if AUCTIONS.set(auctions.data).is_err() {
         log::error!("Unable to set global auctions");
     }
 
-    let assets: Vec<Asset> =
-        ciborium::de::from_reader(
-            &decompress_to_vec(include_bytes!("../assets.cbor.zlib"))
-                .expect("Failed to decompress")[..],
-        )
-        .unwrap();
-
-    if ASSETS.set(assets).is_err() {
+    if ASSETS.set(assets.data).is_err() {
         log::error!("Unable to set global assets");
     }
 
@@ -229,6 +231,20 @@ pub(crate) async fn set_global_info() -> Result<(), JsValue> {
         log::error!("Not able to set provinces");
     };
 
+    if COMMUNITIES
+        .set(
+            PROVINCES
+                .get()
+                .unwrap()
+                .iter()
+                .map(Province::community)
+                .collect::<BTreeSet<AutonomousCommunity>>(),
+        )
+        .is_err()
+    {
+        log::error!("Not able to set communities");
+    };
+
     if CITIES_PROVINCES
         .set(
             ASSETS
@@ -236,7 +252,7 @@ pub(crate) async fn set_global_info() -> Result<(), JsValue> {
                 .unwrap()
                 .iter()
                 .filter_map(|asset| match asset {
-                    Asset::Property(property) => Some((&property.city[..], property.province)),
+                    Asset::Property(property) => Some((property_city(property), property.province)),
                     Asset::Vehicle(_) => None,
                     Asset::Other(_) => None,
                 })
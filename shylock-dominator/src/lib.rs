@@ -10,7 +10,7 @@
 
 use std::sync::Arc;
 
-use crate::app::App;
+use crate::app::{render_data_error, App};
 use dotenvy_macro::dotenv;
 use futures_signals::signal_vec::MutableVec;
 use global::{set_global_info, ASSETS};
@@ -47,7 +47,11 @@ pub async fn main_js() -> Result<(), JsValue> {
     #[cfg(debug_assertions)]
     console_error_panic_hook::set_once();
 
-    set_global_info().await?;
+    if let Err(error) = set_global_info().await {
+        log::error!("Unable to load auctions data: {}", error);
+        dominator::append_dom(&dominator::get_id("app"), render_data_error(&error));
+        return Ok(());
+    }
 
     let properties: MutableVec<Arc<PropertyView>> = MutableVec::new();
     let vehicles: MutableVec<Arc<VehicleView>> = MutableVec::new();
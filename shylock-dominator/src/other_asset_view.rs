@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use dominator::{clone, events, html, Dom};
 use futures_signals::signal::{Mutable, SignalExt};
-use shylock_data::{BidInfo, Other};
+use shylock_data::{Auction, BidInfo, OpportunityConfig, Other};
 
 use crate::{
     feather::render_svg_external_link_icon,
@@ -10,7 +10,11 @@ use crate::{
         AUCTIONS, CELL_CLASS, CELL_EXPANDED_CLASS, CELL_FLEX_CONTAINER_CLASS, CELL_FLEX_ITEM_CLASS,
         DEFAULT_ICON_COLOR, DEFAULT_ICON_SIZE, ROW_CLASS,
     },
-    util::{format_valuation, is_targeted_asset, new_bidinfo, summarize, DESCRIPTION_TEXT_LIMIT},
+    util::{
+        boe_auction_link, format_closes_in, format_encumbrances, format_local_datetime,
+        format_measure, format_opportunity, format_state_history, format_valuation,
+        is_targeted_asset, render_attachments, summarize, DESCRIPTION_TEXT_LIMIT,
+    },
 };
 
 #[derive(Debug)]
@@ -18,18 +22,15 @@ pub struct OtherAssetView {
     pub anchor_hovered: Mutable<bool>,
     pub show_expanded: Mutable<bool>,
     pub filtered_in: Mutable<bool>,
+    pub auction: &'static Auction,
     pub other: &'static Other,
     pub bidinfo: BidInfo,
 }
 
 impl OtherAssetView {
     pub fn new(other: &'static Other) -> Arc<Self> {
-        let auction_bidinfo = &AUCTIONS
-            .get()
-            .unwrap()
-            .get(&other.auction_id)
-            .unwrap()
-            .bidinfo;
+        let auction = AUCTIONS.get().unwrap().get(&other.auction_id).unwrap();
+        let auction_bidinfo = &auction.bidinfo;
 
         let bidinfo = if other.bidinfo.is_none() {
             auction_bidinfo
@@ -40,8 +41,9 @@ impl OtherAssetView {
             anchor_hovered: Mutable::new(false),
             show_expanded: Mutable::new(false),
             filtered_in: Mutable::new(true),
+            auction,
             other,
-            bidinfo: new_bidinfo(bidinfo, auction_bidinfo),
+            bidinfo: bidinfo.merged_with(auction_bidinfo),
         })
     }
 
@@ -56,7 +58,7 @@ impl OtherAssetView {
                     .text("Identificador subasta: ")
                     .child(html!("a",{
                         .attr("alt", "Enlace externo a subastas BOE")
-                        .attr("href", &format!("https://subastas.boe.es/detalleSubasta.php?idSub={}",&view.other.auction_id))
+                        .attr("href", &boe_auction_link(&view.other.auction_id, view.other.lot))
                         .attr("target", "_blank")
                         .attr("rel", "external nofollow")
                         .text(&view.other.auction_id)
@@ -72,6 +74,35 @@ impl OtherAssetView {
                         }))
                     }))
                 }))
+                .child(if let Some(lot) = view.other.lot {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Lote: ")
+                        .text(&lot.to_string())
+                        .text(".")
+                    })
+                } else {
+                    Dom::empty()
+                })
+                .child(html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Cierre: ")
+                    .text(&format_local_datetime(&view.auction.end_date))
+                    .text(" (")
+                    .text(&format_closes_in(&view.auction.end_date))
+                    .text(").")
+                }))
+                .child(if view.auction.state_history.is_empty() {
+                    Dom::empty()
+                } else {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Historial: ")
+                        .text(&format_state_history(&view.auction.state_history))
+                        .text(".")
+                    })
+                })
+                .child(render_attachments(&view.other.attachments))
                 .child(html!("span", {
                     .class(&*CELL_FLEX_ITEM_CLASS)
                     .text("Categoría: ")
@@ -96,6 +127,49 @@ impl OtherAssetView {
                         else {"."}
                     )
                 }))
+                .child(if let Some(acquisition_date) = &view.other.acquisition_date {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Fecha adquisición: ")
+                        .text(&acquisition_date.format("%d-%m-%Y").to_string())
+                        .text(".")
+                    })
+                } else {
+                    Dom::empty()
+                })
+                .child(if let Some(quota) = &view.other.quota {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Cuota: ")
+                        .text(&format_measure(quota))
+                        .text(" %.")
+                    })
+                } else {
+                    Dom::empty()
+                })
+                .child(if view.other.encumbrances.is_empty() {
+                    Dom::empty()
+                } else {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Cargas: ")
+                        .text(&format_encumbrances(&view.other.encumbrances))
+                        .text(".")
+                    })
+                })
+                .child({
+                    let opportunity = bidinfo.opportunity(&OpportunityConfig::default());
+                    if opportunity.is_opportunity {
+                        html!("span", {
+                            .class(&*CELL_FLEX_ITEM_CLASS)
+                            .text("Oportunidad: ")
+                            .text(&format_opportunity(&opportunity))
+                            .text(".")
+                        })
+                    } else {
+                        Dom::empty()
+                    }
+                })
                 .child(html!("span", {
                     .class(&*CELL_FLEX_ITEM_CLASS)
                     .text("Valor subasta: ")
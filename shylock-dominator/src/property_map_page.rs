@@ -1,7 +1,6 @@
 use dominator::{clone, html, Dom};
 use futures_signals::signal::{Mutable, SignalExt};
 use futures_signals::signal_vec::MutableVec;
-use rust_decimal::prelude::ToPrimitive;
 use wasm_bindgen::prelude::*;
 
 use crate::leaflet::LeafletMap;
@@ -48,11 +47,7 @@ impl PropertyMapPage {
     }
 
     fn is_opportunity(&self, property_view: &PropertyView) -> bool {
-        let auction_limit = 0.7;
-        let target_value = property_view.bidinfo.value.to_f64().unwrap_or(0.0) * auction_limit;
-
-        property_view.bidinfo.claim_quantity.to_f64().unwrap_or(0.0) > 1.0
-            && target_value > property_view.bidinfo.claim_quantity.to_f64().unwrap_or(0.0)
+        property_view.bidinfo.is_opportunity()
     }
 
     pub fn render(page: Arc<Self>) -> Vec<Dom> {
@@ -5,7 +5,9 @@ use dominator::{clone, events, html, with_node, Dom};
 use futures_signals::signal::{Mutable, SignalExt};
 use futures_signals::signal_vec::{MutableVec, SignalVecExt};
 use rust_decimal::prelude::ToPrimitive;
+use shylock_data::communities::AutonomousCommunity;
 use shylock_data::provinces::Province;
+use shylock_data::{OpportunityConfig, YesNoUnknown};
 use web_sys::{HtmlInputElement, HtmlSelectElement};
 
 use crate::feather::{
@@ -15,13 +17,14 @@ use crate::global::{
     CELL_CLASS, CELL_CLICKABLE_CLASS, DEFAULT_ICON_COLOR, DEFAULT_ICON_SIZE,
     FILTER_FLEX_CONTAINER_CLASS, TABLE_CLASS, TBODY_CLASS, THEAD_CLASS,
 };
-use crate::util::SortingOrder;
+use crate::util::{property_city, SortingOrder};
 use crate::{
-    global::{CITIES_PROVINCES, PROVINCES},
+    global::{CITIES_PROVINCES, COMMUNITIES, PROVINCES},
     property_view::PropertyView,
 };
 
 const ALL_CITIES_STR: &str = "Todas las ciudades";
+const ALL_PROVINCES_STR: &str = "Todas las provincias";
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PropertySorting {
@@ -32,19 +35,21 @@ pub enum PropertySorting {
     ByReverseValue,
 }
 
-const DEFAULT_OPPORTUNITY_VALUE: f64 = 0.7;
-
 pub struct PropertyPage {
     property_list: MutableVec<Arc<PropertyView>>,
     city_options: MutableVec<&'static str>,
     city_property_filter: Mutable<&'static str>,
+    community_filter: Mutable<AutonomousCommunity>,
     opportunity_filter: Mutable<bool>,
-    opportunity_filter_threshold: Mutable<f64>,
+    opportunity_config: Mutable<OpportunityConfig>,
     province_filter: Mutable<Province>,
+    province_options: MutableVec<Province>,
     province_sorting: Mutable<SortingOrder>,
     value_sorting: Mutable<SortingOrder>,
     sorting: Mutable<PropertySorting>,
     bid_step_filter: Mutable<bool>,
+    visitable_filter: Mutable<bool>,
+    not_primary_residence_filter: Mutable<bool>,
 }
 
 impl PropertyPage {
@@ -53,21 +58,25 @@ impl PropertyPage {
             property_list,
             city_options: MutableVec::new(),
             city_property_filter: Mutable::new(ALL_CITIES_STR),
+            community_filter: Mutable::new(AutonomousCommunity::All),
             opportunity_filter: Mutable::new(true),
-            opportunity_filter_threshold: Mutable::new(DEFAULT_OPPORTUNITY_VALUE),
+            opportunity_config: Mutable::new(OpportunityConfig::default()),
             province_filter: Mutable::new(Province::All),
+            province_options: MutableVec::new(),
             province_sorting: Mutable::new(SortingOrder::None),
             value_sorting: Mutable::new(SortingOrder::None),
             sorting: Mutable::new(PropertySorting::None),
             bid_step_filter: Mutable::new(false),
+            visitable_filter: Mutable::new(false),
+            not_primary_residence_filter: Mutable::new(false),
         })
     }
 
-    fn get_province(selected_index: usize) -> Province {
+    fn get_community(selected_index: usize) -> AutonomousCommunity {
         if selected_index == 0 {
-            Province::All
+            AutonomousCommunity::All
         } else {
-            *PROVINCES
+            *COMMUNITIES
                 .get()
                 .unwrap()
                 .iter()
@@ -78,6 +87,15 @@ impl PropertyPage {
         }
     }
 
+    fn filter_by_community(&self, property_view: &Arc<PropertyView>) -> bool {
+        let community = *self.community_filter.lock_ref();
+        if community == AutonomousCommunity::All {
+            true
+        } else {
+            property_view.property.province.community() == community
+        }
+    }
+
     fn filter_by_province(&self, property_view: &Arc<PropertyView>) -> bool {
         let province = *self.province_filter.lock_ref();
         if province == Province::All {
@@ -92,7 +110,7 @@ impl PropertyPage {
         if city == ALL_CITIES_STR {
             true
         } else {
-            property_view.property.city == city
+            property_city(property_view.property) == city
         }
     }
 
@@ -100,11 +118,10 @@ impl PropertyPage {
         if *self.opportunity_filter.lock_ref() {
             return true;
         }
-        let auction_limit = *self.opportunity_filter_threshold.lock_ref();
-        let target_value = property_view.bidinfo.value.to_f64().unwrap_or(0.0) * auction_limit;
-
-        property_view.bidinfo.claim_quantity.to_f64().unwrap_or(0.0) > 1.0
-            && target_value > property_view.bidinfo.claim_quantity.to_f64().unwrap_or(0.0)
+        property_view
+            .bidinfo
+            .opportunity(&self.opportunity_config.lock_ref())
+            .is_opportunity
     }
 
     fn filter_by_bid_step(&self, property_view: &Arc<PropertyView>) -> bool {
@@ -115,18 +132,38 @@ impl PropertyPage {
         property_view.bidinfo.bid_step.to_f64().unwrap_or(0.0) <= 0.0
     }
 
+    fn filter_by_visitable(&self, property_view: &Arc<PropertyView>) -> bool {
+        if !*self.visitable_filter.lock_ref() {
+            return true;
+        }
+
+        property_view.property.visitable == YesNoUnknown::Yes
+    }
+
+    fn filter_by_not_primary_residence(&self, property_view: &Arc<PropertyView>) -> bool {
+        if !*self.not_primary_residence_filter.lock_ref() {
+            return true;
+        }
+
+        property_view.property.primary_residence == YesNoUnknown::No
+    }
+
     fn filter(&self) {
         for property_view in self.property_list.lock_ref().iter() {
             property_view.filtered_in.set_neq(
-                self.filter_by_province(property_view)
+                self.filter_by_community(property_view)
+                    && self.filter_by_province(property_view)
                     && self.filter_by_city(property_view)
                     && self.filter_by_opportunity(property_view)
-                    && self.filter_by_bid_step(property_view),
+                    && self.filter_by_bid_step(property_view)
+                    && self.filter_by_visitable(property_view)
+                    && self.filter_by_not_primary_residence(property_view),
             );
         }
     }
 
     fn update_city_options(&self) {
+        let selected_community = *self.community_filter.lock_ref();
         let selected_province = *self.province_filter.lock_ref();
         self.city_options.lock_mut().clear();
         self.city_options.lock_mut().push_cloned(ALL_CITIES_STR);
@@ -135,7 +172,10 @@ impl PropertyPage {
             .unwrap()
             .iter()
             .for_each(|(city, province)| {
-                if selected_province == Province::All || selected_province == *province {
+                if (selected_community == AutonomousCommunity::All
+                    || selected_community == province.community())
+                    && (selected_province == Province::All || selected_province == *province)
+                {
                     self.city_options.lock_mut().push_cloned(city);
                 }
             });
@@ -145,32 +185,62 @@ impl PropertyPage {
         }
     }
 
-    fn render_province_options(&self) -> Vec<Dom> {
-        let mut provinces = PROVINCES
+    fn render_community_options(&self) -> Vec<Dom> {
+        let mut communities = COMMUNITIES
             .get()
             .unwrap()
             .iter()
             .enumerate()
-            .map(|(i, province)| {
+            .map(|(i, community)| {
                 html!("option", {
                     .attr("value", &(i+1).to_string())
-                    .text(province.name())
+                    .text(community.name())
                 })
             })
             .collect::<Vec<Dom>>();
 
-        provinces.insert(
+        communities.insert(
             0,
             html!("option", {
                 .attr("value", &0.to_string())
-                .text("Todas las provincias")
+                .text("Todas las comunidades")
             }),
         );
+        communities
+    }
+
+    fn province_name(province: &Province) -> &str {
+        if *province == Province::All {
+            ALL_PROVINCES_STR
+        } else {
+            province.name()
+        }
+    }
+
+    fn update_province_options(&self) {
+        let selected_community = *self.community_filter.lock_ref();
+        self.province_options.lock_mut().clear();
+        self.province_options.lock_mut().push_cloned(Province::All);
+        PROVINCES.get().unwrap().iter().for_each(|province| {
+            if selected_community == AutonomousCommunity::All
+                || selected_community == province.community()
+            {
+                self.province_options.lock_mut().push_cloned(*province);
+            }
+        });
+        let selected_province = *self.province_filter.lock_ref();
+        if !self
+            .province_options
+            .lock_ref()
+            .contains(&selected_province)
+        {
+            *self.province_filter.lock_mut() = Province::All;
+        }
         self.update_city_options();
-        provinces
     }
 
     fn render_filter_section(page: Arc<Self>) -> Dom {
+        page.update_province_options();
         html!("div", {
             .class(&*FILTER_FLEX_CONTAINER_CLASS)
             .children(&mut [
@@ -192,6 +262,26 @@ impl PropertyPage {
                      }))
                 })
             }),
+            html!("label", {
+                .visible(false)
+                .attr("for", "select-community")
+                .text("Filtrado por comunidad autónoma:")
+            }),
+            html!("select" => HtmlSelectElement, {
+                .attr("id", "select-community")
+                .attr("alt", "Filtrado por comunidad autónoma")
+                .children(
+                    &mut page.render_community_options()[..]
+                )
+                .with_node!(select => {
+                    .event(clone!(page => move |_: events::Change| {
+                        let index: usize = select.value().parse().unwrap();
+                        *page.community_filter.lock_mut() = PropertyPage::get_community(index);
+                        page.update_province_options();
+                        page.filter();
+                     }))
+                })
+            }),
             html!("label", {
                 .visible(false)
                 .attr("for", "select-province")
@@ -200,13 +290,28 @@ impl PropertyPage {
             html!("select" => HtmlSelectElement, {
                 .attr("id", "select-province")
                 .attr("alt", "Filtrado por provincia")
-                .children(
-                    &mut page.render_province_options()[..]
+                .children_signal_vec(page.province_options.signal_vec_cloned()
+                    .map(clone!(page => move |province| {
+                        let name = PropertyPage::province_name(&province);
+                        if *page.province_filter.lock_ref() == province {
+                            html!("option", {
+                                .attr("selected", "selected")
+                                .attr("value", name)
+                                .text(name)
+                            })
+                        } else {
+                            html!("option", {
+                                .attr("value", name)
+                                .text(name)
+                            })
+                        }
+                    }))
                 )
                 .with_node!(select => {
                     .event(clone!(page => move |_: events::Change| {
-                        let index: usize = select.value().parse().unwrap();
-                        *page.province_filter.lock_mut() = PropertyPage::get_province(index);
+                        let province = *page.province_options.lock_ref().iter()
+                            .find(|p| PropertyPage::province_name(p) == select.value()).unwrap();
+                        *page.province_filter.lock_mut() = province;
                         page.update_city_options();
                         page.filter();
                      }))
@@ -263,16 +368,54 @@ impl PropertyPage {
                      }))
                 })
             }),
+            html!("label", {
+                .visible(true)
+                .attr("for", "checkbox-visitable")
+                .text("Solo visitables:")
+            }),
+            html!("input" => HtmlInputElement, {
+                .attr("id", "checkbox-visitable")
+                .attr("alt", "Solo visitables")
+                .attr("type", "checkbox")
+                .with_node!(_input => {
+                    .event(clone!(page => move |_: events::Change| {
+                        let value = *page.visitable_filter.lock_ref();
+                        *page.visitable_filter.lock_mut() = !value;
+
+                        page.filter();
+                     }))
+                })
+            }),
+            html!("label", {
+                .visible(true)
+                .attr("for", "checkbox-no-vivienda-habitual")
+                .text("No vivienda habitual:")
+            }),
+            html!("input" => HtmlInputElement, {
+                .attr("id", "checkbox-no-vivienda-habitual")
+                .attr("alt", "No es vivienda habitual")
+                .attr("type", "checkbox")
+                .with_node!(_input => {
+                    .event(clone!(page => move |_: events::Change| {
+                        let value = *page.not_primary_residence_filter.lock_ref();
+                        *page.not_primary_residence_filter.lock_mut() = !value;
+
+                        page.filter();
+                     }))
+                })
+            }),
             ])
         })
     }
 
+    // Provinces are grouped by their autonomous community.
     fn sort_by_province(a: &Arc<PropertyView>, b: &Arc<PropertyView>) -> Ordering {
-        a.property.province.cmp(&b.property.province)
+        (a.property.province.community(), a.property.province)
+            .cmp(&(b.property.province.community(), b.property.province))
     }
 
     fn sort_by_reverse_province(a: &Arc<PropertyView>, b: &Arc<PropertyView>) -> Ordering {
-        b.property.province.cmp(&a.property.province)
+        PropertyPage::sort_by_province(b, a)
     }
 
     fn sort_by_value(a: &Arc<PropertyView>, b: &Arc<PropertyView>) -> Ordering {
@@ -1,38 +1,51 @@
 use dominator::{clone, events, html, Dom, EventOptions};
 use futures_signals::signal::{Mutable, SignalExt};
 use rust_decimal::prelude::ToPrimitive;
-use shylock_data::{BidInfo, Property};
+use shylock_data::{
+    categories::PropertyCategory, Acquisition, Asset, Auction, BidInfo, CostAsset,
+    OpportunityConfig, Property, Seller,
+};
 use std::sync::Arc;
 
 use crate::{
     feather::render_svg_external_link_icon,
     global::{
-        AUCTIONS, CELL_CLASS, CELL_EXPANDED_CLASS, CELL_FLEX_CONTAINER_CLASS, CELL_FLEX_ITEM_CLASS,
-        DEFAULT_ICON_COLOR, DEFAULT_ICON_SIZE, ROW_CLASS,
+        ASSETS, AUCTIONS, CELL_CLASS, CELL_EXPANDED_CLASS, CELL_FLEX_CONTAINER_CLASS,
+        CELL_FLEX_ITEM_CLASS, DEFAULT_ICON_COLOR, DEFAULT_ICON_SIZE, ROW_CLASS,
     },
     util::{
-        format_valuation, is_targeted_asset, new_bidinfo, summarize, valid_catastro_reference,
-        DESCRIPTION_TEXT_LIMIT,
+        boe_auction_link, format_closes_in, format_encumbrances, format_local_datetime,
+        format_measure, format_opportunity, format_state_history, format_valuation,
+        is_targeted_asset, property_city, proposed_bid, render_attachments, render_cost_calculator,
+        summarize, DESCRIPTION_TEXT_LIMIT,
     },
 };
 
+// Garages and storage rooms auctioned along with an apartment are housing annexes.
+fn sold_with_dwelling(property: &Property) -> bool {
+    ASSETS.get().unwrap().iter().any(|asset| match asset {
+        Asset::Property(other) => {
+            other.auction_id == property.auction_id && other.category == PropertyCategory::Apartment
+        }
+        _ => false,
+    })
+}
+
 #[derive(Debug)]
 pub struct PropertyView {
     pub anchor_hovered: Mutable<bool>,
     pub show_expanded: Mutable<bool>,
     pub filtered_in: Mutable<bool>,
+    pub auction: &'static Auction,
     pub property: &'static Property,
     pub bidinfo: BidInfo,
+    pub acquisition: Mutable<Acquisition>,
 }
 
 impl PropertyView {
     pub fn new(property: &'static Property) -> Arc<Self> {
-        let auction_bidinfo = &AUCTIONS
-            .get()
-            .unwrap()
-            .get(&property.auction_id)
-            .unwrap()
-            .bidinfo;
+        let auction = AUCTIONS.get().unwrap().get(&property.auction_id).unwrap();
+        let auction_bidinfo = &auction.bidinfo;
 
         let bidinfo = if property.bidinfo.is_none() {
             auction_bidinfo
@@ -40,12 +53,25 @@ impl PropertyView {
             property.bidinfo.as_ref().unwrap()
         };
 
+        let bidinfo = bidinfo.merged_with(auction_bidinfo);
+
         Arc::new(Self {
             anchor_hovered: Mutable::new(false),
             show_expanded: Mutable::new(false),
             filtered_in: Mutable::new(true),
+            auction,
             property,
-            bidinfo: new_bidinfo(bidinfo, auction_bidinfo),
+            bidinfo,
+            acquisition: Mutable::new(Acquisition {
+                asset: CostAsset::Property(property.category),
+                bid: proposed_bid(&bidinfo),
+                bidinfo,
+                charges: property.charges,
+                kind: auction.kind.clone(),
+                province: property.province,
+                seller: Seller::Individual,
+                with_dwelling: sold_with_dwelling(property),
+            }),
         })
     }
 
@@ -57,7 +83,7 @@ impl PropertyView {
                 .text("Identificador subasta: ")
                 .child(html!("a",{
                     .attr("alt", "Enlace externo a subastas BOE")
-                    .attr("href", &format!("https://subastas.boe.es/detalleSubasta.php?idSub={}", &view.property.auction_id))
+                    .attr("href", &boe_auction_link(&view.property.auction_id, view.property.lot))
                     .attr("target", "_blank")
                     .attr("rel", "external nofollow")
                     .text(&view.property.auction_id)
@@ -73,17 +99,46 @@ impl PropertyView {
                     }))
                 }))
             }))
-            .child(if view.property.catastro_link.is_some() {
-                let catastro_link = view.property.catastro_link.clone().unwrap();
+            .child(if let Some(lot) = view.property.lot {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Lote: ")
+                    .text(&lot.to_string())
+                    .text(".")
+                })
+            } else {
+                Dom::empty()
+            })
+            .child(html!("span", {
+                .class(&*CELL_FLEX_ITEM_CLASS)
+                .text("Cierre: ")
+                .text(&format_local_datetime(&view.auction.end_date))
+                .text(" (")
+                .text(&format_closes_in(&view.auction.end_date))
+                .text(").")
+            }))
+            .child(if view.auction.state_history.is_empty() {
+                Dom::empty()
+            } else {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Historial: ")
+                    .text(&format_state_history(&view.auction.state_history))
+                    .text(".")
+                })
+            })
+            .child(render_attachments(&view.property.attachments))
+            .child(if let (Some(catastro_link), Some(catastro_reference)) =
+                (&view.property.catastro_link, &view.property.catastro_reference) {
                 html!("span", {
                     .class(&*CELL_FLEX_ITEM_CLASS)
                     .text("Referencia catastral: ")
                     .child(html!("a", {
                         .attr("alt", "Enlace externo al catastro")
-                        .attr("href", &catastro_link)
+                        .attr("href", catastro_link)
                         .attr("target", "_blank")
                         .attr("rel", "external nofollow")
-                        .text(&view.property.catastro_reference)
+                        .text(catastro_reference.as_str())
                         .child(render_svg_external_link_icon(DEFAULT_ICON_COLOR, DEFAULT_ICON_SIZE))
                         .event(clone!(view => move |_: events::PointerEnter| {
                             *view.anchor_hovered.lock_mut() = true;
@@ -95,11 +150,17 @@ impl PropertyView {
                             *view.anchor_hovered.lock_mut() = false;
                         }))
                     }))
-                }) } else if valid_catastro_reference(&view.property.catastro_reference) {
+                    .text(" (")
+                    .text(catastro_reference.kind().name())
+                    .text(").")
+                }) } else if let Some(catastro_reference) = &view.property.catastro_reference {
                     html!("span", {
                         .class(&*CELL_FLEX_ITEM_CLASS)
                         .text("Referencia catastral: ")
-                        .text(&view.property.catastro_reference)
+                        .text(catastro_reference.as_str())
+                        .text(" (")
+                        .text(catastro_reference.kind().name())
+                        .text(").")
                     })
                  }
                     else {
@@ -109,13 +170,83 @@ impl PropertyView {
             .child(html!("span", {
                 .class(&*CELL_FLEX_ITEM_CLASS)
                 .text("Ciudad: ")
-                .text(&view.property.city)
+                .text(property_city(view.property))
                 .text(".")
             }))
             .child(html!("span", {
                 .class(&*CELL_FLEX_ITEM_CLASS)
                 .text("Provincia: ")
                 .text(view.property.province.name())
+                .text(" (")
+                .text(view.property.province.community().name())
+                .text(").")
+            }))
+            .child(if let Some(area) = &view.property.area {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Superficie: ")
+                    .text(&format_measure(area))
+                    .text(" m².")
+                })
+            } else {
+                Dom::empty()
+            })
+            .child(if let Some(quota) = &view.property.quota {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Cuota: ")
+                    .text(&format_measure(quota))
+                    .text(" %.")
+                })
+            } else {
+                Dom::empty()
+            })
+            .child(if let Some(idufir) = &view.property.idufir {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("IDUFIR: ")
+                    .text(idufir.as_str())
+                    .text(".")
+                })
+            } else {
+                Dom::empty()
+            })
+            .child(if let Some(allotment) = &view.property.allotment {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Parcela: ")
+                    .text(allotment)
+                    .text(".")
+                })
+            } else {
+                Dom::empty()
+            })
+            .child(if let Some(place) = &view.property.place {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Paraje: ")
+                    .text(place)
+                    .text(".")
+                })
+            } else {
+                Dom::empty()
+            })
+            .child(html!("span", {
+                .class(&*CELL_FLEX_ITEM_CLASS)
+                .text("Visitable: ")
+                .text(view.property.visitable.name())
+                .text(".")
+            }))
+            .child(html!("span", {
+                .class(&*CELL_FLEX_ITEM_CLASS)
+                .text("Vivienda habitual: ")
+                .text(view.property.primary_residence.name())
+                .text(".")
+            }))
+            .child(html!("span", {
+                .class(&*CELL_FLEX_ITEM_CLASS)
+                .text("Situación posesoria: ")
+                .text(view.property.owner_status.name())
                 .text(".")
             }))
             .child(html!("span", {
@@ -127,6 +258,29 @@ impl PropertyView {
                     else {"."}
                 )
             }))
+            .child(if view.property.encumbrances.is_empty() {
+                Dom::empty()
+            } else {
+                html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Cargas: ")
+                    .text(&format_encumbrances(&view.property.encumbrances))
+                    .text(".")
+                })
+            })
+            .child({
+                let opportunity = view.bidinfo.opportunity(&OpportunityConfig::default());
+                if opportunity.is_opportunity {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Oportunidad: ")
+                        .text(&format_opportunity(&opportunity))
+                        .text(".")
+                    })
+                } else {
+                    Dom::empty()
+                }
+            })
             .child(html!("span", {
                 .class(&*CELL_FLEX_ITEM_CLASS)
                 .text("Valor subasta: ")
@@ -163,6 +317,7 @@ impl PropertyView {
                       }
                 )
             }))
+            .child(render_cost_calculator(view.acquisition.clone()))
         })
     }
 
@@ -180,7 +335,7 @@ impl PropertyView {
             }),
             html!("td", {
                 .class(&*CELL_CLASS)
-                .text(&self.property.city)
+                .text(property_city(self.property))
             }),
             html!("td", {
                 .class(&*CELL_CLASS)
@@ -1,18 +1,22 @@
-use dominator::Dom;
+use dominator::{clone, events, html, with_node, Dom};
+use futures_signals::signal::Mutable;
 use js_sys::Error;
 use num_format::{Buffer, Locale};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use shylock_data::BidInfo;
+use shylock_data::{
+    Acquisition, AcquisitionCost, Attachment, AuctionState, AuctionStateChange, BidInfo, DateTime,
+    Encumbrance, FixedOffset, Municipality, OpportunityScore, Property, RegistrationPeriod, Seller,
+};
 use std::cmp::min;
 use substring::Substring;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{window, Headers, RequestInit, Response};
+use web_sys::{window, Headers, HtmlInputElement, RequestInit, Response};
 
 use crate::feather::render_svg_crosshair_icon;
-use crate::global::DEFAULT_ICON_COLOR;
+use crate::global::{CELL_FLEX_ITEM_CLASS, COST_RATES, DEFAULT_ICON_COLOR};
 
 pub const DESCRIPTION_TEXT_LIMIT: usize = 150;
 
@@ -24,11 +28,7 @@ pub enum SortingOrder {
 }
 
 pub fn is_targeted_asset(bidinfo: &BidInfo) -> Vec<Dom> {
-    let target_value = &bidinfo.value.to_f64().unwrap_or(0.0) * 0.7;
-
-    if bidinfo.claim_quantity.to_f64().unwrap_or(0.0) > 1.0
-        && target_value > bidinfo.claim_quantity.to_f64().unwrap_or(0.0)
-    {
+    if bidinfo.is_opportunity() {
         vec![render_svg_crosshair_icon(DEFAULT_ICON_COLOR, "12")]
     } else {
         vec![]
@@ -58,6 +58,235 @@ pub fn summarize(text: &str) -> &str {
     text.substring(0, str_min)
 }
 
+fn local_offset(date: &DateTime<FixedOffset>) -> FixedOffset {
+    // getTimezoneOffset returns the minutes from local time to UTC at that date,
+    // it changes with daylight saving time.
+    let timestamp = JsValue::from_f64(date.timestamp_millis() as f64);
+    let offset_minutes = js_sys::Date::new(&timestamp).get_timezone_offset() as i32;
+
+    FixedOffset::west_opt(offset_minutes * 60).unwrap_or_else(|| FixedOffset::east_opt(0).unwrap())
+}
+
+pub fn format_local_datetime(date: &DateTime<FixedOffset>) -> String {
+    date.with_timezone(&local_offset(date))
+        .format("%d/%m/%Y %H:%M")
+        .to_string()
+}
+
+pub fn format_closes_in(date: &DateTime<FixedOffset>) -> String {
+    let now = (js_sys::Date::now() / 1000.0) as i64;
+    let remaining = date.timestamp() - now;
+
+    if remaining <= 0 {
+        "cerrada".to_string()
+    } else if remaining < 3600 {
+        format!("cierra en {} min", (remaining + 59) / 60)
+    } else {
+        format!("cierra en {} h", remaining / 3600)
+    }
+}
+
+pub fn format_state_history(history: &[AuctionStateChange]) -> String {
+    let timeline = history
+        .iter()
+        .map(|change| {
+            format!(
+                "{} {}",
+                change.state.name(),
+                format_local_datetime(&change.date)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join(" → ");
+
+    match history
+        .iter()
+        .filter(|change| change.state == AuctionState::Suspended)
+        .count()
+    {
+        0 => timeline,
+        1 => format!("{} (1 suspensión)", timeline),
+        suspensions => format!("{} ({} suspensiones)", timeline, suspensions),
+    }
+}
+
+pub fn format_registration_period(period: &RegistrationPeriod) -> String {
+    match period.to {
+        Some(to) => format!(
+            "entre {} y {}",
+            period.from.format("%m/%Y"),
+            to.format("%m/%Y")
+        ),
+        None => format!("desde {}", period.from.format("%m/%Y")),
+    }
+}
+
+pub fn format_opportunity(opportunity: &OpportunityScore) -> String {
+    format!(
+        "{} ({})",
+        format_measure(&opportunity.score),
+        opportunity.explanation()
+    )
+}
+
+pub fn format_encumbrances(encumbrances: &[Encumbrance]) -> String {
+    encumbrances
+        .iter()
+        .map(|encumbrance| {
+            let mut text = encumbrance.kind.name().to_owned();
+            if let Some(creditor) = &encumbrance.creditor {
+                text.push_str(" a favor de ");
+                text.push_str(creditor);
+            }
+            if let Some(amount) = &encumbrance.amount {
+                text.push_str(&format!(" por {} €", format_measure(amount)));
+            }
+            if let Some(date) = &encumbrance.date {
+                text.push_str(&format!(" ({})", date.format("%d/%m/%Y")));
+            }
+            text
+        })
+        .collect::<Vec<String>>()
+        .join("; ")
+}
+
+pub fn format_acquisition_cost(cost: &AcquisitionCost) -> String {
+    let items = cost
+        .items
+        .iter()
+        .map(|item| match item.rate {
+            Some(rate) => format!(
+                "{} ({} %): {} €",
+                item.concept.name(),
+                format_measure(&(rate * Decimal::ONE_HUNDRED)),
+                format_valuation(&item.amount)
+            ),
+            None => format!(
+                "{}: {} €",
+                item.concept.name(),
+                format_valuation(&item.amount)
+            ),
+        })
+        .collect::<Vec<String>>()
+        .join(" + ");
+
+    format!(
+        "{} = {} €. Depósito: {} €. Aprobación: {}",
+        items,
+        format_valuation(&cost.total),
+        format_valuation(&cost.deposit),
+        cost.approval.name()
+    )
+}
+
+pub fn proposed_bid(bidinfo: &BidInfo) -> Decimal {
+    if bidinfo.minimum_bid > Decimal::ZERO {
+        bidinfo.minimum_bid
+    } else {
+        bidinfo.value
+    }
+}
+
+pub fn render_attachments(attachments: &[Attachment]) -> Dom {
+    if attachments.is_empty() {
+        return Dom::empty();
+    }
+
+    html!("span", {
+        .class(&*CELL_FLEX_ITEM_CLASS)
+        .text("Documentos: ")
+        .children(attachments.iter().map(|attachment| {
+            let url = attachment.published_url();
+            html!("a", {
+                .attr("href", &url)
+                .attr("target", "_blank")
+                .attr("title", &attachment.title)
+                .event(|event: events::Click| event.stop_propagation())
+                .child(if attachment.is_image() {
+                    html!("img", {
+                        .attr("src", &url)
+                        .attr("alt", attachment.kind.name())
+                        .attr("loading", "lazy")
+                        .style("max-height", "4em")
+                        .style("margin-right", "0.25em")
+                    })
+                } else {
+                    html!("span", {
+                        .style("margin-right", "0.25em")
+                        .text(attachment.kind.name())
+                    })
+                })
+            })
+        }))
+    })
+}
+
+pub fn render_cost_calculator(acquisition: Mutable<Acquisition>) -> Dom {
+    html!("span", {
+        .class(&*CELL_FLEX_ITEM_CLASS)
+        .text("Coste de adquisición con puja de ")
+        .child(html!("input" => HtmlInputElement, {
+            .attr("type", "number")
+            .attr("min", "0")
+            .attr("alt", "Puja propuesta")
+            .attr("value", &acquisition.lock_ref().bid.to_string())
+            .event(|event: events::Click| event.stop_propagation())
+            .with_node!(input => {
+                .event(clone!(acquisition => move |_: events::Input| {
+                    if let Ok(bid) = input.value().parse::<Decimal>() {
+                        acquisition.lock_mut().bid = bid;
+                    }
+                }))
+            })
+        }))
+        .text(" € ")
+        .child(html!("label", {
+            .event(|event: events::Click| event.stop_propagation())
+            .child(html!("input" => HtmlInputElement, {
+                .attr("type", "checkbox")
+                .attr("alt", "Vendedor empresa")
+                .with_node!(input => {
+                    .event(clone!(acquisition => move |_: events::Change| {
+                        acquisition.lock_mut().seller = if input.checked() {
+                            Seller::Business
+                        } else {
+                            Seller::Individual
+                        };
+                    }))
+                })
+            }))
+            .text("vendedor empresa")
+        }))
+        .text(": ")
+        .text_signal(acquisition.signal_ref(|acquisition| {
+            format_acquisition_cost(&acquisition.cost(&COST_RATES))
+        }))
+        .text(".")
+    })
+}
+
+pub fn property_city(property: &Property) -> &str {
+    property
+        .municipality
+        .as_deref()
+        .and_then(Municipality::from_ine_code)
+        .map(|municipality| &municipality.name[..])
+        .unwrap_or(&property.city)
+}
+
+pub fn boe_auction_link(auction_id: &str, lot: Option<u32>) -> String {
+    match lot {
+        Some(lot) => format!(
+            "https://subastas.boe.es/detalleSubasta.php?idSub={}&ver=3&idLote={}",
+            auction_id, lot
+        ),
+        None => format!(
+            "https://subastas.boe.es/detalleSubasta.php?idSub={}",
+            auction_id
+        ),
+    }
+}
+
 pub fn format_valuation(valuation: &Decimal) -> String {
     let mut buf = Buffer::default();
 
@@ -70,42 +299,20 @@ pub fn format_valuation(valuation: &Decimal) -> String {
     )
 }
 
-pub fn new_bidinfo(bidinfo: &BidInfo, auction_bidinfo: &BidInfo) -> BidInfo {
-    BidInfo {
-        appraisal: if bidinfo.appraisal.to_f64().unwrap_or(0.0) > 1.0 {
-            bidinfo.appraisal
-        } else {
-            auction_bidinfo.appraisal
-        },
-        bid_step: if bidinfo.bid_step.to_f64().unwrap_or(0.0) > 1.0 {
-            bidinfo.bid_step
-        } else {
-            auction_bidinfo.bid_step
-        },
-        claim_quantity: if bidinfo.claim_quantity.to_f64().unwrap_or(0.0) > 1.0 {
-            bidinfo.claim_quantity
-        } else {
-            auction_bidinfo.claim_quantity
-        },
-        deposit: if bidinfo.deposit.to_f64().unwrap_or(0.0) > 1.0 {
-            bidinfo.deposit
-        } else {
-            auction_bidinfo.deposit
-        },
-        minimum_bid: if bidinfo.minimum_bid.to_f64().unwrap_or(0.0) > 1.0 {
-            bidinfo.minimum_bid
-        } else {
-            auction_bidinfo.minimum_bid
-        },
-        value: if bidinfo.value.to_f64().unwrap_or(0.0) > 1.0 {
-            bidinfo.value
-        } else {
-            auction_bidinfo.value
-        },
-    }
-}
+pub fn format_measure(measure: &Decimal) -> String {
+    let mut buf = Buffer::default();
+    let measure = measure.round_dp(2);
+
+    buf.write_formatted(&measure.trunc().to_u64().unwrap_or(0), &Locale::es);
 
-/// Check if string it's a valid catastro reference
-pub fn valid_catastro_reference(reference: &str) -> bool {
-    reference.len() == 20 && !reference.contains(' ')
+    let decimals = (measure.fract() * Decimal::ONE_HUNDRED)
+        .round()
+        .to_u32()
+        .unwrap_or(0);
+
+    if decimals > 0 {
+        format!("{},{:02}", buf.as_str(), decimals)
+    } else {
+        buf.as_str().to_owned()
+    }
 }
@@ -5,6 +5,7 @@ use dominator::{clone, events, html, with_node, Dom};
 use futures_signals::signal::{Mutable, SignalExt};
 use futures_signals::signal_vec::{MutableVec, SignalVecExt};
 use rust_decimal::prelude::ToPrimitive;
+use shylock_data::{LicensePlate, OpportunityConfig};
 use web_sys::{HtmlInputElement, HtmlSelectElement};
 
 use crate::feather::{
@@ -20,8 +21,6 @@ use crate::vehicle_view::VehicleView;
 const ALL_BRAND_STR: &str = "Todas las marcas";
 const ALL_MODEL_STR: &str = "Todos los modelos";
 
-const DEFAULT_OPPORTUNITY_VALUE: f64 = 0.7;
-
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum VehicleSorting {
     None,
@@ -35,8 +34,10 @@ pub struct VehiclePage {
     brand_options: MutableVec<&'static str>,
     brand_filter: Mutable<&'static str>,
     bid_step_filter: Mutable<bool>,
+    current_plate_filter: Mutable<bool>,
+    valid_vin_filter: Mutable<bool>,
     opportunity_filter: Mutable<bool>,
-    opportunity_filter_threshold: Mutable<f64>,
+    opportunity_config: Mutable<OpportunityConfig>,
     model_options: MutableVec<&'static str>,
     model_filter: Mutable<&'static str>,
     value_sorting: Mutable<SortingOrder>,
@@ -50,8 +51,10 @@ impl VehiclePage {
             brand_options: MutableVec::new(),
             brand_filter: Mutable::new(ALL_BRAND_STR),
             bid_step_filter: Mutable::new(false),
+            current_plate_filter: Mutable::new(false),
+            valid_vin_filter: Mutable::new(false),
             opportunity_filter: Mutable::new(true),
-            opportunity_filter_threshold: Mutable::new(DEFAULT_OPPORTUNITY_VALUE),
+            opportunity_config: Mutable::new(OpportunityConfig::default()),
             model_options: MutableVec::new(),
             model_filter: Mutable::new(ALL_MODEL_STR),
             value_sorting: Mutable::new(SortingOrder::None),
@@ -81,11 +84,10 @@ impl VehiclePage {
         if *self.opportunity_filter.lock_ref() {
             return true;
         }
-        let auction_limit = *self.opportunity_filter_threshold.lock_ref();
-        let target_value = vehicle_view.bidinfo.value.to_f64().unwrap_or(0.0) * auction_limit;
-
-        vehicle_view.bidinfo.claim_quantity.to_f64().unwrap_or(0.0) > 1.0
-            && target_value > vehicle_view.bidinfo.claim_quantity.to_f64().unwrap_or(0.0)
+        vehicle_view
+            .bidinfo
+            .opportunity(&self.opportunity_config.lock_ref())
+            .is_opportunity
     }
 
     fn filter_by_bid_step(&self, vehicle_view: &Arc<VehicleView>) -> bool {
@@ -96,13 +98,34 @@ impl VehiclePage {
         vehicle_view.bidinfo.bid_step.to_f64().unwrap_or(0.0) <= 0.0
     }
 
+    fn filter_by_current_plate(&self, vehicle_view: &Arc<VehicleView>) -> bool {
+        if !*self.current_plate_filter.lock_ref() {
+            return true;
+        }
+
+        matches!(
+            vehicle_view.vehicle.plate,
+            Some(LicensePlate::Current { .. })
+        )
+    }
+
+    fn filter_by_valid_vin(&self, vehicle_view: &Arc<VehicleView>) -> bool {
+        if !*self.valid_vin_filter.lock_ref() {
+            return true;
+        }
+
+        vehicle_view.vehicle.vin.is_some()
+    }
+
     fn filter(&self) {
         for vehicle_view in self.vehicle_list.lock_ref().iter() {
             vehicle_view.filtered_in.set_neq(
                 self.filter_by_brand(vehicle_view)
                     && self.filter_by_model(vehicle_view)
                     && self.filter_by_opportunity(vehicle_view)
-                    && self.filter_by_bid_step(vehicle_view),
+                    && self.filter_by_bid_step(vehicle_view)
+                    && self.filter_by_current_plate(vehicle_view)
+                    && self.filter_by_valid_vin(vehicle_view),
             );
         }
     }
@@ -273,6 +296,42 @@ impl VehiclePage {
                      }))
                 })
             }),
+            html!("label", {
+                .visible(true)
+                .attr("for", "checkbox-matricula-actual")
+                .text("Solo matrícula actual:")
+            }),
+            html!("input" => HtmlInputElement, {
+                .attr("id", "checkbox-matricula-actual")
+                .attr("alt", "Solo matrícula actual")
+                .attr("type", "checkbox")
+                .with_node!(_input => {
+                    .event(clone!(page => move |_: events::Change| {
+                        let value = *page.current_plate_filter.lock_ref();
+                        *page.current_plate_filter.lock_mut() = !value;
+
+                        page.filter();
+                     }))
+                })
+            }),
+            html!("label", {
+                .visible(true)
+                .attr("for", "checkbox-bastidor-valido")
+                .text("Solo bastidor válido:")
+            }),
+            html!("input" => HtmlInputElement, {
+                .attr("id", "checkbox-bastidor-valido")
+                .attr("alt", "Solo bastidor válido")
+                .attr("type", "checkbox")
+                .with_node!(_input => {
+                    .event(clone!(page => move |_: events::Change| {
+                        let value = *page.valid_vin_filter.lock_ref();
+                        *page.valid_vin_filter.lock_mut() = !value;
+
+                        page.filter();
+                     }))
+                })
+            }),
             ])
         })
     }
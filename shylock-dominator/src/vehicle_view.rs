@@ -1,7 +1,7 @@
 use dominator::{clone, events, html, Dom};
 use futures_signals::signal::{Mutable, SignalExt};
 use rust_decimal::prelude::ToPrimitive;
-use shylock_data::{BidInfo, Vehicle};
+use shylock_data::{Acquisition, Auction, BidInfo, CostAsset, OpportunityConfig, Seller, Vehicle};
 use std::sync::Arc;
 
 use crate::{
@@ -10,7 +10,12 @@ use crate::{
         AUCTIONS, CELL_CLASS, CELL_EXPANDED_CLASS, CELL_FLEX_CONTAINER_CLASS, CELL_FLEX_ITEM_CLASS,
         DEFAULT_ICON_COLOR, DEFAULT_ICON_SIZE, ROW_CLASS,
     },
-    util::{format_valuation, is_targeted_asset, new_bidinfo, summarize, DESCRIPTION_TEXT_LIMIT},
+    util::{
+        boe_auction_link, format_closes_in, format_encumbrances, format_local_datetime,
+        format_opportunity, format_registration_period, format_state_history, format_valuation,
+        is_targeted_asset, proposed_bid, render_attachments, render_cost_calculator, summarize,
+        DESCRIPTION_TEXT_LIMIT,
+    },
 };
 
 #[derive(Debug)]
@@ -18,18 +23,16 @@ pub struct VehicleView {
     pub anchor_hovered: Mutable<bool>,
     pub show_expanded: Mutable<bool>,
     pub filtered_in: Mutable<bool>,
+    pub auction: &'static Auction,
     pub vehicle: &'static Vehicle,
     pub bidinfo: BidInfo,
+    pub acquisition: Mutable<Acquisition>,
 }
 
 impl VehicleView {
     pub fn new(vehicle: &'static Vehicle) -> Arc<Self> {
-        let auction_bidinfo = &AUCTIONS
-            .get()
-            .unwrap()
-            .get(&vehicle.auction_id)
-            .unwrap()
-            .bidinfo;
+        let auction = AUCTIONS.get().unwrap().get(&vehicle.auction_id).unwrap();
+        let auction_bidinfo = &auction.bidinfo;
 
         let bidinfo = if vehicle.bidinfo.is_none() {
             auction_bidinfo
@@ -37,12 +40,25 @@ impl VehicleView {
             vehicle.bidinfo.as_ref().unwrap()
         };
 
+        let bidinfo = bidinfo.merged_with(auction_bidinfo);
+
         Arc::new(Self {
             anchor_hovered: Mutable::new(false),
             show_expanded: Mutable::new(false),
             filtered_in: Mutable::new(true),
+            auction,
             vehicle,
-            bidinfo: new_bidinfo(bidinfo, auction_bidinfo),
+            bidinfo,
+            acquisition: Mutable::new(Acquisition {
+                asset: CostAsset::Vehicle(vehicle.category),
+                bid: proposed_bid(&bidinfo),
+                bidinfo,
+                charges: vehicle.charges,
+                kind: auction.kind.clone(),
+                province: auction.management.province(),
+                seller: Seller::Individual,
+                with_dwelling: false,
+            }),
         })
     }
 
@@ -57,7 +73,7 @@ impl VehicleView {
                     .text("Identificador subasta: ")
                     .child(html!("a",{
                         .attr("alt", "Enlace externo a subastas BOE")
-                        .attr("href", &format!("https://subastas.boe.es/detalleSubasta.php?idSub={}", &view.vehicle.auction_id))
+                        .attr("href", &boe_auction_link(&view.vehicle.auction_id, view.vehicle.lot))
                         .attr("target", "_blank")
                         .attr("rel", "external nofollow")
                         .text(&view.vehicle.auction_id)
@@ -73,6 +89,35 @@ impl VehicleView {
                         }))
                     }))
                 }))
+                .child(if let Some(lot) = view.vehicle.lot {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Lote: ")
+                        .text(&lot.to_string())
+                        .text(".")
+                    })
+                } else {
+                    Dom::empty()
+                })
+                .child(html!("span", {
+                    .class(&*CELL_FLEX_ITEM_CLASS)
+                    .text("Cierre: ")
+                    .text(&format_local_datetime(&view.auction.end_date))
+                    .text(" (")
+                    .text(&format_closes_in(&view.auction.end_date))
+                    .text(").")
+                }))
+                .child(if view.auction.state_history.is_empty() {
+                    Dom::empty()
+                } else {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Historial: ")
+                        .text(&format_state_history(&view.auction.state_history))
+                        .text(".")
+                    })
+                })
+                .child(render_attachments(&view.vehicle.attachments))
                 .child(html!("span", {
                     .class(&*CELL_FLEX_ITEM_CLASS)
                     .text("Marca y modelo: ")
@@ -87,6 +132,38 @@ impl VehicleView {
                     .text(view.vehicle.category.name())
                     .text(".")
                 }))
+                .child(if let Some(vin) = &view.vehicle.vin {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Bastidor: ")
+                        .text(vin.as_str())
+                        .text(&vin.manufacturer().map(|manufacturer| format!(" ({})", manufacturer)).unwrap_or_default())
+                        .text(".")
+                    })
+                } else {
+                    Dom::empty()
+                })
+                .child(if let Some(plate) = &view.vehicle.plate {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Matrícula: ")
+                        .text(&plate.to_string())
+                        .text(&plate.province().map(|province| format!(" ({})", province.name())).unwrap_or_default())
+                        .text(".")
+                    })
+                } else {
+                    Dom::empty()
+                })
+                .child(if let Some(period) = &view.vehicle.registration_period {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Matriculación aproximada: ")
+                        .text(&format_registration_period(period))
+                        .text(".")
+                    })
+                } else {
+                    Dom::empty()
+                })
                 .child(html!("span", {
                     .class(&*CELL_FLEX_ITEM_CLASS)
                     .text("Descripción: ")
@@ -96,6 +173,29 @@ impl VehicleView {
                         else {"."}
                     )
                 }))
+                .child(if view.vehicle.encumbrances.is_empty() {
+                    Dom::empty()
+                } else {
+                    html!("span", {
+                        .class(&*CELL_FLEX_ITEM_CLASS)
+                        .text("Cargas: ")
+                        .text(&format_encumbrances(&view.vehicle.encumbrances))
+                        .text(".")
+                    })
+                })
+                .child({
+                    let opportunity = bidinfo.opportunity(&OpportunityConfig::default());
+                    if opportunity.is_opportunity {
+                        html!("span", {
+                            .class(&*CELL_FLEX_ITEM_CLASS)
+                            .text("Oportunidad: ")
+                            .text(&format_opportunity(&opportunity))
+                            .text(".")
+                        })
+                    } else {
+                        Dom::empty()
+                    }
+                })
                 .child(html!("span", {
                     .class(&*CELL_FLEX_ITEM_CLASS)
                     .text("Valor subasta: ")
@@ -132,7 +232,7 @@ impl VehicleView {
                         }
                   )
             }))
-
+                .child(render_cost_calculator(view.acquisition.clone()))
             }))
         })
     }
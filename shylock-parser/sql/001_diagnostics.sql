@@ -0,0 +1,14 @@
+
+CREATE TABLE IF NOT EXISTS diagnostics (
+    auction_id TEXT NOT NULL,
+
+    concept BoeConcept,
+
+    value TEXT NULL,
+
+    kind DataErrorKind,
+
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_diagnostics_on_auction_id ON diagnostics(auction_id);
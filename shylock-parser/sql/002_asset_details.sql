@@ -0,0 +1,16 @@
+
+ALTER TABLE auctions ADD COLUMN account_record TEXT NULL;
+
+ALTER TABLE properties ADD COLUMN allotment TEXT NULL;
+
+ALTER TABLE properties ADD COLUMN area TEXT NULL;
+
+ALTER TABLE properties ADD COLUMN idufir TEXT NULL;
+
+ALTER TABLE properties ADD COLUMN place TEXT NULL;
+
+ALTER TABLE properties ADD COLUMN quota TEXT NULL;
+
+ALTER TABLE others ADD COLUMN acquisition_date DATETIME NULL;
+
+ALTER TABLE others ADD COLUMN quota TEXT NULL;
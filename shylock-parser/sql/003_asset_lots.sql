@@ -0,0 +1,38 @@
+
+ALTER TABLE properties ADD COLUMN lot INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE vehicles ADD COLUMN lot INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE others ADD COLUMN lot INTEGER NOT NULL DEFAULT 0;
+
+-- Assets of auctions with lots were inserted following lot order, lots are
+-- numbered across the three tables so mixed auctions don't repeat them.
+-- The order between asset kinds was not recorded: properties go first, then
+-- vehicles and others, updates of the auction replace any misplaced lot.
+UPDATE properties SET lot = (
+    SELECT COUNT(*) FROM properties p
+    WHERE p.auction_id = properties.auction_id AND p.rowid <= properties.rowid)
+WHERE auction_id IN (SELECT id FROM auctions WHERE lot_kind != 'NotApplicable');
+
+UPDATE vehicles SET lot = (
+    SELECT COUNT(*) FROM properties p
+    WHERE p.auction_id = vehicles.auction_id) + (
+    SELECT COUNT(*) FROM vehicles v
+    WHERE v.auction_id = vehicles.auction_id AND v.rowid <= vehicles.rowid)
+WHERE auction_id IN (SELECT id FROM auctions WHERE lot_kind != 'NotApplicable');
+
+UPDATE others SET lot = (
+    SELECT COUNT(*) FROM properties p
+    WHERE p.auction_id = others.auction_id) + (
+    SELECT COUNT(*) FROM vehicles v
+    WHERE v.auction_id = others.auction_id) + (
+    SELECT COUNT(*) FROM others o
+    WHERE o.auction_id = others.auction_id AND o.rowid <= others.rowid)
+WHERE auction_id IN (SELECT id FROM auctions WHERE lot_kind != 'NotApplicable');
+
+-- Lot 0 is used for assets of auctions without lots.
+CREATE UNIQUE INDEX idx_properties_on_auction_id_lot ON properties(auction_id, lot);
+
+CREATE UNIQUE INDEX idx_vehicles_on_auction_id_lot ON vehicles(auction_id, lot);
+
+CREATE UNIQUE INDEX idx_others_on_auction_id_lot ON others(auction_id, lot);
@@ -0,0 +1,30 @@
+
+UPDATE properties SET visitable = CASE
+    WHEN UPPER(TRIM(visitable)) IN ('SI', 'SÍ', 'Sí') THEN 'Yes'
+    WHEN UPPER(TRIM(visitable)) = 'NO' THEN 'No'
+    ELSE 'Unknown' END;
+
+UPDATE properties SET primary_residence = CASE
+    WHEN UPPER(TRIM(primary_residence)) IN ('SI', 'SÍ', 'Sí') THEN 'Yes'
+    WHEN UPPER(TRIM(primary_residence)) = 'NO' THEN 'No'
+    ELSE 'Unknown' END;
+
+UPDATE properties SET owner_status = CASE
+    WHEN owner_status LIKE '%DESOCUPAD%'
+        OR owner_status LIKE '%SIN OCUPANTE%'
+        OR owner_status LIKE '%LIBRE%'
+        OR owner_status LIKE '%VAC_O%' THEN 'Vacant'
+    WHEN owner_status LIKE '%OCUPAD%'
+        OR owner_status LIKE '%OCUPANTE%'
+        OR owner_status LIKE '%ARRENDAD%' THEN 'Occupied'
+    ELSE 'Unknown' END;
+
+UPDATE vehicles SET visitable = CASE
+    WHEN UPPER(TRIM(visitable)) IN ('SI', 'SÍ', 'Sí') THEN 'Yes'
+    WHEN UPPER(TRIM(visitable)) = 'NO' THEN 'No'
+    ELSE 'Unknown' END;
+
+UPDATE others SET visitable = CASE
+    WHEN UPPER(TRIM(visitable)) IN ('SI', 'SÍ', 'Sí') THEN 'Yes'
+    WHEN UPPER(TRIM(visitable)) = 'NO' THEN 'No'
+    ELSE 'Unknown' END;
@@ -0,0 +1,13 @@
+-- Dates were stored without time, they become Madrid midnight. Summer time
+-- starts after the last sunday of March and ends after the last sunday of October.
+UPDATE auctions SET start_date = start_date || 'T00:00:00' || CASE
+    WHEN start_date > date(substr(start_date, 1, 4) || '-03-25', 'weekday 0')
+        AND start_date <= date(substr(start_date, 1, 4) || '-10-25', 'weekday 0')
+    THEN '+02:00' ELSE '+01:00' END
+WHERE length(start_date) = 10;
+
+UPDATE auctions SET end_date = end_date || 'T00:00:00' || CASE
+    WHEN end_date > date(substr(end_date, 1, 4) || '-03-25', 'weekday 0')
+        AND end_date <= date(substr(end_date, 1, 4) || '-10-25', 'weekday 0')
+    THEN '+02:00' ELSE '+01:00' END
+WHERE length(end_date) = 10;
@@ -0,0 +1,8 @@
+
+ALTER TABLE vehicles ADD COLUMN plate TEXT NULL;
+
+ALTER TABLE vehicles ADD COLUMN registration_from DATE NULL;
+
+ALTER TABLE vehicles ADD COLUMN registration_to DATE NULL;
+
+ALTER TABLE vehicles ADD COLUMN vin TEXT NULL;
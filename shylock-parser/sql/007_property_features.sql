@@ -0,0 +1,35 @@
+CREATE TABLE IF NOT EXISTS property_features (
+    -- foreign key to auctions table
+    auction_id TEXT NOT NULL,
+
+    lot INTEGER NOT NULL DEFAULT 0,
+
+    -- JSON list of annexes with their confidence
+    annexes TEXT NULL,
+
+    bathrooms INTEGER NULL,
+
+    bathrooms_confidence Confidence NULL,
+
+    built_area TEXT NULL,
+
+    built_area_confidence Confidence NULL,
+
+    finca_number TEXT NULL,
+
+    finca_number_confidence Confidence NULL,
+
+    floor TEXT NULL,
+
+    floor_confidence Confidence NULL,
+
+    rooms INTEGER NULL,
+
+    rooms_confidence Confidence NULL,
+
+    usable_area TEXT NULL,
+
+    usable_area_confidence Confidence NULL
+);
+
+CREATE UNIQUE INDEX idx_property_features_on_auction_id_lot ON property_features(auction_id, lot);
@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS encumbrances (
+    -- foreign key to auctions table
+    auction_id TEXT NOT NULL,
+
+    lot INTEGER NOT NULL DEFAULT 0,
+
+    kind EncumbranceKind NOT NULL,
+
+    creditor TEXT NULL,
+
+    amount TEXT NULL,
+
+    date DATE NULL
+);
+
+CREATE INDEX idx_encumbrances_on_auction_id_lot ON encumbrances(auction_id, lot);
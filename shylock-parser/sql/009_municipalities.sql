@@ -0,0 +1 @@
+ALTER TABLE properties ADD COLUMN municipality TEXT NULL;
@@ -0,0 +1,6 @@
+
+CREATE TABLE IF NOT EXISTS scrape_state (
+    name TEXT NOT NULL PRIMARY KEY,
+
+    last_success TEXT NOT NULL
+);
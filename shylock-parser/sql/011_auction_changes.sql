@@ -0,0 +1,16 @@
+
+CREATE TABLE IF NOT EXISTS auction_changes (
+    auction_id TEXT NOT NULL,
+
+    lot INTEGER NULL,
+
+    field TEXT NOT NULL,
+
+    old_value TEXT NULL,
+
+    new_value TEXT NULL,
+
+    changed_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_auction_changes_on_auction_id ON auction_changes(auction_id);
@@ -0,0 +1,16 @@
+
+CREATE TABLE IF NOT EXISTS auction_state_history (
+    auction_id TEXT NOT NULL,
+
+    previous_state AuctionState NULL,
+
+    state AuctionState NOT NULL,
+
+    changed_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_auction_state_history_on_auction_id ON auction_state_history(auction_id);
+
+-- Transition dates of already stored auctions are unknown, start from now.
+INSERT INTO auction_state_history(auction_id, previous_state, state, changed_at)
+SELECT id, NULL, auction_state, strftime('%Y-%m-%dT%H:%M:%S+00:00', 'now') FROM auctions;
@@ -0,0 +1,17 @@
+
+CREATE TABLE IF NOT EXISTS auction_results (
+    -- foreign key to auctions table
+    auction_id TEXT NOT NULL,
+
+    lot INTEGER NOT NULL DEFAULT 0,
+
+    outcome OutcomeKind,
+
+    highest_bid TEXT NULL,
+
+    concluded_at TEXT NOT NULL,
+
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (auction_id, lot)
+);
@@ -0,0 +1,28 @@
+CREATE TABLE IF NOT EXISTS attachments (
+    -- foreign key to auctions table
+    auction_id TEXT NOT NULL,
+
+    lot INTEGER NOT NULL DEFAULT 0,
+
+    kind AttachmentKind NOT NULL,
+
+    title TEXT NOT NULL,
+
+    source_url TEXT NOT NULL,
+
+    -- SHA-256 of the content, key in the attachments store
+    hash TEXT NOT NULL,
+
+    content_type TEXT NOT NULL,
+
+    size INTEGER NOT NULL,
+
+    -- text extracted from pdf documents to be searched
+    content_text TEXT NULL,
+
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+
+    PRIMARY KEY (auction_id, lot, source_url)
+);
+
+CREATE INDEX idx_attachments_on_hash ON attachments(hash);
@@ -0,0 +1,55 @@
+CREATE TABLE IF NOT EXISTS scrape_runs (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+
+    -- scrape mode, e.g. init or incremental
+    name TEXT NOT NULL,
+
+    -- search form posted to get the result pages
+    query TEXT NOT NULL,
+
+    status ScrapeStatus NOT NULL,
+
+    started_at TEXT NOT NULL,
+
+    finished_at TEXT NULL
+);
+
+CREATE TABLE IF NOT EXISTS scrape_pages (
+    -- foreign key to scrape_runs table
+    run_id INTEGER NOT NULL,
+
+    page INTEGER NOT NULL,
+
+    url TEXT NOT NULL,
+
+    status ScrapeStatus NOT NULL,
+
+    error TEXT NULL,
+
+    attempts INTEGER NOT NULL DEFAULT 0,
+
+    updated_at TEXT NOT NULL,
+
+    PRIMARY KEY (run_id, page)
+);
+
+CREATE TABLE IF NOT EXISTS scrape_links (
+    -- foreign key to scrape_runs table
+    run_id INTEGER NOT NULL,
+
+    page INTEGER NOT NULL,
+
+    auction_id TEXT NOT NULL,
+
+    link TEXT NOT NULL,
+
+    status ScrapeStatus NOT NULL,
+
+    error TEXT NULL,
+
+    attempts INTEGER NOT NULL DEFAULT 0,
+
+    updated_at TEXT NOT NULL,
+
+    PRIMARY KEY (run_id, auction_id)
+);
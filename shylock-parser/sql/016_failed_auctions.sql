@@ -0,0 +1,27 @@
+CREATE TABLE IF NOT EXISTS failed_auctions (
+    auction_id TEXT NOT NULL PRIMARY KEY,
+
+    link TEXT NOT NULL,
+
+    auction_state AuctionState NOT NULL,
+
+    error TEXT NOT NULL,
+
+    attempts INTEGER NOT NULL DEFAULT 1,
+
+    first_failed_at TEXT NOT NULL,
+
+    last_attempt_at TEXT NOT NULL
+);
+
+-- Raw pages fetched in the last attempt.
+CREATE TABLE IF NOT EXISTS failed_auction_pages (
+    -- foreign key to failed_auctions table
+    auction_id TEXT NOT NULL,
+
+    url TEXT NOT NULL,
+
+    content TEXT NOT NULL,
+
+    PRIMARY KEY (auction_id, url)
+);
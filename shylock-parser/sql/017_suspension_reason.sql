@@ -0,0 +1 @@
+ALTER TABLE auctions ADD COLUMN suspension_reason TEXT NULL;
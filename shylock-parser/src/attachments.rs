@@ -0,0 +1,227 @@
+use sha2::{Digest, Sha256};
+use shylock_data::Attachment;
+use std::path::{Path, PathBuf};
+
+/// Default maximum size of a downloaded attachment, 20 MiB.
+pub const DEFAULT_MAX_ATTACHMENT_SIZE: usize = 20 * 1024 * 1024;
+
+/// Media type of html pages.
+pub const HTML_CONTENT_TYPE: &str = "text/html";
+/// Media type of pdf documents.
+pub const PDF_CONTENT_TYPE: &str = "application/pdf";
+
+/// On-disk attachments store addressed by the hash of their content.
+#[derive(Debug, Clone)]
+pub struct AttachmentStore {
+    dir: PathBuf,
+    max_size: usize,
+}
+
+impl AttachmentStore {
+    /// Create a store saving attachments under `dir` up to `max_size` bytes.
+    pub fn new<P: AsRef<Path>>(dir: P, max_size: usize) -> Self {
+        AttachmentStore {
+            dir: dir.as_ref().to_path_buf(),
+            max_size,
+        }
+    }
+
+    /// Returns the maximum size of an attachment in bytes.
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// Returns the file where the content with `hash` is stored.
+    pub fn path(&self, hash: &str) -> PathBuf {
+        self.dir.join(&hash[..2]).join(hash)
+    }
+
+    /// Store `content` returning its hash, contents already stored are not written again.
+    pub fn store(&self, content: &[u8]) -> Result<String, Box<dyn std::error::Error>> {
+        if content.len() > self.max_size {
+            return Err(format!(
+                "attachment of {} bytes is larger than {} bytes",
+                content.len(),
+                self.max_size
+            )
+            .into());
+        }
+
+        let hash = content_hash(content);
+        let path = self.path(&hash);
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, content)?;
+        }
+
+        Ok(hash)
+    }
+
+    /// Returns the stored content with `hash`.
+    pub fn read(&self, hash: &str) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        Ok(std::fs::read(self.path(hash))?)
+    }
+
+    /// Copy the stored `attachment` under `dir` at its `stored_path`, to be served
+    /// along the frontend, copies already published are not written again.
+    pub fn publish<P: AsRef<Path>>(
+        &self,
+        attachment: &Attachment,
+        dir: P,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let target = dir.as_ref().join(attachment.stored_path());
+        if !target.exists() {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(self.path(&attachment.hash), target)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// SHA-256 hex digest of `content`.
+pub fn content_hash(content: &[u8]) -> String {
+    Sha256::digest(content)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Media type of `content` guessed from its first bytes.
+pub fn content_type(content: &[u8]) -> &'static str {
+    if content.starts_with(b"%PDF") {
+        PDF_CONTENT_TYPE
+    } else if content.starts_with(&[0xff, 0xd8, 0xff]) {
+        "image/jpeg"
+    } else if content.starts_with(b"\x89PNG") {
+        "image/png"
+    } else if content.starts_with(b"GIF8") {
+        "image/gif"
+    } else if content.len() > 12 && content.starts_with(b"RIFF") && &content[8..12] == b"WEBP" {
+        "image/webp"
+    } else if String::from_utf8_lossy(&content[..content.len().min(512)])
+        .to_lowercase()
+        .contains("<html")
+    {
+        HTML_CONTENT_TYPE
+    } else {
+        "application/octet-stream"
+    }
+}
+
+/// Text of a pdf `content` to be searched, `None` if it has no text, is not a pdf
+/// or the pdf could not be read.
+pub fn extract_pdf_text(content: &[u8]) -> Option<String> {
+    if content_type(content) != PDF_CONTENT_TYPE {
+        return None;
+    }
+
+    // pdf_extract panics on some malformed documents.
+    match std::panic::catch_unwind(|| pdf_extract::extract_text_from_mem(content)) {
+        Ok(Ok(text)) => {
+            let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+            if text.is_empty() {
+                None
+            } else {
+                Some(text)
+            }
+        }
+        Ok(Err(error)) => {
+            log::warn!("Unable to extract pdf text: {}", error);
+            None
+        }
+        Err(_) => {
+            log::warn!("Unable to extract pdf text: pdf reader panicked");
+            None
+        }
+    }
+}
+
+/// Like `extract_pdf_text` but run in a blocking thread, not to stall the async runtime.
+pub async fn extract_pdf_text_blocking(content: Vec<u8>) -> Option<String> {
+    tokio::task::spawn_blocking(move || extract_pdf_text(&content))
+        .await
+        .unwrap_or_else(|error| {
+            log::warn!("Unable to extract pdf text: {}", error);
+            None
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{TempPath, FIXTURES_DIR};
+    use shylock_data::AttachmentKind;
+
+    const EDICT_FIXTURE: &str = "subastas.boe.es_._documentos_SUB-NE-2020-465937_edicto.pdf.html";
+
+    #[test]
+    fn attachment_store_test() {
+        let dir = TempPath::new("attachments");
+        let store = AttachmentStore::new(&dir, 16);
+
+        let hash = store.store(b"%PDF-1.4 edicto").unwrap();
+        assert_eq!(
+            "ef20817abf0411c00aab054912ba0df82682cd2646be9a3c52ba422e66c4425a",
+            hash
+        );
+        assert_eq!(hash, store.store(b"%PDF-1.4 edicto").unwrap());
+        assert_eq!(b"%PDF-1.4 edicto".to_vec(), store.read(&hash).unwrap());
+        assert!(store.store(b"%PDF-1.4 edicto de subasta").is_err());
+
+        let attachment = Attachment {
+            kind: AttachmentKind::Edict,
+            title: "Edicto".to_owned(),
+            source_url: "https://subastas.boe.es/doc/1.pdf".to_owned(),
+            hash: hash.clone(),
+            content_type: PDF_CONTENT_TYPE.to_owned(),
+            size: 15,
+        };
+        let published = dir.join("published");
+        store.publish(&attachment, &published).unwrap();
+        assert_eq!(
+            b"%PDF-1.4 edicto".to_vec(),
+            std::fs::read(published.join(attachment.stored_path())).unwrap()
+        );
+        assert!(store
+            .publish(
+                &Attachment {
+                    hash: "00".repeat(32),
+                    ..attachment
+                },
+                &published
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn extract_pdf_text_test() {
+        let content = std::fs::read(Path::new(FIXTURES_DIR).join(EDICT_FIXTURE)).unwrap();
+        assert_eq!(PDF_CONTENT_TYPE, content_type(&content));
+        assert!(extract_pdf_text(&content)
+            .unwrap()
+            .contains("FINCA NUM. 17228"));
+
+        assert_eq!("image/png", content_type(b"\x89PNG\r\n\x1a\n"));
+        assert_eq!(HTML_CONTENT_TYPE, content_type(b"<!DOCTYPE html><html>"));
+        assert_eq!(None, extract_pdf_text(b"<html></html>"));
+    }
+
+    #[tokio::test]
+    async fn extract_pdf_text_blocking_test() {
+        let content = std::fs::read(Path::new(FIXTURES_DIR).join(EDICT_FIXTURE)).unwrap();
+        assert!(extract_pdf_text_blocking(content)
+            .await
+            .unwrap()
+            .contains("FINCA NUM. 17228"));
+
+        assert_eq!(
+            None,
+            extract_pdf_text_blocking(b"%PDF-1.4\n%%EOF garbage".to_vec()).await
+        );
+    }
+}
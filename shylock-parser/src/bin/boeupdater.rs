@@ -1,64 +1,116 @@
 use std::collections::BTreeMap;
 
+use chrono::Utc;
 use clap::{arg, Command};
 use env_logger::Env;
 use futures::{stream, StreamExt};
-use shylock_data::types::{Asset, Auction};
+use shylock_data::{
+    attachments::PUBLISHED_ATTACHMENTS_DIR,
+    price_statistics,
+    provinces::Province,
+    types::{Asset, Auction},
+    Attachment, DateTime, Decimal, ExportCounts, ExportHeader, FixedOffset, NaiveDate,
+};
 use shylock_parser::{
+    attachments::{AttachmentStore, DEFAULT_MAX_ATTACHMENT_SIZE},
     db::{DbClient, DEFAULT_DB_PATH},
     geosolver::GeoSolver,
-    http::{UrlFetcher, MAIN_ALL_AUCTIONS_BOE_PARAMS, MAIN_ALL_AUCTIONS_BOE_POST_URL},
+    http::{
+        fixture_file_name, BoeSearchQuery, CacheMode, HttpClient, ResponseCache, UrlFetcher,
+        MAIN_ALL_AUCTIONS_BOE_POST_URL,
+    },
     image::create_svg_histogram,
-    scraper::{auction_state_page_scraper, page_scraper, DEFAULT_COUNTRY},
-    util::{dump_to_cbor_compressed_file, valid_catastro_reference},
+    scraper::{
+        auction_attachments_scraper, auction_changes_scraper, auction_outcome_scraper,
+        auction_state_page_scraper, failed_auction_scraper, incremental_search_query,
+        scrape_run_scraper, DEFAULT_COUNTRY, INCREMENTAL_SCRAPE, INIT_SCRAPE,
+    },
+    util::{dump_to_cbor_compressed_file, parse_duration},
     AuctionState,
 };
+use std::time::Duration;
 
 const DEFAULT_CONCURRENCY: usize = 6;
+const DEFAULT_CACHE_DIR: &str = "./cache";
+const DEFAULT_ATTACHMENTS_DIR: &str = "./attachments";
+const DEFAULT_OVERLAP: &str = "1d";
+const DEFAULT_TRANSITIONS_SINCE: &str = "7d";
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_RETRY_BACKOFF: &str = "1h";
+
+async fn init_scrape(
+    http_client: &UrlFetcher,
+    db_client: &DbClient,
+    name: &str,
+    query: &BoeSearchQuery,
+    resume: bool,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let run_id = match db_client.get_last_unfinished_scrape_run(name).await? {
+        Some(run) if resume => {
+            log::info!(
+                "Resuming {} scrape run {} started at {}",
+                name,
+                run.id,
+                run.started_at
+            );
+            run.id
+        }
+        _ => {
+            if resume {
+                log::info!(
+                    "No unfinished {} scrape run to resume, starting a new one.",
+                    name
+                );
+            }
+            db_client.create_scrape_run(name, &query.to_form()).await?
+        }
+    };
 
-async fn init_scrape(db_client: &DbClient) -> Result<(), Box<dyn std::error::Error>> {
-    let http_client = &UrlFetcher::new();
     log::info!("Visiting: {}", *MAIN_ALL_AUCTIONS_BOE_POST_URL);
-    let main_page = http_client
-        .post_url(
-            &MAIN_ALL_AUCTIONS_BOE_POST_URL,
-            MAIN_ALL_AUCTIONS_BOE_PARAMS,
-        )
-        .await?;
-    log::info!("Content: {}", main_page);
-    let mut pages_url = shylock_parser::parser::parse_extra_pages(&main_page);
-
-    pages_url.insert(0, MAIN_ALL_AUCTIONS_BOE_POST_URL.to_string());
-    log::info!("Total BOE pages to process: {}", pages_url.len());
+    scrape_run_scraper(http_client, db_client, run_id, DEFAULT_CONCURRENCY).await
+}
 
-    let stream = stream::iter(pages_url.iter().enumerate());
+async fn incremental_scrape(
+    http_client: &UrlFetcher,
+    db_client: &DbClient,
+    overlap: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let started_at: DateTime<FixedOffset> = Utc::now().into();
+    let last_scrape = db_client.get_last_scrape(INCREMENTAL_SCRAPE).await?;
+    match last_scrape {
+        Some(last_scrape) => log::info!("Scraping auctions since last scrape: {}", last_scrape),
+        None => log::info!("No previous scrape, scraping all auctions."),
+    }
 
-    stream
-        .for_each_concurrent(DEFAULT_CONCURRENCY, |page| async move {
-            if let Ok((ok, err, already_proccessed)) =
-                page_scraper(http_client, db_client, page.1).await
-            {
-                log::info!(
-                    "Page {} ended succesfully ok {}/err {}/total {}",
-                    page.0,
-                    ok,
-                    err,
-                    already_proccessed
-                );
-            }
-        })
-        .await;
+    changes_scrape(http_client, db_client).await?;
+
+    let query = incremental_search_query(last_scrape, overlap);
+    let failed_pages =
+        init_scrape(http_client, db_client, INCREMENTAL_SCRAPE, &query, false).await?;
+
+    if failed_pages == 0 {
+        db_client
+            .set_last_scrape(INCREMENTAL_SCRAPE, &started_at)
+            .await?;
+    } else {
+        log::warn!(
+            "{} pages failed, keeping last scrape time to retry them.",
+            failed_pages
+        );
+    }
 
     Ok(())
 }
 
-async fn update_scrape(db_client: &DbClient) -> Result<(), Box<dyn std::error::Error>> {
+async fn update_scrape(
+    http_client: &UrlFetcher,
+    db_client: &DbClient,
+) -> Result<(), Box<dyn std::error::Error>> {
     let auction_states = &[
         AuctionState::Ongoing,
         AuctionState::ToBeOpened,
         AuctionState::Suspended,
     ];
-    let http_client = &UrlFetcher::new();
     let auction_ids = &db_client
         .get_auction_ids_with_states(auction_states)
         .await?;
@@ -70,10 +122,10 @@ async fn update_scrape(db_client: &DbClient) -> Result<(), Box<dyn std::error::E
     let main_page = http_client
         .post_url(
             &MAIN_ALL_AUCTIONS_BOE_POST_URL,
-            MAIN_ALL_AUCTIONS_BOE_PARAMS,
+            &BoeSearchQuery::new().to_form(),
         )
         .await?;
-    let mut pages_url = shylock_parser::parser::parse_extra_pages(&main_page);
+    let mut pages_url = shylock_parser::parser::parse_extra_pages(&main_page)?;
 
     pages_url.insert(0, MAIN_ALL_AUCTIONS_BOE_POST_URL.to_string());
     log::info!("Total BOE pages to process: {}", pages_url.len());
@@ -93,10 +145,222 @@ async fn update_scrape(db_client: &DbClient) -> Result<(), Box<dyn std::error::E
     Ok(())
 }
 
-async fn export_ongoing_auctions(db_client: &DbClient) -> Result<(), Box<dyn std::error::Error>> {
+async fn changes_scrape(
+    http_client: &UrlFetcher,
+    db_client: &DbClient,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let auction_states = &[
+        AuctionState::Ongoing,
+        AuctionState::ToBeOpened,
+        AuctionState::Suspended,
+    ];
+    let auctions = db_client.get_auctions_with_states(auction_states).await?;
+    let assets = &db_client
+        .get_assets_by_auction_with_states(auction_states)
+        .await?;
+
+    log::info!(
+        "Total BOE auctions to check for changes: {}",
+        auctions.len()
+    );
+
+    stream::iter(auctions.iter())
+        .for_each_concurrent(DEFAULT_CONCURRENCY, |auction| async move {
+            let auction_assets = assets.get(&auction.id).map_or(&[][..], Vec::as_slice);
+            match auction_changes_scraper(http_client, db_client, auction, auction_assets).await {
+                Ok(0) => {}
+                Ok(changes) => log::info!("Auction {} changed {} fields.", auction.id, changes),
+                Err(error) => log::warn!("Unable to check auction {}: {}", auction.id, error),
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+async fn results_scrape(
+    http_client: &UrlFetcher,
+    db_client: &DbClient,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut auctions = Vec::new();
+    for auction in db_client
+        .get_auctions_with_states(&[AuctionState::Finished])
+        .await?
+    {
+        if !db_client.auction_outcome_exists(&auction.id).await? {
+            auctions.push(auction);
+        }
+    }
+
+    log::info!(
+        "Total BOE concluded auctions without outcome: {}",
+        auctions.len()
+    );
+
+    stream::iter(auctions.iter())
+        .for_each_concurrent(DEFAULT_CONCURRENCY, |auction| async move {
+            if let Err(error) = auction_outcome_scraper(http_client, db_client, auction).await {
+                log::warn!("Unable to get outcome of auction {}: {}", auction.id, error);
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+async fn attachments_scrape(
+    http_client: &UrlFetcher,
+    db_client: &DbClient,
+    store: &AttachmentStore,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let auctions = db_client
+        .get_auctions_with_states(&[
+            AuctionState::Ongoing,
+            AuctionState::ToBeOpened,
+            AuctionState::Suspended,
+        ])
+        .await?;
+
+    log::info!(
+        "Total BOE auctions to download attachments: {}",
+        auctions.len()
+    );
+
+    stream::iter(auctions.iter())
+        .for_each_concurrent(DEFAULT_CONCURRENCY, |auction| async move {
+            match auction_attachments_scraper(http_client, db_client, store, auction).await {
+                Ok(0) => {}
+                Ok(stored) => log::info!("Auction {} stored {} attachments.", auction.id, stored),
+                Err(error) => log::warn!(
+                    "Unable to download attachments of auction {}: {}",
+                    auction.id,
+                    error
+                ),
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+async fn retry_failed_auctions(
+    http_client: &UrlFetcher,
+    db_client: &DbClient,
+    max_attempts: u32,
+    backoff: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let now: DateTime<FixedOffset> = Utc::now().into();
+    let failed_auctions: Vec<_> = db_client
+        .get_failed_auctions()
+        .await?
+        .into_iter()
+        .filter(|failed| failed.attempts < max_attempts && failed.is_due(&now, backoff))
+        .collect();
+
+    log::info!(
+        "Total failed BOE auctions to retry: {}",
+        failed_auctions.len()
+    );
+
+    stream::iter(failed_auctions.iter())
+        .for_each_concurrent(DEFAULT_CONCURRENCY, |failed| async move {
+            match failed_auction_scraper(http_client, db_client, failed).await {
+                Ok(()) => log::info!("Auction {} retried successfully.", failed.auction_id),
+                Err(error) => log::warn!(
+                    "Auction {} failed again after {} attempts: {}",
+                    failed.auction_id,
+                    failed.attempts + 1,
+                    error
+                ),
+            }
+        })
+        .await;
+
+    Ok(())
+}
+
+async fn report_failed_auctions(
+    db_client: &DbClient,
+    max_attempts: u32,
+    fixtures_dir: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for failed in db_client
+        .get_failed_auctions()
+        .await?
+        .into_iter()
+        .filter(|failed| failed.attempts >= max_attempts)
+    {
+        println!(
+            "{} {} attempts, last {}: {}",
+            failed.auction_id, failed.attempts, failed.last_attempt_at, failed.error
+        );
+
+        if let Some(dir) = fixtures_dir {
+            std::fs::create_dir_all(dir)?;
+            for (url, content) in db_client
+                .get_failed_auction_pages(&failed.auction_id)
+                .await?
+            {
+                std::fs::write(
+                    std::path::Path::new(dir).join(fixture_file_name(&url, None)),
+                    content,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn search_attachments(
+    db_client: &DbClient,
+    text: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (auction_id, lot, attachment) in db_client.search_attachments(text).await? {
+        println!(
+            "{} {}: {} {} {}",
+            auction_id,
+            lot.map_or("-".to_owned(), |lot| lot.to_string()),
+            attachment.kind.name(),
+            attachment.title,
+            attachment.source_url
+        );
+    }
+
+    Ok(())
+}
+
+// Publish the stored copies of `attachments` next to the frontend, the ones
+// not downloaded yet are not exported as the frontend only links stored copies.
+fn publish_attachments(store: &AttachmentStore, attachments: &mut Vec<Attachment>) {
+    let dir = format!(
+        "{}/../shylock-dominator/dist/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        PUBLISHED_ATTACHMENTS_DIR
+    );
+
+    attachments.retain(|attachment| match store.publish(attachment, &dir) {
+        Ok(()) => true,
+        Err(error) => {
+            log::warn!(
+                "Unable to publish attachment {} from {}: {}",
+                attachment.hash,
+                attachment.source_url,
+                error
+            );
+            false
+        }
+    });
+}
+
+async fn export_ongoing_auctions(
+    http_client: UrlFetcher,
+    db_client: &DbClient,
+    store: &AttachmentStore,
+) -> Result<(), Box<dyn std::error::Error>> {
     let mut auctions: BTreeMap<String, Auction> = BTreeMap::new();
     let mut assets: Vec<Asset> = Vec::new();
-    let geosolver = &GeoSolver::new();
+    let geosolver = &GeoSolver::with_client(http_client);
 
     db_client
         .get_auctions_with_states(&[AuctionState::Ongoing])
@@ -106,31 +370,21 @@ async fn export_ongoing_auctions(db_client: &DbClient) -> Result<(), Box<dyn std
             auctions.insert(x.id.clone(), x);
         });
 
-    let auction_file = format!(
-        "{}/../shylock-dominator/{}",
-        env!("CARGO_MANIFEST_DIR"),
-        "auctions.cbor.zlib"
-    );
-    dump_to_cbor_compressed_file(&auction_file, &auctions)?;
-
     let mut properties = db_client
         .get_properties_with_auction_states(&[AuctionState::Ongoing])
         .await?;
 
     stream::iter(properties.iter_mut())
         .for_each_concurrent(DEFAULT_CONCURRENCY, |property| async move {
-            if property.catastro_link.is_none()
-                && valid_catastro_reference(&property.catastro_reference)
+            if let (None, Some(catastro_reference)) =
+                (&property.catastro_link, &property.catastro_reference)
             {
-                match geosolver
-                    .get_catastro_link(&property.catastro_reference)
-                    .await
-                {
+                match geosolver.get_catastro_link(catastro_reference).await {
                     Ok(link) => property.catastro_link = link,
                     Err(error) => log::warn!(
                         "Unable to get catastro link {} for catastro reference: {}",
                         error,
-                        &property.catastro_reference
+                        catastro_reference
                     ),
                 }
             }
@@ -142,7 +396,7 @@ async fn export_ongoing_auctions(db_client: &DbClient) -> Result<(), Box<dyn std
                         property.province.name(),
                         DEFAULT_COUNTRY,
                         &property.postal_code,
-                        &property.catastro_reference,
+                        property.catastro_reference.as_ref(),
                     )
                     .await
                 {
@@ -188,12 +442,152 @@ async fn export_ongoing_auctions(db_client: &DbClient) -> Result<(), Box<dyn std
             assets.push(Asset::Other(x));
         });
 
+    assets.iter_mut().for_each(|asset| match asset {
+        Asset::Property(property) => publish_attachments(store, &mut property.attachments),
+        Asset::Vehicle(vehicle) => publish_attachments(store, &mut vehicle.attachments),
+        Asset::Other(other) => publish_attachments(store, &mut other.attachments),
+    });
+
+    let mut counts = ExportCounts {
+        auctions: auctions.len(),
+        ..ExportCounts::default()
+    };
+    assets.iter().for_each(|asset| match asset {
+        Asset::Property(_) => counts.properties += 1,
+        Asset::Vehicle(_) => counts.vehicles += 1,
+        Asset::Other(_) => counts.others += 1,
+    });
+    let header = ExportHeader::new(
+        Utc::now().into(),
+        db_client.get_last_done_scrape_run_id().await?,
+        counts,
+    );
+
+    let auction_file = format!(
+        "{}/../shylock-dominator/{}",
+        env!("CARGO_MANIFEST_DIR"),
+        "auctions.cbor.zlib"
+    );
+    dump_to_cbor_compressed_file(&auction_file, &header, &auctions)?;
+
     let assets_file = format!(
         "{}/../shylock-dominator/{}",
         env!("CARGO_MANIFEST_DIR"),
         "assets.cbor.zlib"
     );
-    dump_to_cbor_compressed_file(&assets_file, &assets)?;
+    dump_to_cbor_compressed_file(&assets_file, &header, &assets)?;
+
+    Ok(())
+}
+
+fn attachment_store(
+    matches: &clap::ArgMatches,
+) -> Result<AttachmentStore, Box<dyn std::error::Error>> {
+    let max_size = match matches.value_of("max_attachment_size") {
+        Some(max_size) => max_size.parse::<usize>()?,
+        None => DEFAULT_MAX_ATTACHMENT_SIZE,
+    };
+
+    Ok(AttachmentStore::new(
+        matches
+            .value_of("attachments_dir")
+            .unwrap_or(DEFAULT_ATTACHMENTS_DIR),
+        max_size,
+    ))
+}
+
+fn parse_search_date(
+    matches: &clap::ArgMatches,
+    name: &str,
+) -> Result<Option<NaiveDate>, Box<dyn std::error::Error>> {
+    match matches.value_of(name) {
+        Some(date) => Ok(Some(NaiveDate::parse_from_str(date, "%Y-%m-%d")?)),
+        None => Ok(None),
+    }
+}
+
+fn search_query(matches: &clap::ArgMatches) -> Result<BoeSearchQuery, Box<dyn std::error::Error>> {
+    let mut query = BoeSearchQuery::new()
+        .end_date(
+            parse_search_date(matches, "end_from")?,
+            parse_search_date(matches, "end_to")?,
+        )
+        .start_date(
+            parse_search_date(matches, "start_from")?,
+            parse_search_date(matches, "start_to")?,
+        );
+
+    if let Some(province) = matches.value_of("province") {
+        query = query.province(Province::from_ine_code(province)?);
+    }
+    if let Some(city) = matches.value_of("city") {
+        query = query.city(city);
+    }
+    if let Some(postal_code) = matches.value_of("postal_code") {
+        query = query.postal_code(postal_code);
+    }
+    if let Some(asset_type) = matches.value_of("asset_type") {
+        query = query.asset_type(asset_type);
+    }
+    if let Some(state_code) = matches.value_of("state_code") {
+        query = query.state_code(state_code);
+    }
+
+    Ok(query)
+}
+
+async fn report_state_transitions(
+    db_client: &DbClient,
+    since: Duration,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let now: DateTime<FixedOffset> = Utc::now().into();
+    let since = now - chrono::Duration::from_std(since)?;
+
+    let changes = db_client.get_state_changes_since(&since).await?;
+    log::info!(
+        "Auction state transitions since {}: {}",
+        since,
+        changes.len()
+    );
+
+    for (auction_id, change) in changes {
+        match change.previous {
+            Some(previous) => println!(
+                "{} {}: {} -> {}",
+                change.date, auction_id, previous, change.state
+            ),
+            None => println!("{} {}: new {}", change.date, auction_id, change.state),
+        }
+    }
+
+    Ok(())
+}
+
+async fn report_price_statistics(db_client: &DbClient) -> Result<(), Box<dyn std::error::Error>> {
+    let outcomes = db_client.get_property_outcomes().await?;
+    let statistics = price_statistics(
+        outcomes
+            .iter()
+            .map(|(province, category, outcome, bidinfo)| {
+                (*province, *category, outcome, *bidinfo)
+            }),
+    );
+
+    for statistic in statistics {
+        println!(
+            "{} {}: {} concluded, {} deserted, achieved {} of appraisal",
+            statistic.province.name(),
+            statistic.category.name(),
+            statistic.auctions,
+            statistic.deserted,
+            statistic
+                .average_ratio
+                .map_or("-".to_owned(), |ratio| format!(
+                    "{:.2}%",
+                    ratio * Decimal::ONE_HUNDRED
+                )),
+        );
+    }
 
     Ok(())
 }
@@ -222,22 +616,150 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             arg!(<MODE>)
                 .help(
                     r#"create: creates database and tables.
-init: initializes database loading all auctions and assets.
+init: initializes database loading all auctions and assets, --resume continues the last unfinished init.
+incremental: checks stored ongoing auctions for changes and loads auctions started since the last incremental scrape.
 update: updates ongoing auctions status.
-export: exports ongoing auctions and assets to cbor files.
+changes: updates and records changes of ongoing auctions and assets.
+results: stores outcomes of concluded auctions.
+attachments: downloads documents and photos of ongoing auctions.
+search: searches text in downloaded documents.
+export: exports ongoing auctions and assets to cbor files and publishes their stored attachments.
 statistics: exports auction statistics as images.
+transitions: reports recent auction state transitions.
+prices: reports achieved prices of concluded property auctions.
+retry-failed: retries failed auctions whose backoff has elapsed.
+failed-report: reports auctions failing after --max_attempts, --fixtures_dir saves their pages.
 "#,
                 )
-                .value_parser(["create", "init", "update", "export", "statistics"]),
+                .value_parser([
+                    "create",
+                    "init",
+                    "incremental",
+                    "update",
+                    "changes",
+                    "results",
+                    "attachments",
+                    "search",
+                    "export",
+                    "statistics",
+                    "transitions",
+                    "prices",
+                    "retry-failed",
+                    "failed-report",
+                ]),
         )
         .arg(
             arg!(-d --db_path <DB_PATH> "Sets the database path, default: ./db/shylock.db")
                 .required(false),
         )
+        .arg(
+            arg!(--cache <CACHE_MODE> "Sets the http responses cache mode, default: off")
+                .required(false)
+                .value_parser(["off", "record", "replay", "refresh-after"]),
+        )
+        .arg(
+            arg!(--cache_dir <CACHE_DIR> "Sets the http responses cache path, default: ./cache")
+                .required(false),
+        )
+        .arg(
+            arg!(--refresh_after <DURATION> "Sets the age of cached responses to request again with refresh-after cache mode, e.g. 12h")
+                .required(false),
+        )
+        .arg(
+            arg!(--attachments_dir <ATTACHMENTS_DIR> "Sets the downloaded attachments path, default: ./attachments")
+                .required(false),
+        )
+        .arg(
+            arg!(--max_attachment_size <BYTES> "Sets the maximum size of a downloaded attachment, default: 20 MiB")
+                .required(false),
+        )
+        .arg(
+            arg!(--text <TEXT> "Sets the text to look for in search mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--resume "Resumes the last unfinished init scrape retrying its failed pages")
+                .required(false),
+        )
+        .arg(
+            arg!(--overlap <DURATION> "Sets the window before the last scrape to search again in incremental mode, default: 1d")
+                .required(false),
+        )
+        .arg(
+            arg!(--since <DURATION> "Sets how far back to report in transitions mode, default: 7d")
+                .required(false),
+        )
+        .arg(
+            arg!(--max_attempts <ATTEMPTS> "Sets the attempts before a failed auction is no longer retried, default: 5")
+                .required(false),
+        )
+        .arg(
+            arg!(--backoff <DURATION> "Sets the wait before retrying a failed auction, doubled after each attempt, default: 1h")
+                .required(false),
+        )
+        .arg(
+            arg!(--fixtures_dir <FIXTURES_DIR> "Sets the path to save pages of failed auctions in failed-report mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--province <INE_CODE> "Only scrape auctions with assets in the province, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--city <CITY> "Only scrape auctions with assets in the city, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--postal_code <POSTAL_CODE> "Only scrape auctions with assets in the postal code, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--asset_type <CODE> "Only scrape auctions with assets of the BOE type code, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--state_code <CODE> "Only scrape auctions in the BOE state code, e.g. EJ for ongoing, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--end_from <DATE> "Only scrape auctions ending from date YYYY-MM-DD, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--end_to <DATE> "Only scrape auctions ending until date YYYY-MM-DD, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--start_from <DATE> "Only scrape auctions starting from date YYYY-MM-DD, init mode")
+                .required(false),
+        )
+        .arg(
+            arg!(--start_to <DATE> "Only scrape auctions starting until date YYYY-MM-DD, init mode")
+                .required(false),
+        )
         .get_matches();
 
     let db_path = matches.value_of("db_path").unwrap_or(DEFAULT_DB_PATH);
 
+    let cache_mode = match matches
+        .get_one::<String>("cache")
+        .map_or("off", |mode| mode.as_str())
+    {
+        "record" => CacheMode::Record,
+        "replay" => CacheMode::Replay,
+        "refresh-after" => CacheMode::RefreshAfter(parse_duration(
+            matches
+                .value_of("refresh_after")
+                .ok_or("refresh-after cache mode requires --refresh_after duration")?,
+        )?),
+        _ => CacheMode::Off,
+    };
+    let cache = ResponseCache::new(
+        matches.value_of("cache_dir").unwrap_or(DEFAULT_CACHE_DIR),
+        cache_mode,
+    );
+    let http_client = || UrlFetcher::new().with_cache(cache.clone());
+
     let db_client = DbClient::new(db_path).await?;
 
     match matches
@@ -251,20 +773,87 @@ statistics: exports auction statistics as images.
         }
         "init" => {
             log::info!("Initialization mode going to all auctions.");
-            let _ = init_scrape(&db_client).await;
+            let query = search_query(&matches)?;
+            let resume = matches.contains_id("resume");
+            match init_scrape(&http_client(), &db_client, INIT_SCRAPE, &query, resume).await {
+                Ok(0) => log::info!("Init scrape finished."),
+                Ok(failed_pages) => log::warn!(
+                    "Init scrape finished with {} failed pages, run again with --resume to retry them.",
+                    failed_pages
+                ),
+                Err(error) => log::error!("Init scrape failed: {}", error),
+            }
+        }
+        "incremental" => {
+            log::info!("Incremental mode going to auctions since last scrape.");
+            let overlap = parse_duration(matches.value_of("overlap").unwrap_or(DEFAULT_OVERLAP))?;
+            if let Err(error) = incremental_scrape(&http_client(), &db_client, overlap).await {
+                log::error!("Incremental scrape failed: {}", error);
+            }
         }
         "update" => {
             log::info!("Updating status of ongoing auctions.");
-            let _ = update_scrape(&db_client).await;
+            let _ = update_scrape(&http_client(), &db_client).await;
+        }
+        "changes" => {
+            log::info!("Checking changes of ongoing auctions.");
+            let _ = changes_scrape(&http_client(), &db_client).await;
+        }
+        "results" => {
+            log::info!("Storing outcomes of concluded auctions.");
+            let _ = results_scrape(&http_client(), &db_client).await;
+        }
+        "attachments" => {
+            log::info!("Downloading attachments of ongoing auctions.");
+            let store = attachment_store(&matches)?;
+            let _ = attachments_scrape(&http_client(), &db_client, &store).await;
+        }
+        "search" => {
+            let text = matches
+                .value_of("text")
+                .ok_or("search mode requires --text")?;
+            search_attachments(&db_client, text).await?;
         }
         "export" => {
             log::info!("Exporting ongoing auctions and assets to cbor files.");
-            let _ = export_ongoing_auctions(&db_client).await;
+            let store = attachment_store(&matches)?;
+            let _ = export_ongoing_auctions(http_client(), &db_client, &store).await;
         }
         "statistics" => {
             log::info!("Exporting auction statistics as images.");
             let _ = export_auction_statistics(&db_client).await;
         }
+        "transitions" => {
+            log::info!("Reporting recent auction state transitions.");
+            let since = parse_duration(
+                matches
+                    .value_of("since")
+                    .unwrap_or(DEFAULT_TRANSITIONS_SINCE),
+            )?;
+            report_state_transitions(&db_client, since).await?;
+        }
+        "prices" => {
+            log::info!("Reporting achieved prices of concluded property auctions.");
+            report_price_statistics(&db_client).await?;
+        }
+        "retry-failed" => {
+            log::info!("Retrying failed auctions.");
+            let max_attempts = match matches.value_of("max_attempts") {
+                Some(max_attempts) => max_attempts.parse::<u32>()?,
+                None => DEFAULT_MAX_ATTEMPTS,
+            };
+            let backoff =
+                parse_duration(matches.value_of("backoff").unwrap_or(DEFAULT_RETRY_BACKOFF))?;
+            retry_failed_auctions(&http_client(), &db_client, max_attempts, backoff).await?;
+        }
+        "failed-report" => {
+            let max_attempts = match matches.value_of("max_attempts") {
+                Some(max_attempts) => max_attempts.parse::<u32>()?,
+                None => DEFAULT_MAX_ATTEMPTS,
+            };
+            report_failed_auctions(&db_client, max_attempts, matches.value_of("fixtures_dir"))
+                .await?;
+        }
         _ => unreachable!(),
     }
 
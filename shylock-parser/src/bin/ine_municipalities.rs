@@ -0,0 +1,51 @@
+use clap::{arg, Command};
+use env_logger::Env;
+use shylock_data::municipalities::Municipality;
+
+const DEFAULT_OUTPUT: &str = "shylock-data/data/municipalities.csv";
+const CSV_HEADER: &str = "ine_code;name;alternative_names";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    env_logger::from_env(Env::default().default_filter_or("info")).init();
+
+    let matches = Command::new("ine_municipalities")
+        .about("Regenerate the embedded municipalities dataset from the INE dictionary")
+        .arg(arg!(<DICTIONARY> "INE diccionario de municipios saved as semicolon separated CSV"))
+        .arg(arg!(-o --output <FILE> "Dataset to write").required(false))
+        .get_matches();
+
+    let dictionary = std::fs::read_to_string(matches.value_of("DICTIONARY").unwrap())?;
+    let output = matches.value_of("output").unwrap_or(DEFAULT_OUTPUT);
+
+    let mut municipalities = Municipality::from_ine_dictionary(&dictionary);
+    municipalities.sort_by(|a, b| a.ine_code.cmp(&b.ine_code));
+
+    for known in Municipality::all() {
+        if !municipalities
+            .iter()
+            .any(|municipality| municipality.ine_code == known.ine_code)
+        {
+            log::warn!(
+                "Municipality {} {} not found in the INE dictionary",
+                known.ine_code,
+                known.name
+            );
+        }
+    }
+
+    let mut csv = String::from(CSV_HEADER);
+    for municipality in municipalities.iter() {
+        csv.push('\n');
+        csv.push_str(&municipality.to_csv_line());
+    }
+    csv.push('\n');
+    std::fs::write(output, csv)?;
+
+    log::info!(
+        "Written {} municipalities to {}",
+        municipalities.len(),
+        output
+    );
+
+    Ok(())
+}
@@ -18,5 +18,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("asset {}: {:?}", i, asset);
     }
 
+    for warning in result.2.iter() {
+        println!("warning: {}", warning);
+    }
+
     Ok(())
 }
@@ -0,0 +1,310 @@
+use serde::Serialize;
+use serde_json::Value;
+use shylock_data::types::{Asset, Auction};
+
+use crate::util::normalize;
+
+// Field recorded when a whole asset is added, removed or changes its kind.
+const ASSET_FIELD: &str = "asset";
+
+/// Part of an auction a change belongs to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ChangeTarget {
+    /// The auction itself.
+    Auction,
+    /// The asset with the lot, `None` for auctions without lots.
+    Asset(Option<u32>),
+}
+
+/// Change of one field of an auction or of one of its assets.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FieldChange {
+    /// Auction changed.
+    pub auction_id: String,
+    /// Auction or asset changed.
+    pub target: ChangeTarget,
+    /// Name of the field changed.
+    pub field: String,
+    /// Stored value, `None` if it was empty.
+    pub old_value: Option<String>,
+    /// Published value, `None` if it is empty.
+    pub new_value: Option<String>,
+}
+
+fn field_value<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_value(value).expect("Serializing field value") {
+        Value::Null => None,
+        Value::String(value) => Some(value),
+        value => Some(value.to_string()),
+    }
+}
+
+macro_rules! diff_fields {
+    ($changes:expr, $auction_id:expr, $target:expr, $old:expr, $new:expr, [$($field:ident),+ $(,)?]) => {{
+        $(
+        if $old.$field != $new.$field {
+            $changes.push(FieldChange {
+                auction_id: $auction_id.to_owned(),
+                target: $target,
+                field: stringify!($field).to_owned(),
+                old_value: field_value(&$old.$field),
+                new_value: field_value(&$new.$field),
+            });
+        }
+        )+
+    }};
+}
+
+/// Fields that differ between the `stored` auction and the `fresh` one.
+pub fn diff_auction(stored: &Auction, fresh: &Auction) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    diff_fields!(
+        changes,
+        stored.id,
+        ChangeTarget::Auction,
+        stored,
+        fresh,
+        [
+            auction_state,
+            kind,
+            claim_quantity,
+            lots,
+            lot_kind,
+            management,
+            bidinfo,
+            start_date,
+            end_date,
+            notice,
+            account_record,
+            suspension_reason,
+        ]
+    );
+
+    changes
+}
+
+fn diff_asset(auction_id: &str, stored: &Asset, fresh: &Asset) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let target = ChangeTarget::Asset(fresh.lot());
+
+    match (stored, fresh) {
+        (Asset::Property(old), Asset::Property(new)) => diff_fields!(
+            changes,
+            auction_id,
+            target,
+            old,
+            new,
+            [
+                address,
+                allotment,
+                area,
+                bidinfo,
+                catastro_reference,
+                category,
+                charges,
+                city,
+                description,
+                encumbrances,
+                features,
+                idufir,
+                municipality,
+                owner_status,
+                place,
+                postal_code,
+                primary_residence,
+                province,
+                quota,
+                register_inscription,
+                visitable,
+            ]
+        ),
+        (Asset::Vehicle(old), Asset::Vehicle(new)) => diff_fields!(
+            changes,
+            auction_id,
+            target,
+            old,
+            new,
+            [
+                bidinfo,
+                brand,
+                category,
+                charges,
+                description,
+                encumbrances,
+                frame_number,
+                licensed_date,
+                license_plate,
+                localization,
+                model,
+                plate,
+                registration_period,
+                vin,
+                visitable,
+            ]
+        ),
+        (Asset::Other(old), Asset::Other(new)) => diff_fields!(
+            changes,
+            auction_id,
+            target,
+            old,
+            new,
+            [
+                acquisition_date,
+                additional_information,
+                bidinfo,
+                category,
+                charges,
+                description,
+                encumbrances,
+                judicial_title,
+                quota,
+                visitable,
+            ]
+        ),
+        _ => changes.push(FieldChange {
+            auction_id: auction_id.to_owned(),
+            target,
+            field: ASSET_FIELD.to_owned(),
+            old_value: field_value(stored),
+            new_value: field_value(fresh),
+        }),
+    }
+
+    changes
+}
+
+/// Fields that differ between the `stored` assets of an auction and the `fresh` ones,
+/// assets are matched by lot and added or removed lots are recorded as a whole.
+pub fn diff_assets(auction_id: &str, stored: &[Asset], fresh: &[Asset]) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    for new in fresh {
+        match stored.iter().find(|old| old.lot() == new.lot()) {
+            Some(old) => changes.extend(diff_asset(auction_id, old, new)),
+            None => changes.push(FieldChange {
+                auction_id: auction_id.to_owned(),
+                target: ChangeTarget::Asset(new.lot()),
+                field: ASSET_FIELD.to_owned(),
+                old_value: None,
+                new_value: field_value(new),
+            }),
+        }
+    }
+
+    for old in stored {
+        if !fresh.iter().any(|new| new.lot() == old.lot()) {
+            changes.push(FieldChange {
+                auction_id: auction_id.to_owned(),
+                target: ChangeTarget::Asset(old.lot()),
+                field: ASSET_FIELD.to_owned(),
+                old_value: field_value(old),
+                new_value: None,
+            });
+        }
+    }
+
+    changes
+}
+
+/// Prepare `fresh` assets to be compared with the `stored` ones,
+/// normalizing text as stored and keeping information not published by BOE.
+pub fn carry_over_assets(stored: &[Asset], fresh: &mut [Asset]) {
+    for new in fresh.iter_mut() {
+        let old = stored.iter().find(|old| old.lot() == new.lot());
+
+        match new {
+            Asset::Property(property) => {
+                property.city = normalize(&property.city);
+                if let Some(Asset::Property(old)) = old {
+                    property.catastro_link = old.catastro_link.clone();
+                    property.coordinates = old.coordinates;
+                }
+            }
+            Asset::Vehicle(vehicle) => {
+                vehicle.brand = normalize(&vehicle.brand);
+                vehicle.model = normalize(&vehicle.model);
+            }
+            Asset::Other(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::{FixtureClient, HttpClient};
+    use crate::parser::parse_result_page;
+    use crate::scraper::process_auction_link;
+    use crate::test_util::{FIXTURES_DIR, RESULT_PAGE_URL};
+    use rust_decimal::Decimal;
+    use shylock_data::AuctionState;
+
+    async fn fixture_auction() -> (Auction, Vec<Asset>) {
+        let client = FixtureClient::new(FIXTURES_DIR);
+        let links = parse_result_page(&client.get_url(RESULT_PAGE_URL).await.unwrap()).unwrap();
+        let (auction, assets, _) = process_auction_link(&client, &links[0]).await.unwrap();
+
+        (auction, assets)
+    }
+
+    #[tokio::test]
+    async fn diff_auction_test() {
+        let (stored, _) = fixture_auction().await;
+        let mut fresh = stored.clone();
+        assert_eq!(Vec::<FieldChange>::new(), diff_auction(&stored, &fresh));
+
+        fresh.claim_quantity += Decimal::ONE_HUNDRED;
+        fresh.notice = "BOE-B-2020-00000".to_owned();
+        let changes = diff_auction(&stored, &fresh);
+        assert_eq!(2, changes.len());
+        assert_eq!("claim_quantity", changes[0].field);
+        assert_eq!(ChangeTarget::Auction, changes[0].target);
+        assert_eq!(
+            Some((stored.claim_quantity + Decimal::ONE_HUNDRED).to_string()),
+            changes[0].new_value
+        );
+        assert_eq!("notice", changes[1].field);
+        assert_eq!(Some(stored.notice.clone()), changes[1].old_value);
+
+        let mut fresh = stored.clone();
+        fresh.auction_state = AuctionState::Suspended;
+        fresh.suspension_reason = Some("Suspendida por resolución judicial".to_owned());
+        let changes = diff_auction(&stored, &fresh);
+        assert_eq!(2, changes.len());
+        assert_eq!("auction_state", changes[0].field);
+        assert_eq!("suspension_reason", changes[1].field);
+        assert_eq!(None, changes[1].old_value);
+    }
+
+    #[tokio::test]
+    async fn diff_assets_test() {
+        let (auction, stored) = fixture_auction().await;
+        let (_, mut fresh) = fixture_auction().await;
+        carry_over_assets(&stored, &mut fresh);
+        assert_eq!(
+            Vec::<FieldChange>::new(),
+            diff_assets(&auction.id, &stored, &fresh)
+        );
+
+        if let Asset::Property(property) = &mut fresh[0] {
+            property.postal_code = "47002".to_owned();
+        }
+        let changes = diff_assets(&auction.id, &stored, &fresh);
+        assert_eq!(1, changes.len());
+        assert_eq!("postal_code", changes[0].field);
+        assert_eq!(Some("47002".to_owned()), changes[0].new_value);
+
+        let (_, lot) = fixture_auction().await;
+        fresh.push(lot.into_iter().next().unwrap().with_lot(Some(2)));
+        let changes = diff_assets(&auction.id, &stored, &fresh);
+        assert_eq!(2, changes.len());
+        assert_eq!(ASSET_FIELD, changes[1].field);
+        assert_eq!(ChangeTarget::Asset(Some(2)), changes[1].target);
+        assert_eq!(None, changes[1].old_value);
+
+        let changes = diff_assets(&auction.id, &fresh, &stored);
+        assert_eq!(ASSET_FIELD, changes[1].field);
+        assert_eq!(None, changes[1].new_value);
+    }
+}
@@ -1,17 +1,24 @@
+use chrono::{SecondsFormat, Utc};
 use geo_types::Point;
 use rust_decimal::Decimal;
 use shylock_data::{
-    Asset, Auction, AuctionState, BidInfo, Management, Other, Property, Vehicle, DEFAULT_DECIMALS,
+    categories::PropertyCategory, provinces::Province, Asset, Attachment, Auction, AuctionOutcome,
+    AuctionState, AuctionStateChange, BidInfo, CatastroReference, Confidence, DataError, DateTime,
+    Encumbrance, Extracted, FixedOffset, Idufir, LicensePlate, Management, NaiveDate, Other,
+    Property, PropertyFeatures, RegistrationPeriod, Vehicle, Vin,
 };
 use sqlx::{
     sqlite::{
-        SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow, SqliteSynchronous,
+        SqliteConnectOptions, SqliteConnection, SqliteJournalMode, SqlitePoolOptions, SqliteRow,
+        SqliteSynchronous,
     },
     Pool, Row, Sqlite,
 };
+use std::collections::HashMap;
 use std::fmt::Write;
 use std::{str::FromStr, time::Duration};
 
+use crate::changes::{ChangeTarget, FieldChange};
 use crate::util::normalize;
 
 /// Default path for db file.
@@ -19,6 +26,193 @@ pub const DEFAULT_DB_PATH: &str = "./db/shylock.db";
 const DEFAULT_POOL_TIMEOUT: Duration = Duration::from_secs(30);
 const DEFAULT_MAX_CONNECTIONS: u32 = 5;
 
+// Lot stored for assets of auctions without lots.
+const NO_LOT: u32 = 0;
+
+fn lot_to_db(lot: Option<u32>) -> u32 {
+    lot.unwrap_or(NO_LOT)
+}
+
+fn lot_from_db(lot: u32) -> Option<u32> {
+    if lot == NO_LOT {
+        None
+    } else {
+        Some(lot)
+    }
+}
+
+// Dates are stored as ISO-8601, malformed ones are reported as decoding errors.
+fn parse_datetime(column: &str, value: &str) -> Result<DateTime<FixedOffset>, sqlx::Error> {
+    DateTime::parse_from_rfc3339(value).map_err(|err| sqlx::Error::ColumnDecode {
+        index: column.to_owned(),
+        source: Box::new(err),
+    })
+}
+
+fn datetime_column(row: &SqliteRow, column: &str) -> Result<DateTime<FixedOffset>, sqlx::Error> {
+    parse_datetime(column, row.try_get(column)?)
+}
+
+fn optional_datetime_column(
+    row: &SqliteRow,
+    column: &str,
+) -> Result<Option<DateTime<FixedOffset>>, sqlx::Error> {
+    row.try_get::<Option<&str>, _>(column)?
+        .map(|value| parse_datetime(column, value))
+        .transpose()
+}
+
+fn state_change_from_row(row: &SqliteRow) -> Result<AuctionStateChange, sqlx::Error> {
+    Ok(AuctionStateChange {
+        previous: row.get("previous_state"),
+        state: row.get("state"),
+        date: datetime_column(row, "changed_at")?,
+    })
+}
+
+fn confidence<T>(extracted: &Option<Extracted<T>>) -> Option<Confidence> {
+    extracted.as_ref().map(|extracted| extracted.confidence)
+}
+
+fn extracted<T>(value: Option<T>, confidence: Option<Confidence>) -> Option<Extracted<T>> {
+    value
+        .zip(confidence)
+        .map(|(value, confidence)| Extracted { value, confidence })
+}
+
+fn property_features_from_row(row: &SqliteRow) -> PropertyFeatures {
+    PropertyFeatures {
+        annexes: row
+            .get::<Option<String>, _>("annexes")
+            .and_then(|annexes| serde_json::from_str(&annexes).ok())
+            .unwrap_or_default(),
+        bathrooms: extracted(row.get("bathrooms"), row.get("bathrooms_confidence")),
+        built_area: extracted(
+            parse_optional_decimal(row.get("built_area")),
+            row.get("built_area_confidence"),
+        ),
+        finca_number: extracted(row.get("finca_number"), row.get("finca_number_confidence")),
+        floor: extracted(row.get("floor"), row.get("floor_confidence")),
+        rooms: extracted(row.get("rooms"), row.get("rooms_confidence")),
+        usable_area: extracted(
+            parse_optional_decimal(row.get("usable_area")),
+            row.get("usable_area_confidence"),
+        ),
+    }
+}
+
+// Decimal columns keep the numeric value, read as text to avoid losing decimals.
+fn decimal_column(row: &SqliteRow, column: &str) -> Decimal {
+    row.get_unchecked::<Option<String>, _>(column)
+        .and_then(|value| Decimal::from_str(&value).ok())
+        .unwrap_or_default()
+}
+
+fn parse_optional_decimal(value: Option<String>) -> Option<Decimal> {
+    value.and_then(|value| Decimal::from_str(&value).ok())
+}
+
+// Attachments of the asset with `lot`, including those linked from the whole auction.
+fn asset_attachments(
+    attachments: &HashMap<(String, u32), Vec<Attachment>>,
+    auction_id: &str,
+    lot: u32,
+) -> Vec<Attachment> {
+    let mut result = attachments
+        .get(&(auction_id.to_owned(), NO_LOT))
+        .cloned()
+        .unwrap_or_default();
+    if lot != NO_LOT {
+        result.extend(
+            attachments
+                .get(&(auction_id.to_owned(), lot))
+                .cloned()
+                .unwrap_or_default(),
+        );
+    }
+
+    result
+}
+
+fn attachment_from_row(row: &SqliteRow) -> Attachment {
+    Attachment {
+        kind: row.get("kind"),
+        title: row.get("title"),
+        source_url: row.get("source_url"),
+        hash: row.get("hash"),
+        content_type: row.get("content_type"),
+        size: row.get::<i64, _>("size") as u64,
+    }
+}
+
+/// Status of a scrape run, one of its result pages or auction links.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, sqlx::Type)]
+pub enum ScrapeStatus {
+    /// Not finished yet.
+    Pending,
+    /// Finished without errors.
+    Done,
+    /// Finished with errors, to be retried.
+    Failed,
+}
+
+/// Scrape run whose result pages and auction links are checkpointed to resume it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ScrapeRun {
+    /// Run identifier.
+    pub id: i64,
+    /// Scrape mode, e.g. init or incremental.
+    pub name: String,
+    /// Search form posted to get the result pages.
+    pub query: String,
+    /// Run status.
+    pub status: ScrapeStatus,
+    /// When the run started.
+    pub started_at: DateTime<FixedOffset>,
+    /// When the run last finished, if it did.
+    pub finished_at: Option<DateTime<FixedOffset>>,
+}
+
+/// Auction that failed to be scraped, kept to be retried.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FailedAuction {
+    /// Auction identifier.
+    pub auction_id: String,
+    /// Link to the auction with the state it was listed with.
+    pub link: (String, AuctionState),
+    /// Error of the last attempt.
+    pub error: String,
+    /// Number of failed attempts.
+    pub attempts: u32,
+    /// When the auction failed for the first time.
+    pub first_failed_at: DateTime<FixedOffset>,
+    /// When the auction was last attempted.
+    pub last_attempt_at: DateTime<FixedOffset>,
+}
+
+impl FailedAuction {
+    /// If the auction can be retried at `now`, waiting `backoff` after the first
+    /// attempt and doubling it after each one.
+    pub fn is_due(&self, now: &DateTime<FixedOffset>, backoff: Duration) -> bool {
+        backoff
+            .checked_mul(2u32.saturating_pow(self.attempts.saturating_sub(1)))
+            .and_then(|wait| chrono::Duration::from_std(wait).ok())
+            .and_then(|wait| self.last_attempt_at.checked_add_signed(wait))
+            .is_some_and(|next_attempt| next_attempt <= *now)
+    }
+}
+
+fn scrape_run_from_row(row: &SqliteRow) -> Result<ScrapeRun, sqlx::Error> {
+    Ok(ScrapeRun {
+        id: row.get("id"),
+        name: row.get("name"),
+        query: row.get("query"),
+        status: row.get("status"),
+        started_at: datetime_column(row, "started_at")?,
+        finished_at: optional_datetime_column(row, "finished_at")?,
+    })
+}
+
 /// Database client.
 #[derive(Debug)]
 pub struct DbClient {
@@ -56,17 +250,34 @@ impl DbClient {
         Ok(())
     }
 
-    async fn insert_other_asset(&self, auction: &Auction, other: &Other) {
+    async fn insert_other_asset(
+        conn: &mut SqliteConnection,
+        auction: &Auction,
+        other: &Other,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
     INSERT INTO others(
-        additional_information, auction_id,
-        bidinfo, category, charges,
-        description, judicial_title,
+        acquisition_date, additional_information,
+        auction_id, bidinfo, category, charges,
+        description, judicial_title, lot, quota,
         visitable
     )
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?)"#,
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    ON CONFLICT(auction_id, lot)
+        DO UPDATE SET
+        acquisition_date = excluded.acquisition_date,
+        additional_information = excluded.additional_information,
+        bidinfo = excluded.bidinfo,
+        category = excluded.category,
+        charges = excluded.charges,
+        description = excluded.description,
+        judicial_title = excluded.judicial_title,
+        quota = excluded.quota,
+        visitable = excluded.visitable
+    "#,
         )
+        .bind(other.acquisition_date)
         .bind(&other.additional_information)
         .bind(&auction.id)
         .bind(other.bidinfo.as_ref().map(|bidinfo| bidinfo.to_string()))
@@ -74,45 +285,198 @@ impl DbClient {
         .bind(&other.charges.to_string())
         .bind(&other.description)
         .bind(&other.judicial_title)
-        .bind(&other.visitable)
-        .execute(&self.pool)
-        .await
-        .expect("Inserting asset other in db");
+        .bind(lot_to_db(other.lot))
+        .bind(other.quota.map(|quota| quota.to_string()))
+        .bind(other.visitable)
+        .execute(conn)
+        .await?;
+
+        Ok(())
     }
 
-    async fn insert_property_asset(&self, auction: &Auction, property: &Property) {
+    async fn insert_property_asset(
+        conn: &mut SqliteConnection,
+        auction: &Auction,
+        property: &Property,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
     INSERT INTO properties(
-        address, auction_id, bidinfo,
+        address, allotment, area,
+        auction_id, bidinfo,
         catastro_reference, category,
         charges, city, description,
-        owner_status, postal_code,
-        primary_residence, province,
+        idufir, lot, municipality,
+        owner_status, place,
+        postal_code, primary_residence,
+        province, quota,
         register_inscription, visitable
     )
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    ON CONFLICT(auction_id, lot)
+        DO UPDATE SET
+        address = excluded.address,
+        allotment = excluded.allotment,
+        area = excluded.area,
+        bidinfo = excluded.bidinfo,
+        catastro_reference = excluded.catastro_reference,
+        category = excluded.category,
+        charges = excluded.charges,
+        city = excluded.city,
+        description = excluded.description,
+        idufir = excluded.idufir,
+        municipality = excluded.municipality,
+        owner_status = excluded.owner_status,
+        place = excluded.place,
+        postal_code = excluded.postal_code,
+        primary_residence = excluded.primary_residence,
+        province = excluded.province,
+        quota = excluded.quota,
+        register_inscription = excluded.register_inscription,
+        visitable = excluded.visitable
+    "#,
         )
         .bind(&property.address)
+        .bind(&property.allotment)
+        .bind(property.area.map(|area| area.to_string()))
         .bind(&auction.id)
         .bind(property.bidinfo.as_ref().map(|bidinfo| bidinfo.to_string()))
-        .bind(&property.catastro_reference)
+        .bind(
+            property
+                .catastro_reference
+                .as_ref()
+                .map(|catastro_reference| catastro_reference.to_string()),
+        )
         .bind(property.category)
         .bind(&property.charges.to_string())
         .bind(&property.city)
         .bind(&property.description)
-        .bind(&property.owner_status)
+        .bind(property.idufir.as_ref().map(|idufir| idufir.to_string()))
+        .bind(lot_to_db(property.lot))
+        .bind(&property.municipality)
+        .bind(property.owner_status)
+        .bind(&property.place)
         .bind(&property.postal_code)
-        .bind(&property.primary_residence)
+        .bind(property.primary_residence)
         .bind(property.province)
+        .bind(property.quota.map(|quota| quota.to_string()))
         .bind(&property.register_inscription)
-        .bind(&property.visitable)
-        .execute(&self.pool)
-        .await
-        .expect("Inserting asset property in db");
+        .bind(property.visitable)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_property_features(
+        conn: &mut SqliteConnection,
+        auction: &Auction,
+        property: &Property,
+    ) -> Result<(), sqlx::Error> {
+        let features = &property.features;
+
+        sqlx::query(
+            r#"
+    INSERT INTO property_features(
+        auction_id, lot, annexes,
+        bathrooms, bathrooms_confidence,
+        built_area, built_area_confidence,
+        finca_number, finca_number_confidence,
+        floor, floor_confidence,
+        rooms, rooms_confidence,
+        usable_area, usable_area_confidence
+    )
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    ON CONFLICT(auction_id, lot)
+        DO UPDATE SET
+        annexes = excluded.annexes,
+        bathrooms = excluded.bathrooms,
+        bathrooms_confidence = excluded.bathrooms_confidence,
+        built_area = excluded.built_area,
+        built_area_confidence = excluded.built_area_confidence,
+        finca_number = excluded.finca_number,
+        finca_number_confidence = excluded.finca_number_confidence,
+        floor = excluded.floor,
+        floor_confidence = excluded.floor_confidence,
+        rooms = excluded.rooms,
+        rooms_confidence = excluded.rooms_confidence,
+        usable_area = excluded.usable_area,
+        usable_area_confidence = excluded.usable_area_confidence
+    "#,
+        )
+        .bind(&auction.id)
+        .bind(lot_to_db(property.lot))
+        .bind(serde_json::to_string(&features.annexes).expect("Serializing annexes"))
+        .bind(features.bathrooms.as_ref().map(|bathrooms| bathrooms.value))
+        .bind(confidence(&features.bathrooms))
+        .bind(
+            features
+                .built_area
+                .as_ref()
+                .map(|area| area.value.to_string()),
+        )
+        .bind(confidence(&features.built_area))
+        .bind(
+            features
+                .finca_number
+                .as_ref()
+                .map(|finca| finca.value.clone()),
+        )
+        .bind(confidence(&features.finca_number))
+        .bind(features.floor.as_ref().map(|floor| floor.value.clone()))
+        .bind(confidence(&features.floor))
+        .bind(features.rooms.as_ref().map(|rooms| rooms.value))
+        .bind(confidence(&features.rooms))
+        .bind(
+            features
+                .usable_area
+                .as_ref()
+                .map(|area| area.value.to_string()),
+        )
+        .bind(confidence(&features.usable_area))
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_encumbrances(
+        conn: &mut SqliteConnection,
+        auction: &Auction,
+        lot: Option<u32>,
+        encumbrances: &[Encumbrance],
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(r#"DELETE FROM encumbrances WHERE auction_id = ? AND lot = ?"#)
+            .bind(&auction.id)
+            .bind(lot_to_db(lot))
+            .execute(&mut *conn)
+            .await?;
+
+        for encumbrance in encumbrances {
+            sqlx::query(
+                r#"
+    INSERT INTO encumbrances(auction_id, lot, kind, creditor, amount, date)
+    VALUES (?, ?, ?, ?, ?, ?)
+    "#,
+            )
+            .bind(&auction.id)
+            .bind(lot_to_db(lot))
+            .bind(encumbrance.kind)
+            .bind(&encumbrance.creditor)
+            .bind(encumbrance.amount.map(|amount| amount.to_string()))
+            .bind(encumbrance.date)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
     }
 
-    async fn insert_vehicle_asset(&self, auction: &Auction, vehicle: &Vehicle) {
+    async fn insert_vehicle_asset(
+        conn: &mut SqliteConnection,
+        auction: &Auction,
+        vehicle: &Vehicle,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"
     INSERT INTO vehicles(
@@ -120,9 +484,29 @@ impl DbClient {
         category, charges, description,
         frame_number, licensed_date,
         license_plate, localization,
-        model, visitable
+        lot, model, plate,
+        registration_from, registration_to,
+        vin, visitable
     )
-    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+    ON CONFLICT(auction_id, lot)
+        DO UPDATE SET
+        bidinfo = excluded.bidinfo,
+        brand = excluded.brand,
+        category = excluded.category,
+        charges = excluded.charges,
+        description = excluded.description,
+        frame_number = excluded.frame_number,
+        licensed_date = excluded.licensed_date,
+        license_plate = excluded.license_plate,
+        localization = excluded.localization,
+        model = excluded.model,
+        plate = excluded.plate,
+        registration_from = excluded.registration_from,
+        registration_to = excluded.registration_to,
+        vin = excluded.vin,
+        visitable = excluded.visitable
+    "#,
         )
         .bind(&auction.id)
         .bind(vehicle.bidinfo.as_ref().map(|bidinfo| bidinfo.to_string()))
@@ -134,23 +518,56 @@ impl DbClient {
         .bind(vehicle.licensed_date)
         .bind(&vehicle.license_plate)
         .bind(&vehicle.localization)
+        .bind(lot_to_db(vehicle.lot))
         .bind(&vehicle.model)
-        .bind(&vehicle.visitable)
-        .execute(&self.pool)
-        .await
-        .expect("Inserting asset vehicle in db");
+        .bind(vehicle.plate.as_ref().map(|plate| plate.to_string()))
+        .bind(vehicle.registration_period.map(|period| period.from))
+        .bind(vehicle.registration_period.and_then(|period| period.to))
+        .bind(vehicle.vin.as_ref().map(|vin| vin.to_string()))
+        .bind(vehicle.visitable)
+        .execute(conn)
+        .await?;
+
+        Ok(())
     }
 
-    /// Insert `auction` `assets` (Property, vehicle and other) in db.
-    pub async fn insert_assets(&self, auction: &Auction, assets: &Vec<Asset>) {
+    async fn write_assets(
+        conn: &mut SqliteConnection,
+        auction: &Auction,
+        assets: &[Asset],
+    ) -> Result<(), sqlx::Error> {
         for asset in assets {
             match asset {
-                Asset::Other(other) => self.insert_other_asset(auction, other).await,
-
-                Asset::Property(property) => self.insert_property_asset(auction, property).await,
-                Asset::Vehicle(vehicle) => self.insert_vehicle_asset(auction, vehicle).await,
+                Asset::Other(other) => {
+                    Self::insert_other_asset(conn, auction, other).await?;
+                    Self::insert_encumbrances(conn, auction, other.lot, &other.encumbrances)
+                        .await?;
+                }
+                Asset::Property(property) => {
+                    Self::insert_property_asset(conn, auction, property).await?;
+                    Self::insert_property_features(conn, auction, property).await?;
+                    Self::insert_encumbrances(conn, auction, property.lot, &property.encumbrances)
+                        .await?;
+                }
+                Asset::Vehicle(vehicle) => {
+                    Self::insert_vehicle_asset(conn, auction, vehicle).await?;
+                    Self::insert_encumbrances(conn, auction, vehicle.lot, &vehicle.encumbrances)
+                        .await?;
+                }
             }
         }
+
+        Ok(())
+    }
+
+    /// Insert or update `auction` `assets` (Property, vehicle and other) in db,
+    /// assets are identified by auction and lot.
+    pub async fn insert_assets(&self, auction: &Auction, assets: &[Asset]) {
+        let mut conn = self.pool.acquire().await.expect("Acquiring db connection");
+
+        Self::write_assets(&mut conn, auction, assets)
+            .await
+            .expect("Inserting assets in db");
     }
 
     /// Insert `auction` in db.
@@ -159,8 +576,9 @@ impl DbClient {
             r#"INSERT INTO auctions(
         id, auction_state, kind, claim_quantity,
         lots, lot_kind, management, bidinfo,
-        start_date, end_date, notice)
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+        start_date, end_date, notice,
+        account_record, suspension_reason)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
         )
         .bind(&auction.id)
         .bind(auction.auction_state)
@@ -170,16 +588,216 @@ impl DbClient {
         .bind(&auction.lot_kind)
         .bind(&auction.management.code)
         .bind(&auction.bidinfo.to_string())
-        .bind(auction.start_date)
-        .bind(auction.end_date)
+        .bind(auction.start_date.to_rfc3339())
+        .bind(auction.end_date.to_rfc3339())
         .bind(&auction.notice)
+        .bind(&auction.account_record)
+        .bind(&auction.suspension_reason)
         .execute(&self.pool)
         .await
         .expect("Inserting auction in db");
+
+        let mut conn = self.pool.acquire().await.expect("Acquiring db connection");
+        Self::insert_state_change(&mut conn, &auction.id, None, auction.auction_state)
+            .await
+            .expect("Inserting auction state history in db");
     }
 
-    /// Insert `management` information in db.
-    pub async fn insert_management(&self, management: &Management) {
+    async fn insert_state_change(
+        conn: &mut SqliteConnection,
+        auction_id: &str,
+        previous: Option<AuctionState>,
+        state: AuctionState,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"INSERT INTO auction_state_history(
+        auction_id, previous_state, state, changed_at)
+        VALUES (?, ?, ?, ?)"#,
+        )
+        .bind(auction_id)
+        .bind(previous)
+        .bind(state)
+        .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, false))
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn write_auction_update(
+        conn: &mut SqliteConnection,
+        auction: &Auction,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"UPDATE auctions
+        SET auction_state = ?, kind = ?, claim_quantity = ?,
+        lots = ?, lot_kind = ?, management = ?, bidinfo = ?,
+        start_date = ?, end_date = ?, notice = ?,
+        account_record = ?, suspension_reason = ?
+        WHERE id = ?"#,
+        )
+        .bind(auction.auction_state)
+        .bind(&auction.kind)
+        .bind(auction.claim_quantity.to_string())
+        .bind(auction.lots)
+        .bind(&auction.lot_kind)
+        .bind(&auction.management.code)
+        .bind(auction.bidinfo.to_string())
+        .bind(auction.start_date.to_rfc3339())
+        .bind(auction.end_date.to_rfc3339())
+        .bind(&auction.notice)
+        .bind(&auction.account_record)
+        .bind(&auction.suspension_reason)
+        .bind(&auction.id)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Update stored `auction` with its current information.
+    pub async fn update_auction(
+        &self,
+        auction: &Auction,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Ok(Self::write_auction_update(&mut conn, auction).await?)
+    }
+
+    async fn delete_asset_rows(
+        conn: &mut SqliteConnection,
+        auction_id: &str,
+        lot: Option<u32>,
+    ) -> Result<(), sqlx::Error> {
+        for table in [
+            "properties",
+            "property_features",
+            "vehicles",
+            "others",
+            "encumbrances",
+        ] {
+            sqlx::query(&format!(
+                "DELETE FROM {} WHERE auction_id = ? AND lot = ?",
+                table
+            ))
+            .bind(auction_id)
+            .bind(lot_to_db(lot))
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Delete the asset with `lot` of `auction_id` auction and its related information.
+    pub async fn delete_asset(
+        &self,
+        auction_id: &str,
+        lot: Option<u32>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Ok(Self::delete_asset_rows(&mut conn, auction_id, lot).await?)
+    }
+
+    async fn write_auction_changes(
+        conn: &mut SqliteConnection,
+        changes: &[FieldChange],
+    ) -> Result<(), sqlx::Error> {
+        for change in changes {
+            let lot = match change.target {
+                ChangeTarget::Auction => None,
+                ChangeTarget::Asset(lot) => Some(lot_to_db(lot)),
+            };
+
+            sqlx::query(
+                r#"INSERT INTO auction_changes(
+        auction_id, lot, field, old_value, new_value)
+        VALUES (?, ?, ?, ?, ?)"#,
+            )
+            .bind(&change.auction_id)
+            .bind(lot)
+            .bind(&change.field)
+            .bind(&change.old_value)
+            .bind(&change.new_value)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Record auction `changes` in db.
+    pub async fn insert_auction_changes(
+        &self,
+        changes: &[FieldChange],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut conn = self.pool.acquire().await?;
+
+        Ok(Self::write_auction_changes(&mut conn, changes).await?)
+    }
+
+    /// Replace the `stored` auction and `stored_assets` with the `fresh` ones
+    /// published and record their `changes`, everything or nothing is written.
+    pub async fn apply_auction_changes(
+        &self,
+        stored: &Auction,
+        stored_assets: &[Asset],
+        fresh: &Auction,
+        fresh_assets: &[Asset],
+        changes: &[FieldChange],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut tx = self.pool.begin().await?;
+
+        Self::write_management(&mut tx, &fresh.management).await?;
+        Self::write_auction_update(&mut tx, fresh).await?;
+        if stored.auction_state != fresh.auction_state {
+            Self::insert_state_change(
+                &mut tx,
+                &fresh.id,
+                Some(stored.auction_state),
+                fresh.auction_state,
+            )
+            .await?;
+        }
+
+        // Lots removed or published now as another kind of asset are dropped whole.
+        for asset in stored_assets {
+            if !fresh_assets.iter().any(|fresh| {
+                fresh.lot() == asset.lot()
+                    && std::mem::discriminant(fresh) == std::mem::discriminant(asset)
+            }) {
+                Self::delete_asset_rows(&mut tx, &stored.id, asset.lot()).await?;
+            }
+        }
+        Self::write_assets(&mut tx, fresh, fresh_assets).await?;
+
+        Self::write_auction_changes(&mut tx, changes).await?;
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Returns the number of changes recorded for `auction_id` auction.
+    pub async fn count_auction_changes(
+        &self,
+        auction_id: &str,
+    ) -> Result<u32, Box<dyn std::error::Error>> {
+        Ok(
+            sqlx::query(r#"SELECT COUNT(*) FROM auction_changes WHERE auction_id = ?"#)
+                .bind(auction_id)
+                .map(|row: SqliteRow| row.get(0))
+                .fetch_one(&self.pool)
+                .await?,
+        )
+    }
+
+    async fn write_management(
+        conn: &mut SqliteConnection,
+        management: &Management,
+    ) -> Result<(), sqlx::Error> {
         sqlx::query(
             r#"INSERT INTO managements(
         code, description, address, telephone, fax, email)
@@ -199,9 +817,37 @@ impl DbClient {
         .bind(&management.telephone)
         .bind(&management.fax)
         .bind(&management.email)
-        .execute(&self.pool)
-        .await
-        .expect("Inserting management in db");
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Insert `management` information in db.
+    pub async fn insert_management(&self, management: &Management) {
+        let mut conn = self.pool.acquire().await.expect("Acquiring db connection");
+
+        Self::write_management(&mut conn, management)
+            .await
+            .expect("Inserting management in db");
+    }
+
+    /// Insert data `diagnostics` found while parsing auctions in db.
+    pub async fn insert_diagnostics(&self, diagnostics: &[DataError]) {
+        for diagnostic in diagnostics {
+            sqlx::query(
+                r#"INSERT INTO diagnostics(
+        auction_id, concept, value, kind)
+        VALUES (?, ?, ?, ?)"#,
+            )
+            .bind(&diagnostic.auction_id)
+            .bind(diagnostic.concept)
+            .bind(&diagnostic.value)
+            .bind(diagnostic.kind)
+            .execute(&self.pool)
+            .await
+            .expect("Inserting diagnostic in db");
+        }
     }
 
     /// Check if a auction with `id` is already in db.
@@ -217,6 +863,437 @@ impl DbClient {
         }
     }
 
+    /// Returns when the scrape `name` last finished successfully.
+    pub async fn get_last_scrape(
+        &self,
+        name: &str,
+    ) -> Result<Option<DateTime<FixedOffset>>, Box<dyn std::error::Error>> {
+        Ok(
+            sqlx::query(r#"SELECT last_success FROM scrape_state WHERE name = ?"#)
+                .bind(name)
+                .fetch_optional(&self.pool)
+                .await?
+                .map(|row: SqliteRow| datetime_column(&row, "last_success"))
+                .transpose()?,
+        )
+    }
+
+    /// Record the scrape `name` finished successfully at `date`.
+    pub async fn set_last_scrape(
+        &self,
+        name: &str,
+        date: &DateTime<FixedOffset>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(
+            r#"INSERT INTO scrape_state(name, last_success)
+        VALUES (?, ?)
+        ON CONFLICT(name) DO UPDATE SET last_success = excluded.last_success"#,
+        )
+        .bind(name)
+        .bind(date.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Create a pending scrape run `name` of the result pages of search `query`,
+    /// returns its identifier.
+    pub async fn create_scrape_run(
+        &self,
+        name: &str,
+        query: &str,
+    ) -> Result<i64, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"INSERT INTO scrape_runs(name, query, status, started_at) VALUES (?, ?, ?, ?)"#,
+        )
+        .bind(name)
+        .bind(query)
+        .bind(ScrapeStatus::Pending)
+        .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, false))
+        .execute(&self.pool)
+        .await?
+        .last_insert_rowid())
+    }
+
+    /// Returns the scrape run with `id`.
+    pub async fn get_scrape_run(
+        &self,
+        id: i64,
+    ) -> Result<Option<ScrapeRun>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(r#"SELECT * FROM scrape_runs WHERE id = ?"#)
+            .bind(id)
+            .try_map(|row: SqliteRow| scrape_run_from_row(&row))
+            .fetch_optional(&self.pool)
+            .await?)
+    }
+
+    /// Returns the last scrape run `name` not done, if any.
+    pub async fn get_last_unfinished_scrape_run(
+        &self,
+        name: &str,
+    ) -> Result<Option<ScrapeRun>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT * FROM scrape_runs WHERE name = ? AND status != ?
+            ORDER BY id DESC LIMIT 1"#,
+        )
+        .bind(name)
+        .bind(ScrapeStatus::Done)
+        .try_map(|row: SqliteRow| scrape_run_from_row(&row))
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    /// Returns the identifier of the last scrape run done, if any.
+    pub async fn get_last_done_scrape_run_id(
+        &self,
+    ) -> Result<Option<i64>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT id FROM scrape_runs WHERE status = ? ORDER BY finished_at DESC LIMIT 1"#,
+        )
+        .bind(ScrapeStatus::Done)
+        .map(|row: SqliteRow| row.get("id"))
+        .fetch_optional(&self.pool)
+        .await?)
+    }
+
+    /// Set the `status` of scrape run with `id` as it has finished.
+    pub async fn finish_scrape_run(
+        &self,
+        id: i64,
+        status: ScrapeStatus,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(r#"UPDATE scrape_runs SET status = ?, finished_at = ? WHERE id = ?"#)
+            .bind(status)
+            .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, false))
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Insert or update the `status` of result `page` of scrape run `run_id`,
+    /// finished statuses count as an attempt.
+    pub async fn set_scrape_page(
+        &self,
+        run_id: i64,
+        page: u32,
+        url: &str,
+        status: ScrapeStatus,
+        error: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(
+            r#"INSERT INTO scrape_pages(run_id, page, url, status, error, attempts, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(run_id, page)
+            DO UPDATE SET
+            url = excluded.url,
+            status = excluded.status,
+            error = excluded.error,
+            attempts = attempts + excluded.attempts,
+            updated_at = excluded.updated_at"#,
+        )
+        .bind(run_id)
+        .bind(page)
+        .bind(url)
+        .bind(status)
+        .bind(error)
+        .bind(u32::from(status != ScrapeStatus::Pending))
+        .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, false))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the result pages of scrape run `run_id` with their status.
+    pub async fn get_scrape_pages(
+        &self,
+        run_id: i64,
+    ) -> Result<Vec<(u32, ScrapeStatus)>, Box<dyn std::error::Error>> {
+        Ok(
+            sqlx::query(r#"SELECT page, status FROM scrape_pages WHERE run_id = ? ORDER BY page"#)
+                .bind(run_id)
+                .map(|row: SqliteRow| (row.get("page"), row.get("status")))
+                .fetch_all(&self.pool)
+                .await?,
+        )
+    }
+
+    /// Insert or update the `status` of auction `link` found in result `page`
+    /// of scrape run `run_id`.
+    pub async fn set_scrape_link(
+        &self,
+        run_id: i64,
+        page: u32,
+        auction_id: &str,
+        link: &str,
+        status: ScrapeStatus,
+        error: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(
+            r#"INSERT INTO scrape_links(
+        run_id, page, auction_id, link, status, error, attempts, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?, 1, ?)
+        ON CONFLICT(run_id, auction_id)
+            DO UPDATE SET
+            page = excluded.page,
+            link = excluded.link,
+            status = excluded.status,
+            error = excluded.error,
+            attempts = attempts + 1,
+            updated_at = excluded.updated_at"#,
+        )
+        .bind(run_id)
+        .bind(page)
+        .bind(auction_id)
+        .bind(link)
+        .bind(status)
+        .bind(error)
+        .bind(Utc::now().to_rfc3339_opts(SecondsFormat::Secs, false))
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Returns the auction links of scrape run `run_id` with their status.
+    pub async fn get_scrape_links(
+        &self,
+        run_id: i64,
+    ) -> Result<Vec<(String, ScrapeStatus)>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT auction_id, status FROM scrape_links WHERE run_id = ? ORDER BY auction_id"#,
+        )
+        .bind(run_id)
+        .map(|row: SqliteRow| (row.get("auction_id"), row.get("status")))
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Record a failed attempt to scrape the auction with `auction_id` from `link`
+    /// with its `error` and the raw `pages` fetched.
+    pub async fn record_failed_auction(
+        &self,
+        auction_id: &str,
+        link: &(String, AuctionState),
+        error: &str,
+        pages: &[(String, String)],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, false);
+        let mut tx = self.pool.begin().await?;
+
+        sqlx::query(
+            r#"INSERT INTO failed_auctions(
+        auction_id, link, auction_state, error, attempts, first_failed_at, last_attempt_at)
+        VALUES (?, ?, ?, ?, 1, ?, ?)
+        ON CONFLICT(auction_id)
+            DO UPDATE SET
+            link = excluded.link,
+            auction_state = excluded.auction_state,
+            error = excluded.error,
+            attempts = attempts + 1,
+            last_attempt_at = excluded.last_attempt_at"#,
+        )
+        .bind(auction_id)
+        .bind(&link.0)
+        .bind(link.1)
+        .bind(error)
+        .bind(&now)
+        .bind(&now)
+        .execute(&mut tx)
+        .await?;
+
+        sqlx::query(r#"DELETE FROM failed_auction_pages WHERE auction_id = ?"#)
+            .bind(auction_id)
+            .execute(&mut tx)
+            .await?;
+
+        for (url, content) in pages {
+            sqlx::query(
+                r#"INSERT OR REPLACE INTO failed_auction_pages(auction_id, url, content)
+            VALUES (?, ?, ?)"#,
+            )
+            .bind(auction_id)
+            .bind(url)
+            .bind(content)
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Delete the failed auction with `auction_id` and its pages, if any.
+    pub async fn delete_failed_auction(
+        &self,
+        auction_id: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(r#"DELETE FROM failed_auction_pages WHERE auction_id = ?"#)
+            .bind(auction_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query(r#"DELETE FROM failed_auctions WHERE auction_id = ?"#)
+            .bind(auction_id)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Returns all failed auctions.
+    pub async fn get_failed_auctions(
+        &self,
+    ) -> Result<Vec<FailedAuction>, Box<dyn std::error::Error>> {
+        Ok(
+            sqlx::query(r#"SELECT * FROM failed_auctions ORDER BY auction_id"#)
+                .try_map(|row: SqliteRow| {
+                    Ok(FailedAuction {
+                        auction_id: row.get("auction_id"),
+                        link: (row.get("link"), row.get("auction_state")),
+                        error: row.get("error"),
+                        attempts: row.get("attempts"),
+                        first_failed_at: datetime_column(&row, "first_failed_at")?,
+                        last_attempt_at: datetime_column(&row, "last_attempt_at")?,
+                    })
+                })
+                .fetch_all(&self.pool)
+                .await?,
+        )
+    }
+
+    /// Returns the url and content of the pages fetched in the last attempt
+    /// of failed auction with `auction_id`.
+    pub async fn get_failed_auction_pages(
+        &self,
+        auction_id: &str,
+    ) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT url, content FROM failed_auction_pages WHERE auction_id = ? ORDER BY rowid"#,
+        )
+        .bind(auction_id)
+        .map(|row: SqliteRow| (row.get("url"), row.get("content")))
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Insert or update `attachment` of auction with `auction_id` and `lot`,
+    /// `text` is the content text to be searched, if any.
+    pub async fn insert_attachment(
+        &self,
+        auction_id: &str,
+        lot: Option<u32>,
+        attachment: &Attachment,
+        text: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(
+            r#"INSERT INTO attachments(
+        auction_id, lot, kind, title, source_url, hash, content_type, size, content_text)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(auction_id, lot, source_url)
+            DO UPDATE SET
+            kind = excluded.kind,
+            title = excluded.title,
+            hash = excluded.hash,
+            content_type = excluded.content_type,
+            size = excluded.size,
+            content_text = excluded.content_text"#,
+        )
+        .bind(auction_id)
+        .bind(lot_to_db(lot))
+        .bind(attachment.kind)
+        .bind(&attachment.title)
+        .bind(&attachment.source_url)
+        .bind(&attachment.hash)
+        .bind(&attachment.content_type)
+        .bind(attachment.size as i64)
+        .bind(text)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Check if attachment from `source_url` of auction with `auction_id` is already in db.
+    pub async fn attachment_exists(
+        &self,
+        auction_id: &str,
+        source_url: &str,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT auction_id FROM attachments WHERE auction_id = ? AND source_url = ?"#,
+        )
+        .bind(auction_id)
+        .bind(source_url)
+        .fetch_optional(&self.pool)
+        .await?
+        .is_some())
+    }
+
+    /// Returns the auction, lot and attachment whose content text contains `text`.
+    pub async fn search_attachments(
+        &self,
+        text: &str,
+    ) -> Result<Vec<(String, Option<u32>, Attachment)>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT * FROM attachments WHERE content_text LIKE '%' || ? || '%'
+            ORDER BY auction_id, lot"#,
+        )
+        .bind(text)
+        .map(|row: SqliteRow| {
+            (
+                row.get("auction_id"),
+                lot_from_db(row.get("lot")),
+                attachment_from_row(&row),
+            )
+        })
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Insert or update the `outcome` of a concluded auction in db.
+    pub async fn insert_auction_outcome(
+        &self,
+        outcome: &AuctionOutcome,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(
+            r#"INSERT INTO auction_results(
+        auction_id, lot, outcome, highest_bid, concluded_at)
+        VALUES (?, ?, ?, ?, ?)
+        ON CONFLICT(auction_id, lot)
+            DO UPDATE SET
+            outcome = excluded.outcome,
+            highest_bid = excluded.highest_bid,
+            concluded_at = excluded.concluded_at"#,
+        )
+        .bind(&outcome.auction_id)
+        .bind(lot_to_db(outcome.lot))
+        .bind(outcome.kind)
+        .bind(outcome.highest_bid.map(|bid| bid.to_string()))
+        .bind(outcome.date.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Check if the outcome of auction with `id` is already in db.
+    pub async fn auction_outcome_exists(
+        &self,
+        id: &str,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        Ok(
+            sqlx::query(r#"SELECT auction_id FROM auction_results WHERE auction_id = ?"#)
+                .bind(id)
+                .fetch_optional(&self.pool)
+                .await?
+                .is_some(),
+        )
+    }
+
     /// Returns all auctions with a determine `state`.
     pub async fn get_auction_ids_with_states(
         &self,
@@ -238,12 +1315,27 @@ impl DbClient {
             .await?)
     }
 
-    /// Update `auction_id` auction with the new `state`.
+    /// Update `auction_id` auction with the new `state`,
+    /// recording the transition if the state changed.
     pub async fn update_auction_state(
         &self,
         auction_id: &str,
         state: AuctionState,
     ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut tx = self.pool.begin().await?;
+
+        let previous: Option<AuctionState> =
+            sqlx::query(r#"SELECT auction_state FROM auctions WHERE id = ?"#)
+                .bind(auction_id)
+                .map(|row: SqliteRow| row.get(0))
+                .fetch_optional(&mut tx)
+                .await?;
+        if previous == Some(state) {
+            return Ok(());
+        }
+
+        Self::insert_state_change(&mut tx, auction_id, previous, state).await?;
+
         sqlx::query(
             r#"UPDATE auctions
         SET auction_state = ?
@@ -251,9 +1343,11 @@ impl DbClient {
         )
         .bind(state)
         .bind(auction_id)
-        .execute(&self.pool)
+        .execute(&mut tx)
         .await?;
 
+        tx.commit().await?;
+
         Ok(())
     }
 
@@ -267,11 +1361,11 @@ impl DbClient {
             r#"UPDATE properties
             SET coordinates = ?
             WHERE auction_id = ? and
-            catastro_reference = ?"#,
+            lot = ?"#,
         )
         .bind(&format!("{} {}", point.0, point.1))
         .bind(&property.auction_id)
-        .bind(&property.catastro_reference)
+        .bind(lot_to_db(property.lot))
         .execute(&self.pool)
         .await?;
 
@@ -301,11 +1395,114 @@ impl DbClient {
         }
     }
 
+    async fn get_state_history_with_auction_states(
+        &self,
+        states: &[AuctionState],
+    ) -> Result<HashMap<String, Vec<AuctionStateChange>>, Box<dyn std::error::Error>> {
+        let mut select_query =
+            "SELECT h.* FROM auction_state_history h JOIN auctions a ON h.auction_id = a.id WHERE ("
+                .to_owned();
+
+        for (i, state) in states.iter().enumerate() {
+            if i > 0 {
+                write!(select_query, "or a.auction_state = '{}' ", state)?;
+            } else {
+                write!(select_query, "a.auction_state = '{}' ", state)?;
+            }
+        }
+        select_query.push_str(") ORDER BY julianday(h.changed_at)");
+
+        let rows = sqlx::query(&select_query)
+            .try_map(|row: SqliteRow| Ok((row.get("auction_id"), state_change_from_row(&row)?)))
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut history: HashMap<String, Vec<AuctionStateChange>> = HashMap::new();
+        for (auction_id, change) in rows {
+            history.entry(auction_id).or_default().push(change);
+        }
+
+        Ok(history)
+    }
+
+    /// Returns the state transitions of all auctions since `date`, oldest first.
+    pub async fn get_state_changes_since(
+        &self,
+        date: &DateTime<FixedOffset>,
+    ) -> Result<Vec<(String, AuctionStateChange)>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT * FROM auction_state_history
+            WHERE julianday(changed_at) >= julianday(?)
+            ORDER BY julianday(changed_at)"#,
+        )
+        .bind(date.to_rfc3339_opts(SecondsFormat::Secs, false))
+        .try_map(|row: SqliteRow| Ok((row.get("auction_id"), state_change_from_row(&row)?)))
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Returns how long `auction_id` auction has been in each state until `now`.
+    pub async fn get_time_in_states(
+        &self,
+        auction_id: &str,
+        now: &DateTime<FixedOffset>,
+    ) -> Result<Vec<(AuctionState, chrono::Duration)>, Box<dyn std::error::Error>> {
+        Ok(sqlx::query(
+            r#"SELECT state, SUM(
+                CAST(strftime('%s', COALESCE(next_changed_at, ?)) AS INTEGER)
+                - CAST(strftime('%s', changed_at) AS INTEGER)) AS seconds
+            FROM (
+                SELECT state, changed_at,
+                LEAD(changed_at) OVER (ORDER BY julianday(changed_at)) AS next_changed_at
+                FROM auction_state_history WHERE auction_id = ?
+            )
+            GROUP BY state"#,
+        )
+        .bind(now.to_rfc3339_opts(SecondsFormat::Secs, false))
+        .bind(auction_id)
+        .map(|row: SqliteRow| {
+            (
+                row.get("state"),
+                chrono::Duration::seconds(row.get("seconds")),
+            )
+        })
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
+    /// Returns how many times auctions with determine `states` have been suspended.
+    pub async fn get_suspension_counts(
+        &self,
+        states: &[AuctionState],
+    ) -> Result<HashMap<String, u32>, Box<dyn std::error::Error>> {
+        let mut select_query = r#"SELECT h.auction_id, COUNT(*) FROM auction_state_history h
+            JOIN auctions a ON h.auction_id = a.id WHERE h.state = ? AND ("#
+            .to_owned();
+
+        for (i, state) in states.iter().enumerate() {
+            if i > 0 {
+                write!(select_query, "or a.auction_state = '{}' ", state)?;
+            } else {
+                write!(select_query, "a.auction_state = '{}' ", state)?;
+            }
+        }
+        select_query.push_str(") GROUP BY h.auction_id");
+
+        Ok(sqlx::query(&select_query)
+            .bind(AuctionState::Suspended)
+            .map(|row: SqliteRow| (row.get(0), row.get(1)))
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .collect())
+    }
+
     /// Returns all auctions with determine `states`.
     pub async fn get_auctions_with_states(
         &self,
         states: &[AuctionState],
     ) -> Result<Vec<Auction>, Box<dyn std::error::Error>> {
+        let mut state_history = self.get_state_history_with_auction_states(states).await?;
         let mut select_query =
             "SELECT a.*, m.* FROM auctions a JOIN managements m ON a.management = m.code WHERE "
                 .to_owned();
@@ -319,8 +1516,7 @@ impl DbClient {
         }
 
         Ok(sqlx::query(&select_query)
-            .map(|row: SqliteRow| {
-                let claim_quantity: i64 = row.get_unchecked("claim_quantity");
+            .try_map(|row: SqliteRow| {
                 let bidinfo: String = row.get("bidinfo");
                 let management = Management {
                     code: row.get("code"),
@@ -331,31 +1527,121 @@ impl DbClient {
                     email: row.get("email"),
                 };
 
-                Auction {
-                    id: row.get("id"),
+                let id: String = row.get("id");
+
+                Ok(Auction {
+                    id: id.clone(),
                     auction_state: row.get("auction_state"),
                     kind: row.get("kind"),
-                    claim_quantity: Decimal::new(claim_quantity, DEFAULT_DECIMALS),
+                    claim_quantity: decimal_column(&row, "claim_quantity"),
                     lots: row.get("lots"),
                     lot_kind: row.get("lot_kind"),
                     management,
                     bidinfo: BidInfo::from_str(&bidinfo).unwrap(),
-                    start_date: row.get("start_date"),
-                    end_date: row.get("end_date"),
+                    start_date: datetime_column(&row, "start_date")?,
+                    end_date: datetime_column(&row, "end_date")?,
                     notice: row.get("notice"),
-                }
+                    account_record: row.get("account_record"),
+                    suspension_reason: row.get("suspension_reason"),
+                    state_history: state_history.remove(&id).unwrap_or_default(),
+                })
             })
             .fetch_all(&self.pool)
             .await?)
     }
 
+    async fn get_encumbrances_with_auction_states(
+        &self,
+        states: &[AuctionState],
+    ) -> Result<HashMap<(String, u32), Vec<Encumbrance>>, Box<dyn std::error::Error>> {
+        let mut select_query =
+            "SELECT e.* FROM encumbrances e JOIN auctions a ON e.auction_id = a.id WHERE "
+                .to_owned();
+
+        for (i, state) in states.iter().enumerate() {
+            if i > 0 {
+                write!(select_query, "or a.auction_state = '{}' ", state)?;
+            } else {
+                write!(select_query, "a.auction_state = '{}' ", state)?;
+            }
+        }
+
+        let rows = sqlx::query(&select_query)
+            .map(|row: SqliteRow| {
+                (
+                    (row.get("auction_id"), row.get("lot")),
+                    Encumbrance {
+                        kind: row.get("kind"),
+                        creditor: row.get("creditor"),
+                        amount: parse_optional_decimal(row.get("amount")),
+                        date: row.get("date"),
+                    },
+                )
+            })
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut encumbrances: HashMap<(String, u32), Vec<Encumbrance>> = HashMap::new();
+        for (key, encumbrance) in rows {
+            encumbrances.entry(key).or_default().push(encumbrance);
+        }
+
+        Ok(encumbrances)
+    }
+
+    async fn get_attachments_with_auction_states(
+        &self,
+        states: &[AuctionState],
+    ) -> Result<HashMap<(String, u32), Vec<Attachment>>, Box<dyn std::error::Error>> {
+        let mut select_query =
+            "SELECT t.* FROM attachments t JOIN auctions a ON t.auction_id = a.id WHERE "
+                .to_owned();
+
+        for (i, state) in states.iter().enumerate() {
+            if i > 0 {
+                write!(select_query, "or a.auction_state = '{}' ", state)?;
+            } else {
+                write!(select_query, "a.auction_state = '{}' ", state)?;
+            }
+        }
+        select_query.push_str("ORDER BY t.rowid");
+
+        let rows = sqlx::query(&select_query)
+            .map(|row: SqliteRow| {
+                (
+                    (row.get("auction_id"), row.get("lot")),
+                    attachment_from_row(&row),
+                )
+            })
+            .fetch_all(&self.pool)
+            .await?;
+
+        let mut attachments: HashMap<(String, u32), Vec<Attachment>> = HashMap::new();
+        for (key, attachment) in rows {
+            attachments.entry(key).or_default().push(attachment);
+        }
+
+        Ok(attachments)
+    }
+
     /// Returns all properties with determine auction `states`.
     pub async fn get_properties_with_auction_states(
         &self,
         states: &[AuctionState],
     ) -> Result<Vec<Property>, Box<dyn std::error::Error>> {
-        let mut select_query =
-            "SELECT p.* FROM properties p JOIN auctions a ON p.auction_id = a.id WHERE ".to_owned();
+        let attachments = self.get_attachments_with_auction_states(states).await?;
+        let mut encumbrances = self.get_encumbrances_with_auction_states(states).await?;
+        let mut select_query = r#"SELECT p.*,
+            f.annexes, f.bathrooms, f.bathrooms_confidence,
+            f.built_area, f.built_area_confidence,
+            f.finca_number, f.finca_number_confidence,
+            f.floor, f.floor_confidence,
+            f.rooms, f.rooms_confidence,
+            f.usable_area, f.usable_area_confidence
+            FROM properties p JOIN auctions a ON p.auction_id = a.id
+            LEFT JOIN property_features f ON f.auction_id = p.auction_id AND f.lot = p.lot
+            WHERE "#
+            .to_owned();
 
         for (i, state) in states.iter().enumerate() {
             if i > 0 {
@@ -367,8 +1653,10 @@ impl DbClient {
 
         Ok(sqlx::query(&select_query)
             .map(|row: SqliteRow| {
-                let charges: i64 = row.get_unchecked("charges");
                 let bidinfo: String = row.get("bidinfo");
+                let encumbrances = encumbrances
+                    .remove(&(row.get("auction_id"), row.get("lot")))
+                    .unwrap_or_default();
                 let points_str: Option<String> = row.get("coordinates");
                 let coordinates = if let Some(points) = points_str {
                     let points = points.split(' ').collect::<Vec<&str>>();
@@ -382,22 +1670,42 @@ impl DbClient {
 
                 Property {
                     address: row.get("address"),
+                    allotment: row.get("allotment"),
+                    area: parse_optional_decimal(row.get("area")),
+                    attachments: asset_attachments(
+                        &attachments,
+                        row.get("auction_id"),
+                        row.get("lot"),
+                    ),
                     auction_id: row.get("auction_id"),
                     bidinfo: match BidInfo::from_str(&bidinfo) {
                         Ok(v) => Some(v),
                         _ => None,
                     },
                     catastro_link: None,
-                    catastro_reference: row.get("catastro_reference"),
+                    catastro_reference: row
+                        .get::<Option<String>, _>("catastro_reference")
+                        .and_then(|catastro_reference| {
+                            CatastroReference::from_str(&catastro_reference).ok()
+                        }),
                     category: row.get("category"),
-                    charges: Decimal::new(charges, DEFAULT_DECIMALS),
+                    charges: decimal_column(&row, "charges"),
                     city: normalize(row.get("city")),
                     coordinates,
                     description: row.get("description"),
+                    encumbrances,
+                    features: property_features_from_row(&row),
+                    idufir: row
+                        .get::<Option<String>, _>("idufir")
+                        .and_then(|idufir| Idufir::from_str(&idufir).ok()),
+                    lot: lot_from_db(row.get("lot")),
+                    municipality: row.get("municipality"),
                     owner_status: row.get("owner_status"),
+                    place: row.get("place"),
                     postal_code: row.get("postal_code"),
                     primary_residence: row.get("primary_residence"),
                     province: row.get("province"),
+                    quota: parse_optional_decimal(row.get("quota")),
                     register_inscription: row.get("register_inscription"),
                     visitable: row.get("visitable"),
                 }
@@ -411,6 +1719,8 @@ impl DbClient {
         &self,
         states: &[AuctionState],
     ) -> Result<Vec<Vehicle>, Box<dyn std::error::Error>> {
+        let attachments = self.get_attachments_with_auction_states(states).await?;
+        let mut encumbrances = self.get_encumbrances_with_auction_states(states).await?;
         let mut select_query =
             "SELECT v.* FROM vehicles v JOIN auctions a ON v.auction_id = a.id WHERE ".to_owned();
 
@@ -424,10 +1734,17 @@ impl DbClient {
 
         Ok(sqlx::query(&select_query)
             .map(|row: SqliteRow| {
-                let charges: i64 = row.get_unchecked("charges");
                 let bidinfo: String = row.get("bidinfo");
+                let encumbrances = encumbrances
+                    .remove(&(row.get("auction_id"), row.get("lot")))
+                    .unwrap_or_default();
 
                 Vehicle {
+                    attachments: asset_attachments(
+                        &attachments,
+                        row.get("auction_id"),
+                        row.get("lot"),
+                    ),
                     auction_id: row.get("auction_id"),
                     bidinfo: match BidInfo::from_str(&bidinfo) {
                         Ok(v) => Some(v),
@@ -435,13 +1752,27 @@ impl DbClient {
                     },
                     brand: normalize(row.get("brand")),
                     category: row.get("category"),
-                    charges: Decimal::new(charges, DEFAULT_DECIMALS),
+                    charges: decimal_column(&row, "charges"),
                     description: row.get("description"),
+                    encumbrances,
                     frame_number: row.get("frame_number"),
                     licensed_date: row.get("licensed_date"),
                     license_plate: row.get("license_plate"),
                     localization: row.get("localization"),
+                    lot: lot_from_db(row.get("lot")),
                     model: normalize(row.get("model")),
+                    plate: row
+                        .get::<Option<String>, _>("plate")
+                        .and_then(|plate| LicensePlate::from_str(&plate).ok()),
+                    registration_period: row.get::<Option<NaiveDate>, _>("registration_from").map(
+                        |from| RegistrationPeriod {
+                            from,
+                            to: row.get("registration_to"),
+                        },
+                    ),
+                    vin: row
+                        .get::<Option<String>, _>("vin")
+                        .and_then(|vin| Vin::from_str(&vin).ok()),
                     visitable: row.get("visitable"),
                 }
             })
@@ -454,6 +1785,8 @@ impl DbClient {
         &self,
         states: &[AuctionState],
     ) -> Result<Vec<Other>, Box<dyn std::error::Error>> {
+        let attachments = self.get_attachments_with_auction_states(states).await?;
+        let mut encumbrances = self.get_encumbrances_with_auction_states(states).await?;
         let mut select_query =
             "SELECT o.* FROM others o JOIN auctions a ON o.auction_id = a.id WHERE ".to_owned();
 
@@ -467,20 +1800,31 @@ impl DbClient {
 
         Ok(sqlx::query(&select_query)
             .map(|row: SqliteRow| {
-                let charges: i64 = row.get_unchecked("charges");
                 let bidinfo: String = row.get("bidinfo");
+                let encumbrances = encumbrances
+                    .remove(&(row.get("auction_id"), row.get("lot")))
+                    .unwrap_or_default();
 
                 Other {
+                    acquisition_date: row.get("acquisition_date"),
                     additional_information: row.get("additional_information"),
+                    attachments: asset_attachments(
+                        &attachments,
+                        row.get("auction_id"),
+                        row.get("lot"),
+                    ),
                     auction_id: row.get("auction_id"),
                     bidinfo: match BidInfo::from_str(&bidinfo) {
                         Ok(v) => Some(v),
                         _ => None,
                     },
                     category: row.get("category"),
-                    charges: Decimal::new(charges, DEFAULT_DECIMALS),
+                    charges: decimal_column(&row, "charges"),
                     description: row.get("description"),
+                    encumbrances,
                     judicial_title: row.get("judicial_title"),
+                    lot: lot_from_db(row.get("lot")),
+                    quota: parse_optional_decimal(row.get("quota")),
                     visitable: row.get("visitable"),
                 }
             })
@@ -488,13 +1832,86 @@ impl DbClient {
             .await?)
     }
 
+    /// Returns all assets with determine auction `states` grouped by auction.
+    pub async fn get_assets_by_auction_with_states(
+        &self,
+        states: &[AuctionState],
+    ) -> Result<HashMap<String, Vec<Asset>>, Box<dyn std::error::Error>> {
+        let mut assets: HashMap<String, Vec<Asset>> = HashMap::new();
+
+        for property in self.get_properties_with_auction_states(states).await? {
+            assets
+                .entry(property.auction_id.clone())
+                .or_default()
+                .push(Asset::Property(property));
+        }
+        for vehicle in self.get_vehicles_with_auction_states(states).await? {
+            assets
+                .entry(vehicle.auction_id.clone())
+                .or_default()
+                .push(Asset::Vehicle(vehicle));
+        }
+        for other in self.get_other_assets_with_auction_states(states).await? {
+            assets
+                .entry(other.auction_id.clone())
+                .or_default()
+                .push(Asset::Other(other));
+        }
+
+        Ok(assets)
+    }
+
+    /// Returns the outcomes of concluded property auctions with the property
+    /// province, category and bid information.
+    pub async fn get_property_outcomes(
+        &self,
+    ) -> Result<
+        Vec<(Province, PropertyCategory, AuctionOutcome, BidInfo)>,
+        Box<dyn std::error::Error>,
+    > {
+        Ok(sqlx::query(
+            r#"SELECT r.*, p.province, p.category,
+            p.bidinfo AS property_bidinfo, a.bidinfo AS auction_bidinfo
+            FROM auction_results r
+            JOIN properties p ON p.auction_id = r.auction_id AND p.lot = r.lot
+            JOIN auctions a ON a.id = r.auction_id"#,
+        )
+        .try_map(|row: SqliteRow| {
+            let auction_bidinfo: String = row.try_get("auction_bidinfo")?;
+            let auction_bidinfo =
+                BidInfo::from_str(&auction_bidinfo).map_err(|_| sqlx::Error::ColumnDecode {
+                    index: "auction_bidinfo".to_owned(),
+                    source: format!("invalid bid info: {}", auction_bidinfo).into(),
+                })?;
+            let bidinfo = match BidInfo::from_str(row.get("property_bidinfo")) {
+                Ok(bidinfo) => bidinfo.merged_with(&auction_bidinfo),
+                _ => auction_bidinfo,
+            };
+
+            Ok((
+                row.get("province"),
+                row.get("category"),
+                AuctionOutcome {
+                    auction_id: row.get("auction_id"),
+                    lot: lot_from_db(row.get("lot")),
+                    kind: row.get("outcome"),
+                    highest_bid: parse_optional_decimal(row.get("highest_bid")),
+                    date: datetime_column(&row, "concluded_at")?,
+                },
+                bidinfo,
+            ))
+        })
+        .fetch_all(&self.pool)
+        .await?)
+    }
+
     /// Get statistics of number of auctions by month.
     pub async fn get_auctions_by_month_statistics(
         &self,
     ) -> Result<Vec<(String, u32)>, Box<dyn std::error::Error>> {
         let result = sqlx::query(
-            r#"SELECT COUNT(*) as 'started auctions', strftime("%Y-%m", start_date) as 'year-month' 
-            FROM auctions group by strftime("%Y-%m", start_date) order by 'year-month'"#,
+            r#"SELECT COUNT(*) as 'started auctions', substr(start_date, 1, 7) as 'year-month'
+            FROM auctions group by substr(start_date, 1, 7) order by substr(start_date, 1, 7)"#,
         )
         .map(|row: SqliteRow| {
             let n: u32 = row.get(0);
@@ -507,3 +1924,178 @@ impl DbClient {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{test_db, TempPath};
+    use sqlx::migrate::Migrator;
+    use std::borrow::Cow;
+
+    static MIGRATOR: Migrator = sqlx::migrate!("./sql");
+
+    // Database migrated up to, but not including, `version`.
+    async fn db_before_migration(name: &str, version: i64) -> (DbClient, TempPath) {
+        let db_path = TempPath::new(&format!("{}.db", name));
+        let db_client = DbClient::new(db_path.to_str().unwrap()).await.unwrap();
+        Migrator {
+            migrations: Cow::Owned(
+                MIGRATOR
+                    .iter()
+                    .filter(|migration| migration.version < version)
+                    .cloned()
+                    .collect(),
+            ),
+            ignore_missing: false,
+            locking: true,
+        }
+        .run(&db_client.pool)
+        .await
+        .unwrap();
+
+        (db_client, db_path)
+    }
+
+    async fn lots(db_client: &DbClient, table: &str, auction_id: &str) -> Vec<u32> {
+        sqlx::query(&format!(
+            "SELECT lot FROM {} WHERE auction_id = ? ORDER BY rowid",
+            table
+        ))
+        .bind(auction_id)
+        .map(|row: SqliteRow| row.get(0))
+        .fetch_all(&db_client.pool)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn asset_lots_migration_test() {
+        let (db_client, _db_path) = db_before_migration("asset-lots-migration", 3).await;
+
+        for statement in [
+            "INSERT INTO auctions(id, auction_state, lot_kind) VALUES ('SUB-MIXED', 'Finished', 'Splitted')",
+            "INSERT INTO auctions(id, auction_state, lot_kind) VALUES ('SUB-SINGLE', 'Finished', 'NotApplicable')",
+            "INSERT INTO properties(auction_id) VALUES ('SUB-MIXED')",
+            "INSERT INTO vehicles(auction_id) VALUES ('SUB-MIXED')",
+            "INSERT INTO properties(auction_id) VALUES ('SUB-MIXED')",
+            "INSERT INTO others(auction_id) VALUES ('SUB-MIXED')",
+            "INSERT INTO vehicles(auction_id) VALUES ('SUB-MIXED')",
+            "INSERT INTO properties(auction_id) VALUES ('SUB-SINGLE')",
+        ] {
+            sqlx::query(statement)
+                .execute(&db_client.pool)
+                .await
+                .unwrap();
+        }
+
+        db_client.migrate().await.unwrap();
+
+        assert_eq!(
+            vec![1, 2],
+            lots(&db_client, "properties", "SUB-MIXED").await
+        );
+        assert_eq!(vec![3, 4], lots(&db_client, "vehicles", "SUB-MIXED").await);
+        assert_eq!(vec![5], lots(&db_client, "others", "SUB-MIXED").await);
+        assert_eq!(
+            vec![NO_LOT],
+            lots(&db_client, "properties", "SUB-SINGLE").await
+        );
+    }
+
+    #[tokio::test]
+    async fn auction_datetimes_migration_test() {
+        let (db_client, _db_path) = db_before_migration("auction-datetimes-migration", 5).await;
+
+        for (id, date) in [
+            ("SUB-WINTER", "2020-01-15"),
+            ("SUB-SUMMER", "2020-07-14"),
+            ("SUB-SUMMER-START", "2020-03-29"),
+            ("SUB-SUMMER-FIRST", "2020-03-30"),
+            ("SUB-SUMMER-END", "2020-10-25"),
+            ("SUB-WINTER-FIRST", "2020-10-26"),
+        ] {
+            sqlx::query(
+                "INSERT INTO auctions(id, auction_state, start_date, end_date)
+                VALUES (?, 'Finished', ?, ?)",
+            )
+            .bind(id)
+            .bind(date)
+            .bind(date)
+            .execute(&db_client.pool)
+            .await
+            .unwrap();
+        }
+
+        db_client.migrate().await.unwrap();
+
+        let dates: Vec<(String, String, String)> =
+            sqlx::query("SELECT id, start_date, end_date FROM auctions ORDER BY start_date")
+                .map(|row: SqliteRow| (row.get(0), row.get(1), row.get(2)))
+                .fetch_all(&db_client.pool)
+                .await
+                .unwrap();
+        let expected = [
+            ("SUB-WINTER", "2020-01-15T00:00:00+01:00"),
+            ("SUB-SUMMER-START", "2020-03-29T00:00:00+01:00"),
+            ("SUB-SUMMER-FIRST", "2020-03-30T00:00:00+02:00"),
+            ("SUB-SUMMER", "2020-07-14T00:00:00+02:00"),
+            ("SUB-SUMMER-END", "2020-10-25T00:00:00+02:00"),
+            ("SUB-WINTER-FIRST", "2020-10-26T00:00:00+01:00"),
+        ];
+        assert_eq!(expected.len(), dates.len());
+        for ((id, date), (stored_id, start_date, end_date)) in expected.iter().zip(dates.iter()) {
+            assert_eq!(id, stored_id);
+            assert_eq!(date, start_date);
+            assert_eq!(date, end_date);
+        }
+    }
+
+    #[tokio::test]
+    async fn malformed_datetime_test() {
+        let (db_client, _db_path) = test_db("malformed-datetime").await;
+
+        for statement in [
+            "INSERT INTO managements(code) VALUES ('3003000230')",
+            "INSERT INTO auctions(
+            id, auction_state, kind, lot_kind, management, bidinfo, start_date, end_date)
+            VALUES ('SUB-MALFORMED', 'Ongoing', 'NotaryExtraJudicial', 'NotApplicable',
+            '3003000230', '0|0|0|0|0|0', 'invalid', '2020-08-03T18:00:00+02:00')",
+        ] {
+            sqlx::query(statement)
+                .execute(&db_client.pool)
+                .await
+                .unwrap();
+        }
+
+        assert!(db_client
+            .get_auctions_with_states(&[AuctionState::Ongoing])
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn auctions_by_month_statistics_test() {
+        let (db_client, _db_path) = test_db("auctions-by-month").await;
+
+        // Local dates are kept, in UTC both auctions would start in July.
+        for (id, date) in [
+            ("SUB-JULY", "2020-07-31T23:30:00+02:00"),
+            ("SUB-AUGUST", "2020-08-01T00:30:00+02:00"),
+            ("SUB-AUGUST-END", "2020-08-31T18:00:00+02:00"),
+        ] {
+            sqlx::query(
+                "INSERT INTO auctions(id, auction_state, start_date) VALUES (?, 'Ongoing', ?)",
+            )
+            .bind(id)
+            .bind(date)
+            .execute(&db_client.pool)
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(
+            vec![(String::from("2020-07"), 1), (String::from("2020-08"), 2)],
+            db_client.get_auctions_by_month_statistics().await.unwrap()
+        );
+    }
+}
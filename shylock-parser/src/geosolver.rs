@@ -2,14 +2,14 @@ use geo_types::Point;
 use lazy_static::lazy_static;
 use proj::Proj;
 use regex::Regex;
+use shylock_data::CatastroReference;
 use std::{borrow::Cow, thread, time};
 
 use crate::{
-    http::UrlFetcher,
+    http::{HttpClient, UrlFetcher},
     parser::{
         parse_coordinates_from_catastro_cpmrc_response, parse_data_from_catastro_dnprc_response,
     },
-    util::valid_catastro_reference,
 };
 
 const NOMINATIN_OSM_URL: &str = "https://nominatim.openstreetmap.org/search.php";
@@ -29,8 +29,8 @@ lazy_static! {
 
 /// Provides client for geosolving addresses.
 #[derive(Debug)]
-pub struct GeoSolver {
-    client: UrlFetcher,
+pub struct GeoSolver<C: HttpClient = UrlFetcher> {
+    client: C,
 }
 
 impl GeoSolver {
@@ -40,6 +40,13 @@ impl GeoSolver {
             client: UrlFetcher::new(),
         }
     }
+}
+
+impl<C: HttpClient> GeoSolver<C> {
+    /// Creates geosolver making requests with `client`.
+    pub fn with_client(client: C) -> Self {
+        GeoSolver { client }
+    }
 
     async fn get_url(&self, target: &str) -> Result<String, Box<dyn std::error::Error>> {
         let body = self.client.get_url(target).await?;
@@ -96,7 +103,7 @@ impl GeoSolver {
         log::debug!("catastro reference url: {}", url);
         let body = self.get_url(url).await?;
 
-        parse_coordinates_from_catastro_cpmrc_response(&body)
+        Ok(parse_coordinates_from_catastro_cpmrc_response(&body)?)
     }
 
     async fn try_nominatin(
@@ -136,17 +143,13 @@ impl GeoSolver {
 
     async fn try_catastro_reference(
         &self,
-        catastro_reference: &str,
+        catastro_reference: &CatastroReference,
     ) -> Result<Option<Point<f64>>, Box<dyn std::error::Error>> {
-        let url = if valid_catastro_reference(catastro_reference) {
-            format!(
-                "{}{}",
-                &*CATASTRO_COORDINATES_QUERY_WITH_REFERENCE_URL,
-                &catastro_reference[0..14]
-            )
-        } else {
-            return Ok(None);
-        };
+        let url = format!(
+            "{}{}",
+            &*CATASTRO_COORDINATES_QUERY_WITH_REFERENCE_URL,
+            catastro_reference.parcel()
+        );
 
         let mut result: Option<Point<f64>> = self.try_catastro_reference_url(&url).await?;
         result = Some(self.transform_cooordinates(result.unwrap()));
@@ -163,12 +166,15 @@ impl GeoSolver {
         province: &str,
         country: &str,
         postal_code: &str,
-        catastro_reference: &str,
+        catastro_reference: Option<&CatastroReference>,
     ) -> Result<Option<Point<f64>>, Box<dyn std::error::Error>> {
-        let mut result = self
-            .try_catastro_reference(catastro_reference)
-            .await
-            .unwrap_or(None);
+        let mut result = match catastro_reference {
+            Some(catastro_reference) => self
+                .try_catastro_reference(catastro_reference)
+                .await
+                .unwrap_or(None),
+            None => None,
+        };
 
         if result.is_none() {
             result = self
@@ -182,20 +188,19 @@ impl GeoSolver {
     /// get catastro link from a `catastro_reference`.
     pub async fn get_catastro_link(
         &self,
-        catastro_reference: &str,
+        catastro_reference: &CatastroReference,
     ) -> Result<Option<String>, Box<dyn std::error::Error>> {
-        let url = if valid_catastro_reference(catastro_reference) {
-            format!(
-                "{}{}",
-                &*CATASTRO_DATA_QUERY_WITH_REFERENCE_URL, catastro_reference
-            )
-        } else {
-            return Ok(None);
-        };
+        let url = format!(
+            "{}{}",
+            &*CATASTRO_DATA_QUERY_WITH_REFERENCE_URL, catastro_reference
+        );
         log::debug!("catastro reference url: {}", &url);
         let body = self.get_url(&url).await?;
 
-        parse_data_from_catastro_dnprc_response(&body, catastro_reference)
+        Ok(parse_data_from_catastro_dnprc_response(
+            &body,
+            catastro_reference.as_str(),
+        )?)
     }
 }
 
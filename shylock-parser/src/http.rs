@@ -1,35 +1,442 @@
+use async_trait::async_trait;
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use reqwest_tracing::TracingMiddleware;
+use sha2::{Digest, Sha256};
+use shylock_data::{provinces::Province, NaiveDate};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
 
 pub(crate) const BASE_BOE_URL: &str = "https://subastas.boe.es/";
 
-/// Params for getting all auctions in POST request.
-pub const MAIN_ALL_AUCTIONS_BOE_PARAMS: &str = "campo%5B0%5D=SUBASTA.ORIGEN&dato%5B0%5D=&campo%5B1%5D=SUBASTA.AUTORIDAD&dato%5B1%5D=&campo%5B2%5D=SUBASTA.ESTADO.CODIGO&dato%5B2%5D=&campo%5B3%5D=BIEN.TIPO&dato%5B3%5D=&dato%5B4%5D=&campo%5B5%5D=BIEN.DIRECCION&dato%5B5%5D=&campo%5B6%5D=BIEN.CODPOSTAL&dato%5B6%5D=&campo%5B7%5D=BIEN.LOCALIDAD&dato%5B7%5D=&campo%5B8%5D=BIEN.COD_PROVINCIA&dato%5B8%5D=&campo%5B9%5D=SUBASTA.POSTURA_MINIMA_MINIMA_LOTES&dato%5B9%5D=&campo%5B10%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_1&dato%5B10%5D=&campo%5B11%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_2&dato%5B11%5D=&campo%5B12%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_3&dato%5B12%5D=&campo%5B13%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_4&dato%5B13%5D=&campo%5B14%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_5&dato%5B14%5D=&campo%5B15%5D=SUBASTA.ID_SUBASTA_BUSCAR&dato%5B15%5D=&campo%5B16%5D=SUBASTA.ACREEDORES&dato%5B16%5D=&campo%5B17%5D=SUBASTA.FECHA_FIN&dato%5B17%5D%5B0%5D=&dato%5B17%5D%5B1%5D=&campo%5B18%5D=SUBASTA.FECHA_INICIO&dato%5B18%5D%5B0%5D=&dato%5B18%5D%5B1%5D=&page_hits=500&sort_field%5B0%5D=SUBASTA.FECHA_FIN&sort_order%5B0%5D=desc&sort_field%5B1%5D=SUBASTA.FECHA_FIN&sort_order%5B1%5D=asc&accion=Buscar";
-
 lazy_static! {
-    /// URI to obtain all ongoing auctions in BOE website.
-    pub static ref MAIN_ONGOING_AUCTIONS_BOE_URL: String = BASE_BOE_URL.to_owned() + "subastas_ava.php?campo%5B0%5D=SUBASTA.ORIGEN&dato%5B0%5D=&campo%5B1%5D=SUBASTA.AUTORIDAD&dato%5B1%5D=&campo%5B2%5D=SUBASTA.ESTADO&dato%5B2%5D=EJ&campo%5B3%5D=BIEN.TIPO&dato%5B3%5D=&dato%5B4%5D=&campo%5B5%5D=BIEN.DIRECCION&dato%5B5%5D=&campo%5B6%5D=BIEN.CODPOSTAL&dato%5B6%5D=&campo%5B7%5D=BIEN.LOCALIDAD&dato%5B7%5D=&campo%5B8%5D=BIEN.COD_PROVINCIA&dato%5B8%5D=&campo%5B9%5D=SUBASTA.POSTURA_MINIMA_MINIMA_LOTES&dato%5B9%5D=&campo%5B10%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_1&dato%5B10%5D=&campo%5B11%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_2&dato%5B11%5D=&campo%5B12%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_3&dato%5B12%5D=&campo%5B13%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_4&dato%5B13%5D=&campo%5B14%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_5&dato%5B14%5D=&campo%5B15%5D=SUBASTA.ID_SUBASTA_BUSCAR&dato%5B15%5D=&campo%5B16%5D=SUBASTA.FECHA_FIN_YMD&dato%5B16%5D%5B0%5D=&dato%5B16%5D%5B1%5D=&campo%5B17%5D=SUBASTA.FECHA_INICIO_YMD&dato%5B17%5D%5B0%5D=&dato%5B17%5D%5B1%5D=&page_hits=500&sort_field%5B0%5D=SUBASTA.FECHA_FIN_YMD&sort_order%5B0%5D=desc&sort_field%5B1%5D=SUBASTA.FECHA_FIN_YMD&sort_order%5B1%5D=asc&sort_field%5B2%5D=SUBASTA.HORA_FIN&sort_order%5B2%5D=asc&accion=Buscar";
-    /// URI to obtain all auctions in BOE website.
-    pub static ref MAIN_ALL_AUCTIONS_BOE_URL: String = BASE_BOE_URL.to_owned() + "subastas_ava.php?campo%5B0%5D=SUBASTA.ORIGEN&dato%5B0%5D=&campo%5B1%5D=SUBASTA.AUTORIDAD&dato%5B1%5D=&campo%5B2%5D=SUBASTA.ESTADO&dato%5B2%5D=&campo%5B3%5D=BIEN.TIPO&dato%5B3%5D=&dato%5B4%5D=&campo%5B5%5D=BIEN.DIRECCION&dato%5B5%5D=&campo%5B6%5D=BIEN.CODPOSTAL&dato%5B6%5D=&campo%5B7%5D=BIEN.LOCALIDAD&dato%5B7%5D=&campo%5B8%5D=BIEN.COD_PROVINCIA&dato%5B8%5D=&campo%5B9%5D=SUBASTA.POSTURA_MINIMA_MINIMA_LOTES&dato%5B9%5D=&campo%5B10%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_1&dato%5B10%5D=&campo%5B11%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_2&dato%5B11%5D=&campo%5B12%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_3&dato%5B12%5D=&campo%5B13%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_4&dato%5B13%5D=&campo%5B14%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_5&dato%5B14%5D=&campo%5B15%5D=SUBASTA.ID_SUBASTA_BUSCAR&dato%5B15%5D=&campo%5B16%5D=SUBASTA.FECHA_FIN_YMD&dato%5B16%5D%5B0%5D=&dato%5B16%5D%5B1%5D=&campo%5B17%5D=SUBASTA.FECHA_INICIO_YMD&dato%5B17%5D%5B0%5D=&dato%5B17%5D%5B1%5D=&page_hits=500&sort_field%5B0%5D=SUBASTA.FECHA_FIN_YMD&sort_order%5B0%5D=asc&sort_field%5B1%5D=SUBASTA.FECHA_FIN_YMD&sort_order%5B1%5D=asc&sort_field%5B2%5D=SUBASTA.HORA_FIN&sort_order%5B2%5D=asc&accion=Buscar";
     /// URI to obtain one auction in BOE website.
     pub static ref ONE_AUCTION_BOE_URL: String = BASE_BOE_URL.to_owned() + "detalleSubasta.php?idSub=";
     /// URI to obtain all auctions in BOE website.
     pub static ref MAIN_ALL_AUCTIONS_BOE_POST_URL: String = BASE_BOE_URL.to_owned() + "subastas_ava.php";
 }
 
+/// Auction state code of ongoing auctions in the BOE search form.
+pub const ONGOING_STATE_CODE: &str = "EJ";
+
+// Results per page, the maximum allowed by BOE.
+const DEFAULT_PAGE_SIZE: u32 = 500;
+
+const SEARCH_DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Order of the BOE search results.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum SortOrder {
+    /// Ascending order.
+    Ascending,
+    /// Descending order.
+    Descending,
+}
+
+impl SortOrder {
+    fn code(&self) -> &'static str {
+        match *self {
+            Self::Ascending => "asc",
+            Self::Descending => "desc",
+        }
+    }
+}
+
+/// Search in the BOE auctions advanced search form.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BoeSearchQuery {
+    origin: String,
+    authority: String,
+    state_code: String,
+    asset_type: String,
+    address: String,
+    postal_code: String,
+    city: String,
+    province: Option<Province>,
+    end_date: (Option<NaiveDate>, Option<NaiveDate>),
+    start_date: (Option<NaiveDate>, Option<NaiveDate>),
+    page_size: u32,
+    sort: Vec<(String, SortOrder)>,
+}
+
+fn urlencode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'*' => {
+                (byte as char).to_string()
+            }
+            b' ' => "+".to_owned(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn format_search_date(date: Option<NaiveDate>) -> String {
+    date.map(|date| date.format(SEARCH_DATE_FORMAT).to_string())
+        .unwrap_or_default()
+}
+
+impl BoeSearchQuery {
+    /// Search of all auctions sorted by end date.
+    pub fn new() -> Self {
+        BoeSearchQuery {
+            origin: String::new(),
+            authority: String::new(),
+            state_code: String::new(),
+            asset_type: String::new(),
+            address: String::new(),
+            postal_code: String::new(),
+            city: String::new(),
+            province: None,
+            end_date: (None, None),
+            start_date: (None, None),
+            page_size: DEFAULT_PAGE_SIZE,
+            sort: vec![
+                ("SUBASTA.FECHA_FIN".to_owned(), SortOrder::Descending),
+                ("SUBASTA.FECHA_FIN".to_owned(), SortOrder::Ascending),
+            ],
+        }
+    }
+
+    /// Auctions coming from `origin` code.
+    pub fn origin(mut self, origin: &str) -> Self {
+        self.origin = origin.to_owned();
+        self
+    }
+
+    /// Auctions managed by `authority` code.
+    pub fn authority(mut self, authority: &str) -> Self {
+        self.authority = authority.to_owned();
+        self
+    }
+
+    /// Auctions in `state_code` state, e.g. `ONGOING_STATE_CODE`.
+    pub fn state_code(mut self, state_code: &str) -> Self {
+        self.state_code = state_code.to_owned();
+        self
+    }
+
+    /// Auctions with assets of `asset_type` code.
+    pub fn asset_type(mut self, asset_type: &str) -> Self {
+        self.asset_type = asset_type.to_owned();
+        self
+    }
+
+    /// Auctions with assets in `address`.
+    pub fn address(mut self, address: &str) -> Self {
+        self.address = address.to_owned();
+        self
+    }
+
+    /// Auctions with assets in `postal_code`.
+    pub fn postal_code(mut self, postal_code: &str) -> Self {
+        self.postal_code = postal_code.to_owned();
+        self
+    }
+
+    /// Auctions with assets in `city`.
+    pub fn city(mut self, city: &str) -> Self {
+        self.city = city.to_owned();
+        self
+    }
+
+    /// Auctions with assets in `province`.
+    pub fn province(mut self, province: Province) -> Self {
+        self.province = Some(province);
+        self
+    }
+
+    /// Auctions ending between `from` and `to`, both included.
+    pub fn end_date(mut self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        self.end_date = (from, to);
+        self
+    }
+
+    /// Auctions starting between `from` and `to`, both included.
+    pub fn start_date(mut self, from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        self.start_date = (from, to);
+        self
+    }
+
+    /// Number of results for each page.
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
+    /// Sort results by the given form fields, e.g. `SUBASTA.FECHA_FIN`.
+    pub fn sort_by(mut self, sort: &[(&str, SortOrder)]) -> Self {
+        self.sort = sort
+            .iter()
+            .map(|(field, order)| (field.to_string(), *order))
+            .collect();
+        self
+    }
+
+    /// Urlencoded body to post to `MAIN_ALL_AUCTIONS_BOE_POST_URL`.
+    pub fn to_form(&self) -> String {
+        let province = self
+            .province
+            .and_then(|province| province.ine_code())
+            .unwrap_or_default();
+        let fields: [(Option<&str>, &str); 17] = [
+            (Some("SUBASTA.ORIGEN"), &self.origin),
+            (Some("SUBASTA.AUTORIDAD"), &self.authority),
+            (Some("SUBASTA.ESTADO.CODIGO"), &self.state_code),
+            (Some("BIEN.TIPO"), &self.asset_type),
+            (None, ""),
+            (Some("BIEN.DIRECCION"), &self.address),
+            (Some("BIEN.CODPOSTAL"), &self.postal_code),
+            (Some("BIEN.LOCALIDAD"), &self.city),
+            (Some("BIEN.COD_PROVINCIA"), province),
+            (Some("SUBASTA.POSTURA_MINIMA_MINIMA_LOTES"), ""),
+            (Some("SUBASTA.NUM_CUENTA_EXPEDIENTE_1"), ""),
+            (Some("SUBASTA.NUM_CUENTA_EXPEDIENTE_2"), ""),
+            (Some("SUBASTA.NUM_CUENTA_EXPEDIENTE_3"), ""),
+            (Some("SUBASTA.NUM_CUENTA_EXPEDIENTE_4"), ""),
+            (Some("SUBASTA.NUM_CUENTA_EXPEDIENTE_5"), ""),
+            (Some("SUBASTA.ID_SUBASTA_BUSCAR"), ""),
+            (Some("SUBASTA.ACREEDORES"), ""),
+        ];
+
+        let mut params: Vec<(String, String)> = Vec::new();
+        for (i, (field, value)) in fields.iter().enumerate() {
+            if let Some(field) = field {
+                params.push((format!("campo[{}]", i), field.to_string()));
+            }
+            params.push((format!("dato[{}]", i), value.to_string()));
+        }
+
+        let ranges = [
+            ("SUBASTA.FECHA_FIN", self.end_date),
+            ("SUBASTA.FECHA_INICIO", self.start_date),
+        ];
+        for (i, (field, (from, to))) in ranges.iter().enumerate() {
+            let i = i + fields.len();
+            params.push((format!("campo[{}]", i), field.to_string()));
+            params.push((format!("dato[{}][0]", i), format_search_date(*from)));
+            params.push((format!("dato[{}][1]", i), format_search_date(*to)));
+        }
+
+        params.push(("page_hits".to_owned(), self.page_size.to_string()));
+        for (i, (field, order)) in self.sort.iter().enumerate() {
+            params.push((format!("sort_field[{}]", i), field.clone()));
+            params.push((format!("sort_order[{}]", i), order.code().to_owned()));
+        }
+        params.push(("accion".to_owned(), "Buscar".to_owned()));
+
+        params
+            .iter()
+            .map(|(key, value)| format!("{}={}", urlencode(key), urlencode(value)))
+            .collect::<Vec<String>>()
+            .join("&")
+    }
+}
+
+impl Default for BoeSearchQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Name of the user agent used in http requests
 pub static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
 
+// Fixture file names longer than this are shortened and suffixed with a hash of the url.
+const MAX_FIXTURE_NAME_LEN: usize = 120;
+
 /// Http client to make requests to BOE website.
-pub trait HttpClient {
-    /// Get the content of the url
-    fn get_url(&self, target: &str) -> Result<String, Box<dyn std::error::Error>>;
+#[async_trait]
+pub trait HttpClient: Send + Sync {
+    /// Returns `target` web page content or return errors if unable.
+    async fn get_url(&self, target: &str) -> Result<String, Box<dyn std::error::Error>>;
+
+    /// Returns `target` web page content posting `body` or return errors if unable.
+    async fn post_url(
+        &self,
+        target: &str,
+        body: &str,
+    ) -> Result<String, Box<dyn std::error::Error>>;
+
+    /// Returns `target` raw content or return errors if unable or larger than `max_size` bytes.
+    async fn get_bytes(
+        &self,
+        target: &str,
+        max_size: usize,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>>;
+}
+
+fn check_size(
+    target: &str,
+    size: usize,
+    max_size: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if size > max_size {
+        Err(format!("{} is larger than {} bytes", target, max_size).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// How the response cache is used.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CacheMode {
+    /// Always make requests, nothing is stored.
+    Off,
+    /// Always make requests and store the responses.
+    Record,
+    /// Only serve stored responses, fail if a request was not recorded.
+    Replay,
+    /// Serve stored responses younger than the duration, request and store the rest.
+    RefreshAfter(Duration),
+}
+
+// Distinguishes temporary files of responses stored concurrently.
+static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+// Raw downloads are cached apart from pages, whose text is decoded from the response charset.
+const BYTES_CACHE_METHOD: &str = "GET_BYTES";
+
+/// On-disk responses cache addressed by the hash of method, url and body.
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    mode: CacheMode,
+}
+
+impl ResponseCache {
+    /// Create a cache storing responses under `dir` used as `mode` says.
+    pub fn new<P: AsRef<Path>>(dir: P, mode: CacheMode) -> Self {
+        ResponseCache {
+            dir: dir.as_ref().to_path_buf(),
+            mode,
+        }
+    }
+
+    /// Cache not storing nor serving any response.
+    pub fn off() -> Self {
+        Self::new("", CacheMode::Off)
+    }
+
+    /// Returns the cache mode.
+    pub fn mode(&self) -> CacheMode {
+        self.mode
+    }
+
+    /// Returns the file where the response of `method` request to `target` with `body` is stored.
+    pub fn path(&self, method: &str, target: &str, body: &str) -> PathBuf {
+        let digest = Sha256::new()
+            .chain_update(method)
+            .chain_update([0])
+            .chain_update(target)
+            .chain_update([0])
+            .chain_update(body)
+            .finalize();
+        let key: String = digest.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+        self.dir.join(&key[..2]).join(key)
+    }
+
+    /// Returns the stored response if the mode allows serving it.
+    pub fn lookup(
+        &self,
+        method: &str,
+        target: &str,
+        body: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        match self.lookup_bytes(method, target, body)? {
+            Some(response) => Ok(Some(String::from_utf8(response)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the stored raw response if the mode allows serving it.
+    pub fn lookup_bytes(
+        &self,
+        method: &str,
+        target: &str,
+        body: &str,
+    ) -> Result<Option<Vec<u8>>, Box<dyn std::error::Error>> {
+        let path = self.path(method, target, body);
+
+        match self.mode {
+            CacheMode::Off | CacheMode::Record => Ok(None),
+            CacheMode::Replay => std::fs::read(&path).map(Some).map_err(|error| {
+                format!("no recorded response for {} {}: {}", method, target, error).into()
+            }),
+            CacheMode::RefreshAfter(max_age) => {
+                let fresh = std::fs::metadata(&path)
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                    .is_some_and(|age| age < max_age);
+
+                if fresh {
+                    Ok(Some(std::fs::read(&path)?))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    /// Store `response` if the mode records responses.
+    pub fn store(
+        &self,
+        method: &str,
+        target: &str,
+        body: &str,
+        response: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.store_bytes(method, target, body, response.as_bytes())
+    }
+
+    /// Store raw `response` if the mode records responses.
+    pub fn store_bytes(
+        &self,
+        method: &str,
+        target: &str,
+        body: &str,
+        response: &[u8],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if let CacheMode::Record | CacheMode::RefreshAfter(_) = self.mode {
+            let path = self.path(method, target, body);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            // Write aside and rename, so readers never see a partially written response.
+            let mut temp_path = path.clone().into_os_string();
+            temp_path.push(format!(
+                ".{}-{}.tmp",
+                std::process::id(),
+                TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed)
+            ));
+            if let Err(error) = std::fs::write(&temp_path, response)
+                .and_then(|_| std::fs::rename(&temp_path, &path))
+            {
+                let _ = std::fs::remove_file(&temp_path);
+                return Err(error.into());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self::off()
+    }
 }
+
 /// HTTP client.
 #[derive(Debug)]
 pub struct UrlFetcher {
     client: ClientWithMiddleware,
+    cache: ResponseCache,
 }
 
 impl UrlFetcher {
@@ -56,32 +463,76 @@ impl UrlFetcher {
                 // Retry failed requests.
                 .with(RetryTransientMiddleware::new_with_policy(retry_policy))
                 .build(),
+            cache: ResponseCache::off(),
         }
     }
 
-    /// Returns `target` web page content or return errors if unable.
-    pub async fn get_url(&self, target: &str) -> Result<String, Box<dyn std::error::Error>> {
+    /// Use `cache` to record or replay responses.
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+}
+
+#[async_trait]
+impl HttpClient for UrlFetcher {
+    async fn get_url(&self, target: &str) -> Result<String, Box<dyn std::error::Error>> {
+        if let Some(cached) = self.cache.lookup("GET", target, "")? {
+            return Ok(cached);
+        }
+
         let response = self.client.get(target).send().await?;
         let body = response.error_for_status()?.text().await?;
+        self.cache.store("GET", target, "", &body)?;
         Ok(body)
     }
 
-    /// Returns `target` web page content or return errors if unable.
-    pub async fn post_url(
+    async fn post_url(
         &self,
         target: &str,
-        body: &'static str,
+        body: &str,
     ) -> Result<String, Box<dyn std::error::Error>> {
+        if let Some(cached) = self.cache.lookup("POST", target, body)? {
+            return Ok(cached);
+        }
+
         let response = self
             .client
             .post(target)
             .header("content-type", "application/x-www-form-urlencoded")
-            .body(body)
+            .body(body.to_owned())
             .send()
             .await?;
         let result = response.error_for_status()?.text().await?;
+        self.cache.store("POST", target, body, &result)?;
         Ok(result)
     }
+
+    async fn get_bytes(
+        &self,
+        target: &str,
+        max_size: usize,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        if let Some(cached) = self.cache.lookup_bytes(BYTES_CACHE_METHOD, target, "")? {
+            check_size(target, cached.len(), max_size)?;
+            return Ok(cached);
+        }
+
+        let mut response = self.client.get(target).send().await?.error_for_status()?;
+        if let Some(length) = response.content_length() {
+            check_size(target, length as usize, max_size)?;
+        }
+
+        let mut content = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            content.extend_from_slice(&chunk);
+            check_size(target, content.len(), max_size)?;
+        }
+
+        self.cache
+            .store_bytes(BYTES_CACHE_METHOD, target, "", &content)?;
+        Ok(content)
+    }
 }
 
 impl Default for UrlFetcher {
@@ -89,3 +540,242 @@ impl Default for UrlFetcher {
         Self::new()
     }
 }
+
+/// Http client recording the web pages fetched through another client.
+#[derive(Debug)]
+pub struct RecordingClient<'a, C> {
+    client: &'a C,
+    pages: Mutex<Vec<(String, String)>>,
+}
+
+impl<'a, C: HttpClient> RecordingClient<'a, C> {
+    /// Create a client fetching pages through `client`.
+    pub fn new(client: &'a C) -> Self {
+        RecordingClient {
+            client,
+            pages: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the url and content of the pages fetched.
+    pub fn into_pages(self) -> Vec<(String, String)> {
+        self.pages.into_inner().unwrap_or_default()
+    }
+
+    fn record(&self, target: &str, page: &str) {
+        if let Ok(mut pages) = self.pages.lock() {
+            pages.push((target.to_owned(), page.to_owned()));
+        }
+    }
+}
+
+#[async_trait]
+impl<C: HttpClient> HttpClient for RecordingClient<'_, C> {
+    async fn get_url(&self, target: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let page = self.client.get_url(target).await?;
+        self.record(target, &page);
+        Ok(page)
+    }
+
+    async fn post_url(
+        &self,
+        target: &str,
+        body: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let page = self.client.post_url(target, body).await?;
+        self.record(target, &page);
+        Ok(page)
+    }
+
+    async fn get_bytes(
+        &self,
+        target: &str,
+        max_size: usize,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        self.client.get_bytes(target, max_size).await
+    }
+}
+
+/// Returns the file name a fixture for `target` is saved under,
+/// `body` is appended as query for post requests.
+pub fn fixture_file_name(target: &str, body: Option<&str>) -> String {
+    let url = match body {
+        Some(body) => format!("{}?{}", target, body),
+        None => target.to_owned(),
+    };
+    let url = url
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+
+    let mut name: String = url
+        .chars()
+        .map(|x| match x {
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '.' | '-' => x,
+            _ => '_',
+        })
+        .collect();
+
+    if name.len() > MAX_FIXTURE_NAME_LEN {
+        // FNV-1a, stable across builds unlike the std hasher.
+        let hash = url.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3)
+        });
+        name.truncate(MAX_FIXTURE_NAME_LEN - 17);
+        name = format!("{}-{:016x}", name, hash);
+    }
+
+    name + ".html"
+}
+
+/// Http client serving pages saved in a fixtures directory, to scrape without network.
+#[derive(Debug, Clone)]
+pub struct FixtureClient {
+    dir: PathBuf,
+}
+
+impl FixtureClient {
+    /// Create a client serving the files in `dir` named after `fixture_file_name`.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        FixtureClient {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    fn read_fixture(&self, name: String) -> Result<String, Box<dyn std::error::Error>> {
+        let path = self.dir.join(name);
+
+        std::fs::read_to_string(&path)
+            .map_err(|error| format!("unable to read fixture {}: {}", path.display(), error).into())
+    }
+}
+
+#[async_trait]
+impl HttpClient for FixtureClient {
+    async fn get_url(&self, target: &str) -> Result<String, Box<dyn std::error::Error>> {
+        self.read_fixture(fixture_file_name(target, None))
+    }
+
+    async fn post_url(
+        &self,
+        target: &str,
+        body: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        self.read_fixture(fixture_file_name(target, Some(body)))
+    }
+
+    async fn get_bytes(
+        &self,
+        target: &str,
+        max_size: usize,
+    ) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        let path = self.dir.join(fixture_file_name(target, None));
+        let content = std::fs::read(&path)
+            .map_err(|error| format!("unable to read fixture {}: {}", path.display(), error))?;
+        check_size(target, content.len(), max_size)?;
+
+        Ok(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::TempPath;
+
+    #[test]
+    fn fixture_file_name_test() {
+        assert_eq!(
+            "subastas.boe.es_._detalleSubasta.php_idSub_SUB-NE-2020-465937.html",
+            fixture_file_name(
+                "https://subastas.boe.es/./detalleSubasta.php?idSub=SUB-NE-2020-465937",
+                None
+            )
+        );
+
+        let name = fixture_file_name(
+            &MAIN_ALL_AUCTIONS_BOE_POST_URL,
+            Some(&BoeSearchQuery::new().to_form()),
+        );
+        assert!(name.starts_with("subastas.boe.es_subastas_ava.php_campo_5B0_5D_SUBASTA.ORIGEN"));
+        assert_eq!(MAX_FIXTURE_NAME_LEN + ".html".len(), name.len());
+        assert_ne!(
+            name,
+            fixture_file_name(&MAIN_ALL_AUCTIONS_BOE_POST_URL, Some("accion=Buscar"))
+        );
+    }
+
+    #[test]
+    fn boe_search_query_test() {
+        assert_eq!(
+            "campo%5B0%5D=SUBASTA.ORIGEN&dato%5B0%5D=&campo%5B1%5D=SUBASTA.AUTORIDAD&dato%5B1%5D=&campo%5B2%5D=SUBASTA.ESTADO.CODIGO&dato%5B2%5D=&campo%5B3%5D=BIEN.TIPO&dato%5B3%5D=&dato%5B4%5D=&campo%5B5%5D=BIEN.DIRECCION&dato%5B5%5D=&campo%5B6%5D=BIEN.CODPOSTAL&dato%5B6%5D=&campo%5B7%5D=BIEN.LOCALIDAD&dato%5B7%5D=&campo%5B8%5D=BIEN.COD_PROVINCIA&dato%5B8%5D=&campo%5B9%5D=SUBASTA.POSTURA_MINIMA_MINIMA_LOTES&dato%5B9%5D=&campo%5B10%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_1&dato%5B10%5D=&campo%5B11%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_2&dato%5B11%5D=&campo%5B12%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_3&dato%5B12%5D=&campo%5B13%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_4&dato%5B13%5D=&campo%5B14%5D=SUBASTA.NUM_CUENTA_EXPEDIENTE_5&dato%5B14%5D=&campo%5B15%5D=SUBASTA.ID_SUBASTA_BUSCAR&dato%5B15%5D=&campo%5B16%5D=SUBASTA.ACREEDORES&dato%5B16%5D=&campo%5B17%5D=SUBASTA.FECHA_FIN&dato%5B17%5D%5B0%5D=&dato%5B17%5D%5B1%5D=&campo%5B18%5D=SUBASTA.FECHA_INICIO&dato%5B18%5D%5B0%5D=&dato%5B18%5D%5B1%5D=&page_hits=500&sort_field%5B0%5D=SUBASTA.FECHA_FIN&sort_order%5B0%5D=desc&sort_field%5B1%5D=SUBASTA.FECHA_FIN&sort_order%5B1%5D=asc&accion=Buscar",
+            BoeSearchQuery::new().to_form()
+        );
+
+        let form = BoeSearchQuery::new()
+            .province(Province::Madrid)
+            .city("ALCALÁ DE HENARES")
+            .end_date(
+                NaiveDate::from_ymd_opt(2022, 11, 7),
+                NaiveDate::from_ymd_opt(2022, 11, 13),
+            )
+            .sort_by(&[("SUBASTA.FECHA_FIN", SortOrder::Ascending)])
+            .to_form();
+        assert!(form.contains("campo%5B8%5D=BIEN.COD_PROVINCIA&dato%5B8%5D=28&"));
+        assert!(form.contains("dato%5B7%5D=ALCAL%C3%81+DE+HENARES&"));
+        assert!(form.contains("dato%5B17%5D%5B0%5D=2022-11-07&dato%5B17%5D%5B1%5D=2022-11-13&"));
+        assert!(form.ends_with(
+            "page_hits=500&sort_field%5B0%5D=SUBASTA.FECHA_FIN&sort_order%5B0%5D=asc&accion=Buscar"
+        ));
+    }
+
+    #[test]
+    fn response_cache_test() {
+        let dir = TempPath::new("cache-test");
+        let url = "https://subastas.boe.es/detalleSubasta.php?idSub=SUB-JA-2020-149474";
+
+        let record = ResponseCache::new(&dir, CacheMode::Record);
+        assert_ne!(record.path("GET", url, ""), record.path("POST", url, ""));
+        assert_eq!(None, record.lookup("GET", url, "").unwrap());
+        record.store("GET", url, "", "<html></html>").unwrap();
+        assert_eq!(None, record.lookup("GET", url, "").unwrap());
+        assert_eq!(
+            1,
+            std::fs::read_dir(record.path("GET", url, "").parent().unwrap())
+                .unwrap()
+                .count()
+        );
+
+        let replay = ResponseCache::new(&dir, CacheMode::Replay);
+        assert_eq!(
+            Some("<html></html>".to_owned()),
+            replay.lookup("GET", url, "").unwrap()
+        );
+        assert!(replay.lookup("POST", url, "accion=Buscar").is_err());
+
+        let fresh = ResponseCache::new(&dir, CacheMode::RefreshAfter(Duration::from_secs(3600)));
+        assert_eq!(
+            Some("<html></html>".to_owned()),
+            fresh.lookup("GET", url, "").unwrap()
+        );
+        let stale = ResponseCache::new(&dir, CacheMode::RefreshAfter(Duration::ZERO));
+        assert_eq!(None, stale.lookup("GET", url, "").unwrap());
+    }
+
+    #[tokio::test]
+    async fn url_fetcher_cached_bytes_test() {
+        let dir = TempPath::new("bytes-test");
+        let url = "https://subastas.boe.es/documentos/SUB-JA-2020-149474/edicto.pdf";
+
+        ResponseCache::new(&dir, CacheMode::Record)
+            .store_bytes(BYTES_CACHE_METHOD, url, "", b"%PDF-1.4 edicto")
+            .unwrap();
+
+        let fetcher = UrlFetcher::new().with_cache(ResponseCache::new(&dir, CacheMode::Replay));
+        assert_eq!(
+            b"%PDF-1.4 edicto".to_vec(),
+            fetcher.get_bytes(url, 1024).await.unwrap()
+        );
+        assert!(fetcher.get_bytes(url, 8).await.is_err());
+        assert!(fetcher.get_url(url).await.is_err());
+    }
+}
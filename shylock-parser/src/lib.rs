@@ -15,6 +15,10 @@
 #[macro_use]
 extern crate lazy_static;
 
+/// Module to store documents and photos attached to auctions.
+pub mod attachments;
+/// Module to detect changes of already stored auctions.
+pub mod changes;
 /// Module for accessing the auction local database.
 pub mod db;
 /// Module for solving address into coordinates.
@@ -31,6 +35,9 @@ pub mod scraper;
 /// Module with auxiliary functions.
 pub mod util;
 
+#[cfg(test)]
+mod test_util;
+
 pub use chrono::NaiveDate;
 pub use geo_types::Point;
 pub use rust_decimal::Decimal;
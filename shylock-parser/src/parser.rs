@@ -1,102 +1,192 @@
 use crate::http::BASE_BOE_URL;
 use geo_types::Point;
-use scraper::{Html, Selector};
+use scraper::{ElementRef, Html, Selector};
 use shylock_data::{concepts::BoeConcept, AuctionState};
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
+use std::error::Error;
+use std::fmt::Display;
 
 const RESULTS_PER_PAGE: u32 = 500;
 const AUCTION_STATE_STR: &str = "Estado: ";
+const ATTACHMENT_EXTENSIONS: [&str; 6] = [".pdf", ".jpg", ".jpeg", ".png", ".gif", ".webp"];
+const SNIPPET_LEN: usize = 120;
+
+/// Kind of page being parsed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PageKind {
+    /// Search results page listing auctions.
+    Results,
+    /// Main page of an auction.
+    Auction,
+    /// Management authority page of an auction.
+    Management,
+    /// Assets page of an auction.
+    Asset,
+    /// Lots page of an auction.
+    Lot,
+    /// Bids page of a concluded auction.
+    Bids,
+    /// Catastro web service response.
+    Catastro,
+    /// Link found in a BOE page.
+    Link,
+}
+
+impl Display for PageKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Results => write!(f, "results page"),
+            Self::Auction => write!(f, "auction page"),
+            Self::Management => write!(f, "management page"),
+            Self::Asset => write!(f, "asset page"),
+            Self::Lot => write!(f, "lot page"),
+            Self::Bids => write!(f, "bids page"),
+            Self::Catastro => write!(f, "catastro response"),
+            Self::Link => write!(f, "link"),
+        }
+    }
+}
+
+/// Error parsing a BOE page that doesn't have the expected layout.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseError {
+    /// Kind of page being parsed.
+    pub page: PageKind,
+    /// Selector, or link tag, that didn't match as expected.
+    pub selector: String,
+    /// What was wrong.
+    pub reason: String,
+    /// Beginning of the offending html or text.
+    pub snippet: String,
+}
+
+impl ParseError {
+    /// Create an error parsing a `page` with `selector`, `snippet` is shortened.
+    pub fn new(page: PageKind, selector: &str, reason: &str, snippet: &str) -> Self {
+        ParseError {
+            page,
+            selector: selector.to_owned(),
+            reason: reason.to_owned(),
+            snippet: snippet
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ")
+                .chars()
+                .take(SNIPPET_LEN)
+                .collect(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {} with {}: ->{}<-",
+            self.page, self.reason, self.selector, self.snippet
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+fn parse_selector(page: PageKind, selector: &str) -> Result<Selector, ParseError> {
+    Selector::parse(selector)
+        .map_err(|_| ParseError::new(page, selector, "invalid selector", selector))
+}
+
+fn select_first<'a>(
+    page: PageKind,
+    element: ElementRef<'a>,
+    selector: &str,
+) -> Result<ElementRef<'a>, ParseError> {
+    element
+        .select(&parse_selector(page, selector)?)
+        .next()
+        .ok_or_else(|| ParseError::new(page, selector, "no element found", &element.html()))
+}
 
 fn parse_html_table(
     page: &str,
-    data_selector: &Selector,
-) -> Result<HashMap<BoeConcept, String>, Box<dyn std::error::Error>> {
+    kind: PageKind,
+    data_selector: &str,
+    skip_unknown: bool,
+) -> Result<HashMap<BoeConcept, String>, ParseError> {
     let mut result: HashMap<BoeConcept, String> = HashMap::new();
 
     let doc = Html::parse_document(page);
-    let data = doc.select(data_selector).next().ok_or(format!(
-        "error parsing table, no container of table found with selector {:#?}",
-        data_selector
-    ))?;
-
-    let tr_selector = Selector::parse("tr").expect("tr selector creation failed");
-    let th_selector = Selector::parse("th").expect("th selector creation failed");
-    let td_selector = Selector::parse("td").expect("td selector creation failed");
+    let data = select_first(kind, doc.root_element(), data_selector)?;
+    let tr_selector = parse_selector(kind, "tr")?;
 
     for tr in data.select(&tr_selector) {
-        let th = tr
-            .select(&th_selector)
-            .next()
-            .ok_or("not found th")?
-            .text()
-            .collect::<String>();
-        let td = tr
-            .select(&td_selector)
-            .next()
-            .ok_or("not found td")?
-            .text()
-            .collect::<String>();
+        let th = select_first(kind, tr, "th")?.text().collect::<String>();
+        let td = select_first(kind, tr, "td")?.text().collect::<String>();
 
-        result.insert(
-            th.trim().to_uppercase().parse::<BoeConcept>()?,
-            td.trim().to_owned(),
-        );
+        match th.trim().to_uppercase().parse::<BoeConcept>() {
+            Ok(concept) => {
+                result.insert(concept, td.trim().to_owned());
+            }
+            Err(_) if skip_unknown => log::debug!("Skipping unknown concept: {}", th.trim()),
+            Err(error) => {
+                return Err(ParseError::new(
+                    kind,
+                    data_selector,
+                    &error.to_string(),
+                    &tr.html(),
+                ))
+            }
+        }
     }
 
     Ok(result)
 }
 
+// Parses the table of `page` with `data_selector` adding the first h4 header in it.
+fn parse_html_table_with_header(
+    page: &str,
+    kind: PageKind,
+    data_selector: &str,
+) -> Result<HashMap<BoeConcept, String>, ParseError> {
+    let mut result = parse_html_table(page, kind, data_selector, false)?;
+
+    let doc = Html::parse_document(page);
+    let data = select_first(kind, doc.root_element(), data_selector)?;
+    let header = select_first(kind, data, "h4")?;
+
+    result.insert(
+        BoeConcept::Header,
+        header.text().collect::<String>().trim().to_uppercase(),
+    );
+
+    Ok(result)
+}
+
 /// It parses a `page` containing the auction management information and
 /// returns the different boe concepts and values in a hashmap
 pub fn parse_management_auction_page(
     page: &str,
-) -> Result<HashMap<BoeConcept, String>, Box<dyn std::error::Error>> {
+) -> Result<HashMap<BoeConcept, String>, ParseError> {
     parse_html_table(
         page,
-        &Selector::parse(r#"div[id=idBloqueDatos2]"#)
-            .expect("div[id=idBloqueDatos2] selector creation failed"),
+        PageKind::Management,
+        r#"div[id=idBloqueDatos2]"#,
+        false,
     )
 }
 
 /// It parses a `page` containing the auction assets information and
 /// returns the different boe concepts and values in a hashmap
-pub fn parse_asset_auction_page(
-    page: &str,
-) -> Result<HashMap<BoeConcept, String>, Box<dyn std::error::Error>> {
-    let h4_selector = &Selector::parse("h4").expect("h4 selector creation failed");
-    let data_selector = &Selector::parse(r#"div[id^=idBloqueLote]"#)
-        .expect("div[id=^idBloqueLote selector creation failed");
-
-    let mut result = parse_html_table(page, data_selector)?;
-
-    let doc = Html::parse_document(page);
-    let data = doc.select(data_selector).next().ok_or("no div found")?;
-    let header = data
-        .select(h4_selector)
-        .next()
-        .ok_or("no header h4 found")?;
-
-    result.insert(
-        BoeConcept::Header,
-        header.text().collect::<String>().trim().to_uppercase(),
-    );
-
-    Ok(result)
+pub fn parse_asset_auction_page(page: &str) -> Result<HashMap<BoeConcept, String>, ParseError> {
+    parse_html_table_with_header(page, PageKind::Asset, r#"div[id^=idBloqueLote]"#)
 }
 
 /// It parses lot auction `page` and return the links for each lot or error.
-pub fn parse_lot_auction_page_links(page: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+pub fn parse_lot_auction_page_links(page: &str) -> Result<Vec<String>, ParseError> {
     let mut result = Vec::new();
 
     let doc = Html::parse_document(page);
-
-    let ul_selector =
-        Selector::parse("ul.navlistver").expect("ul.navlistver selector creation failed");
-    let a_selector = Selector::parse("a").expect("a selector creation failed");
-    let ul = doc
-        .select(&ul_selector)
-        .next()
-        .ok_or("not ul element found")?;
+    let ul = select_first(PageKind::Lot, doc.root_element(), "ul.navlistver")?;
+    let a_selector = parse_selector(PageKind::Lot, "a")?;
 
     for lot_anchor in ul.select(&a_selector) {
         if let Some(href) = lot_anchor.value().attr("href") {
@@ -111,58 +201,38 @@ pub fn parse_lot_auction_page_links(page: &str) -> Result<Vec<String>, Box<dyn s
 pub fn parse_lot_auction_page(
     page: &str,
     lot_id: &str,
-) -> Result<HashMap<BoeConcept, String>, Box<dyn std::error::Error>> {
+) -> Result<HashMap<BoeConcept, String>, ParseError> {
     log::debug!("Lot id: {}", lot_id);
-    let h4_selector = &Selector::parse("h4").expect("h4 selector creation failed");
-    let div_str_selector = format!(r#"div[id=idBloqueLote{}]"#, lot_id);
-    let data_selector = &Selector::parse(&div_str_selector).expect("div[id=idBloqueLoteX] failed");
-
-    let mut result = parse_html_table(page, data_selector)?;
-
-    let doc = Html::parse_document(page);
-    let data = doc
-        .select(data_selector)
-        .next()
-        .ok_or("no div[id=idBloqueLoteX] field found")?;
-    let header = data.select(h4_selector).next().ok_or("no h4 field found")?;
-
-    result.insert(
-        BoeConcept::Header,
-        header.text().collect::<String>().trim().to_uppercase(),
-    );
-
-    Ok(result)
+    parse_html_table_with_header(
+        page,
+        PageKind::Lot,
+        &format!(r#"div[id=idBloqueLote{}]"#, lot_id),
+    )
 }
 
 /// It parses main auction `page` returning the links for auction and management or error.
-pub fn parse_main_auction_links(
-    page: &str,
-) -> Result<(String, String), Box<dyn std::error::Error>> {
+pub fn parse_main_auction_links(page: &str) -> Result<(String, String), ParseError> {
     let doc = Html::parse_document(page);
-    let links_selector =
-        Selector::parse("ul.navlist").expect("ul.navlist selector creation failed");
-    let data = doc
-        .select(&links_selector)
-        .next()
-        .ok_or("unable to select ul.navlist links")?;
-    let link_selector = Selector::parse("a").expect("a selector creation failed");
-
-    let mut iterator = data.select(&link_selector);
-    iterator.next();
+    let data = select_first(PageKind::Auction, doc.root_element(), "ul.navlist")?;
+    let link_selector = parse_selector(PageKind::Auction, "a")?;
+
+    let mut hrefs = data
+        .select(&link_selector)
+        .skip(1)
+        .map(|anchor| anchor.value().attr("href"));
+    let mut next_link = |name: &str| {
+        hrefs.next().flatten().ok_or_else(|| {
+            ParseError::new(
+                PageKind::Auction,
+                "ul.navlist a",
+                &format!("no {} link href found", name),
+                &data.html(),
+            )
+        })
+    };
 
-    let management_link = iterator
-        .next()
-        .ok_or("error looking for a link")?
-        .value()
-        .attr("href")
-        .ok_or("error looking for href attribute")?;
-
-    let asset_link = iterator
-        .next()
-        .ok_or("error looking for a link")?
-        .value()
-        .attr("href")
-        .ok_or("error looking for href attribute")?;
+    let management_link = next_link("management")?;
+    let asset_link = next_link("asset")?;
 
     Ok((
         BASE_BOE_URL.to_owned() + management_link,
@@ -172,42 +242,98 @@ pub fn parse_main_auction_links(
 
 /// It parses a `page` containing the main auction information and
 /// returns the different boe concepts and values in a hashmap
-pub fn parse_main_auction_page(
+pub fn parse_main_auction_page(page: &str) -> Result<HashMap<BoeConcept, String>, ParseError> {
+    parse_html_table(page, PageKind::Auction, r#"div[id=idBloqueDatos1]"#, false)
+}
+
+/// It parses the bids `page` of a concluded auction, or of its `lot_id` lot,
+/// returning the outcome concepts in a hashmap.
+pub fn parse_bids_auction_page(
     page: &str,
-) -> Result<HashMap<BoeConcept, String>, Box<dyn std::error::Error>> {
-    parse_html_table(
-        page,
-        &Selector::parse(r#"div[id=idBloqueDatos1]"#)
-            .expect("div[id=idBloqueDatos1] selector creation failed"),
-    )
+    lot_id: Option<u32>,
+) -> Result<HashMap<BoeConcept, String>, ParseError> {
+    let data_selector = match lot_id {
+        Some(lot_id) => format!(r#"div[id=idBloqueLote{}]"#, lot_id),
+        None => r#"div[id^=idBloqueDatos]"#.to_owned(),
+    };
+
+    let mut result = parse_html_table(page, PageKind::Bids, &data_selector, true)?;
+
+    if let Entry::Vacant(entry) = result.entry(BoeConcept::Outcome) {
+        let doc = Html::parse_document(page);
+        let text = select_first(PageKind::Bids, doc.root_element(), &data_selector)?
+            .text()
+            .collect::<String>();
+        if let Some(outcome) = text
+            .lines()
+            .map(str::trim)
+            .find(|line| line.to_uppercase().contains("DESIERTA"))
+        {
+            entry.insert(outcome.to_owned());
+        }
+    }
+
+    Ok(result)
+}
+
+/// It parses an auction `page` looking for links to documents and photos,
+/// returning their absolute url and link text.
+pub fn parse_attachment_links(page: &str) -> Result<Vec<(String, String)>, ParseError> {
+    let mut result: Vec<(String, String)> = Vec::new();
+    let doc = Html::parse_document(page);
+    let link_selector = parse_selector(PageKind::Link, "a[href]")?;
+
+    for link in doc.select(&link_selector) {
+        let href = link.value().attr("href").unwrap_or_default().trim();
+        let title = link.text().collect::<String>().trim().to_owned();
+        let lowercase_href = href.to_lowercase();
+
+        let is_attachment = ATTACHMENT_EXTENSIONS
+            .iter()
+            .any(|extension| lowercase_href.ends_with(extension))
+            || title.to_uppercase().contains("FOTOGRAF");
+        if !is_attachment {
+            continue;
+        }
+
+        let url = if href.starts_with("http://") || href.starts_with("https://") {
+            href.to_owned()
+        } else {
+            BASE_BOE_URL.to_owned() + href
+        };
+        if !result.iter().any(|(known, _)| *known == url) {
+            result.push((url, title));
+        }
+    }
+
+    Ok(result)
 }
 
 /// It parses `main_page` to determine the total number of auctions pages, it returns their links.
-pub fn parse_extra_pages(main_page: &str) -> Vec<String> {
+pub fn parse_extra_pages(main_page: &str) -> Result<Vec<String>, ParseError> {
     let mut result = Vec::new();
     let doc = Html::parse_document(main_page);
-    let pages_number_p =
-        Selector::parse("div.paginar").expect("div.paginar selector creation failed");
-
-    let pages = if let Some(paragraph) = doc.select(&pages_number_p).next() {
-        let text = paragraph.text().collect::<String>();
-        let words = text.trim().split(' ');
-
-        let results = words
-            .last()
-            .expect("number")
-            .replace('.', "")
-            .parse::<u32>()
-            .unwrap();
-        (results / RESULTS_PER_PAGE) + 1
-    } else {
-        panic!("Unable to determine number of auctions pages");
-    };
 
-    let pages_div = Selector::parse("div.paginar2").expect("div.paginar2 selector creation failed");
+    let paragraph = select_first(PageKind::Results, doc.root_element(), "div.paginar")?;
+    let text = paragraph.text().collect::<String>();
+    let results = text
+        .split_whitespace()
+        .last()
+        .and_then(|count| count.replace('.', "").parse::<u32>().ok())
+        .ok_or_else(|| {
+            ParseError::new(
+                PageKind::Results,
+                "div.paginar",
+                "no results count found",
+                &text,
+            )
+        })?;
+    let pages = (results / RESULTS_PER_PAGE) + 1;
+
+    let pages_div = parse_selector(PageKind::Results, "div.paginar2")?;
 
     if let Some(div) = doc.select(&pages_div).next() {
-        let anchors_selector = Selector::parse("a").expect("a selector creation failed");
+        let anchors_selector = parse_selector(PageKind::Results, "a")?;
         if let Some(page_anchor) = div.select(&anchors_selector).next() {
             if let Some(href_tmp) = page_anchor.value().attr("href") {
                 let href_template = href_tmp
@@ -228,39 +354,46 @@ pub fn parse_extra_pages(main_page: &str) -> Vec<String> {
 
     result.pop();
 
-    result
+    Ok(result)
 }
 
-/// It parses auction result `page` to return a Vec with tuples with auctions links and state
-///
-/// #Panics
-///
-/// Panics if not found links or state in page for any result.
-pub fn parse_result_page(page: &str) -> Vec<(String, AuctionState)> {
+/// It parses auction result `page` to return a Vec with tuples with auctions links and state,
+/// or an error if any result has no link.
+pub fn parse_result_page(page: &str) -> Result<Vec<(String, AuctionState)>, ParseError> {
+    const AUCTION_ANCHOR: &str = "a.resultado-busqueda-link-otro";
     let mut result = Vec::new();
 
     let doc = Html::parse_document(page);
-
-    let li_results = Selector::parse("li.resultado-busqueda")
-        .expect("Didn't find class resultado-busqueda por li elements");
-    let auction_anchors = Selector::parse("a.resultado-busqueda-link-otro")
-        .expect("a.resultado-busueda-link-otro selector creation failed");
+    let li_results = parse_selector(PageKind::Results, "li.resultado-busqueda")?;
 
     for li_result in doc.select(&li_results) {
-        let auction_anchor = li_result
-            .select(&auction_anchors)
-            .next()
-            .expect("Unable to find auction link");
+        let auction_anchor = select_first(PageKind::Results, li_result, AUCTION_ANCHOR)?;
         let auction_link = match auction_anchor.value().attr("href") {
-            Some(href) => BASE_BOE_URL.to_owned() + href,
-            None => panic!("Not empty link auction allow"),
+            Some(href) if !href.trim().is_empty() => BASE_BOE_URL.to_owned() + href,
+            _ => {
+                return Err(ParseError::new(
+                    PageKind::Results,
+                    AUCTION_ANCHOR,
+                    "empty auction link",
+                    &auction_anchor.html(),
+                ))
+            }
         };
         let text = li_result.text().collect::<String>();
-        let auction_state = match &text[..].find(AUCTION_STATE_STR) {
+        let auction_state = match text.find(AUCTION_STATE_STR) {
             Some(index) => {
                 let begin = index + AUCTION_STATE_STR.len();
-                let end = begin + text[begin..].find(char::is_whitespace).unwrap();
-                text[begin..end].parse::<AuctionState>().unwrap()
+                let end = text[begin..]
+                    .find(char::is_whitespace)
+                    .map_or(text.len(), |end| begin + end);
+                text[begin..end].parse::<AuctionState>().map_err(|_| {
+                    ParseError::new(
+                        PageKind::Results,
+                        "li.resultado-busqueda",
+                        "invalid auction state",
+                        &text[index..],
+                    )
+                })?
             }
             None => AuctionState::Unknown,
         };
@@ -268,32 +401,61 @@ pub fn parse_result_page(page: &str) -> Vec<(String, AuctionState)> {
         result.push((auction_link, auction_state));
     }
 
-    result
+    Ok(result)
+}
+
+/// It parses the state shown in an auction `page`, "Estado: Suspendida - [reason]",
+/// returning it with the suspension reason if any, or `None` if the page shows no state.
+pub fn parse_auction_state(page: &str) -> Option<(AuctionState, Option<String>)> {
+    let doc = Html::parse_document(page);
+    let text = doc.root_element().text().collect::<String>();
+    let begin = text.find(AUCTION_STATE_STR)? + AUCTION_STATE_STR.len();
+    let line = text[begin..].lines().next().unwrap_or_default().trim();
+
+    let (state, detail) = match line.split_once(" - ") {
+        Some((state, detail)) => (state, Some(detail)),
+        None => (line, None),
+    };
+    let state = state.trim().parse::<AuctionState>().ok()?;
+    let reason = detail
+        .filter(|_| state == AuctionState::Suspended)
+        .map(|detail| {
+            detail
+                .trim()
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .trim()
+                .to_owned()
+        })
+        .filter(|reason| !reason.is_empty());
+
+    Some((state, reason))
+}
+
+fn parse_coordinate(doc: &Html, selector: &str) -> Result<f64, ParseError> {
+    let coordinate = select_first(PageKind::Catastro, doc.root_element(), selector)?
+        .text()
+        .collect::<String>();
+
+    coordinate.trim().parse::<f64>().map_err(|_| {
+        ParseError::new(
+            PageKind::Catastro,
+            selector,
+            "invalid coordinate",
+            &coordinate,
+        )
+    })
 }
 
 /// Parse `body` information to return catastro coordinates.
 pub fn parse_coordinates_from_catastro_cpmrc_response(
     body: &str,
-) -> Result<Option<Point<f64>>, Box<dyn std::error::Error>> {
+) -> Result<Option<Point<f64>>, ParseError> {
     let doc = Html::parse_document(body);
 
-    let xcen_selector = Selector::parse("xcen").expect("xcen selector creation failed");
-    let data = doc
-        .select(&xcen_selector)
-        .next()
-        .ok_or("no x coordinates found")?;
-    let x_coord = data.text().collect::<String>();
-
-    let ycen_selector = Selector::parse("ycen").expect("ycen selector creation failed");
-    let data = doc
-        .select(&ycen_selector)
-        .next()
-        .ok_or("no y coordinates found")?;
-    let y_coord = data.text().collect::<String>();
-
     Ok(Some(Point::new(
-        x_coord.parse::<f64>().unwrap(),
-        y_coord.parse::<f64>().unwrap(),
+        parse_coordinate(&doc, "xcen")?,
+        parse_coordinate(&doc, "ycen")?,
     )))
 }
 
@@ -301,24 +463,16 @@ pub fn parse_coordinates_from_catastro_cpmrc_response(
 pub fn parse_data_from_catastro_dnprc_response(
     body: &str,
     catastro_reference: &str,
-) -> Result<Option<String>, Box<dyn std::error::Error>> {
+) -> Result<Option<String>, ParseError> {
     let doc = Html::parse_document(body);
-    //
-
-    let urbrus_selector = Selector::parse("cn").expect("cn selector creation failed");
-    let data = doc
-        .select(&urbrus_selector)
-        .next()
-        .ok_or("no urbrus found")?;
-    let urbrus = data.text().collect::<String>();
-
-    let cp_selector = Selector::parse("cp").expect("cp selector creation failed");
-    let data = doc.select(&cp_selector).next().ok_or("no cp found")?;
-    let cp = data.text().collect::<String>();
+    let text = |selector: &str| {
+        select_first(PageKind::Catastro, doc.root_element(), selector)
+            .map(|data| data.text().collect::<String>())
+    };
 
-    let cmc_selector = Selector::parse("cmc").expect("cmc selector creation failed");
-    let data = doc.select(&cmc_selector).next().ok_or("no cmc found")?;
-    let cmc = data.text().collect::<String>();
+    let urbrus = text("cn")?;
+    let cp = text("cp")?;
+    let cmc = text("cmc")?;
 
     Ok(Some(format!(
         r#"https://www1.sedecatastro.gob.es/CYCBienInmueble/OVCConCiud.aspx?UrbRus={}&RefC={}&esBice=&RCBice1=&RCBice2=&DenoBice=&from=OVCBusqueda&pest=rc&RCCompleta={}&final=&del={}&mun={}"#,
@@ -330,6 +484,42 @@ pub fn parse_data_from_catastro_dnprc_response(
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_bids_auction_page_test() {
+        const INPUT: &str = r#"<div class="bloque" id="idBloqueDatos5">
+        <h3>Pujas</h3>
+        <table>
+          <tr>
+            <th>Resultado</th>
+            <td>Concluida con pujas</td>
+          </tr>
+          <tr>
+            <th>Puja m&#xE1;xima</th>
+            <td>60.101,60 €</td>
+          </tr>
+          <tr>
+            <th>N&#xFA;mero de pujas</th>
+            <td>7</td>
+          </tr>
+        </table>
+      </div>"#;
+        let result = parse_bids_auction_page(INPUT, None).unwrap();
+        assert_eq!(2, result.len());
+        assert_eq!("Concluida con pujas", result[&BoeConcept::Outcome]);
+        assert_eq!("60.101,60 €", result[&BoeConcept::HighestBid]);
+
+        const DESERTED: &str = r#"<div class="bloque" id="idBloqueLote2">
+        <h3>Pujas del lote 2</h3>
+        <p>
+          Subasta desierta
+        </p>
+        <table></table>
+      </div>"#;
+        let result = parse_bids_auction_page(DESERTED, Some(2)).unwrap();
+        assert_eq!("Subasta desierta", result[&BoeConcept::Outcome]);
+        assert!(parse_bids_auction_page(DESERTED, Some(1)).is_err());
+    }
+
     #[test]
     fn parse_lot_auction_page_links_test() {
         const INPUT: &str = r#"<div id="cont-tabs">
@@ -453,6 +643,41 @@ mod tests {
         assert_eq!(lot, parse_lot_auction_page(INPUT, "2").unwrap());
     }
 
+    #[test]
+    fn parse_attachment_links_test() {
+        const INPUT: &str = r#"<div class="bloque" id="idBloqueLote1">
+        <p><a href="./detalleSubasta.php?idSub=SUB-NE-2020-465937&amp;ver=1">Información general</a></p>
+        <ul>
+          <li><a href="./documentos/SUB-NE-2020-465937/edicto.pdf">Edicto de subasta</a></li>
+          <li><a href="https://subastas.boe.es/documentos/SUB-NE-2020-465937/edicto.pdf">Edicto</a></li>
+          <li><a href="./imagenes/SUB-NE-2020-465937/1.JPG">Foto 1</a></li>
+          <li><a href="./detalleSubasta.php?idSub=SUB-NE-2020-465937&amp;ver=4">VER FOTOGRAFÍAS ANEXAS</a></li>
+        </ul>
+        </div>"#;
+
+        assert_eq!(
+            vec![
+                (
+                    BASE_BOE_URL.to_owned() + "./documentos/SUB-NE-2020-465937/edicto.pdf",
+                    "Edicto de subasta".to_owned()
+                ),
+                (
+                    "https://subastas.boe.es/documentos/SUB-NE-2020-465937/edicto.pdf".to_owned(),
+                    "Edicto".to_owned()
+                ),
+                (
+                    BASE_BOE_URL.to_owned() + "./imagenes/SUB-NE-2020-465937/1.JPG",
+                    "Foto 1".to_owned()
+                ),
+                (
+                    BASE_BOE_URL.to_owned() + "./detalleSubasta.php?idSub=SUB-NE-2020-465937&ver=4",
+                    "VER FOTOGRAFÍAS ANEXAS".to_owned()
+                ),
+            ],
+            parse_attachment_links(INPUT).unwrap()
+        );
+    }
+
     #[test]
     fn parse_main_auction_links_test() {
         const INPUT: &str = r#"<div id="tabs">
@@ -534,7 +759,7 @@ mod tests {
       </li>
     </ul>
   </div></body>"##;
-        let pages = parse_extra_pages(INPUT);
+        let pages = parse_extra_pages(INPUT).unwrap();
         assert_eq!(3, pages.len());
         assert_eq!(&"https://subastas.boe.es/subastas_ava.php?accion=Mas&id_busqueda=_YjU3T1REVDZIbFlRRXkwMGhrRC9PZlorZ3RmRDVXL210ZXN4QU1aVWxpL2RjNDVLQldmR2tETFZNWnpmOUcxdXE4a2NBWnhtZ1NHWGxiVGxUdG1mQm1yKzArdk1nOW1IWEs0WTU4VTJnV01iZ1huaEVhSFVqbHplTkp4Nm5DV1RtMFVocDNiYThvbWZ4a1FYcm9lWDJCNFM4bUVHUnRKVWxDdmF5bXZSUVNFY3lGTytyQTlKMFBLUjNVejdVbUU1aW95ZTV3Q2RRbW5kOERKNkpZMDkwY3VkcVhoa3FhWERudXpuc0tSdXVaOTlZNTVwU1F6aWYrbmpWSmVBZERJUg,,-500-500",
                    pages.get(0).unwrap());
@@ -864,7 +1089,97 @@ mod tests {
             (BASE_BOE_URL.to_owned() + "./detalleSubasta.php?idSub=SUB-JA-2020-149625&idBus=_SGFOTnU2NVlnSUwvd2czQzBFcHdoUDFlZTZGS1pLT1lwNm5pbmNIdmNGTXpLNUpZcXNGRElabzlLSGdEckkwL1NuQmpKT3lSd3Z2QTJiM0dPTURUNXBYOEhSNzhqRG5CdExSSXFxZkZSM1phdTh2bkIwUjRXaWFwdkJ2ZzNmVmV0NWc5NjJpU2FDdHQ1amc1SHJSUmhGTGFSTkk4dlFkSWYwTXA5ckFaRUh2TWtkcjM4UmFVY3VCa1JOcklEdWFDdFZpcC81Z0I4UVVYRDdqQjhLeW9RZ2R3aHpOMzRXY1cyZWJwZWRKSXY2RkRHRndmL2JIUXFQckVHdVYzUEh6VA,,", AuctionState::Ongoing),
             (BASE_BOE_URL.to_owned() + "./detalleSubasta.php?idSub=SUB-AT-2020-20R4186001070&idBus=_SGFOTnU2NVlnSUwvd2czQzBFcHdoUDFlZTZGS1pLT1lwNm5pbmNIdmNGTXpLNUpZcXNGRElabzlLSGdEckkwL1NuQmpKT3lSd3Z2QTJiM0dPTURUNXBYOEhSNzhqRG5CdExSSXFxZkZSM1phdTh2bkIwUjRXaWFwdkJ2ZzNmVmV0NWc5NjJpU2FDdHQ1amc1SHJSUmhGTGFSTkk4dlFkSWYwTXA5ckFaRUh2TWtkcjM4UmFVY3VCa1JOcklEdWFDdFZpcC81Z0I4UVVYRDdqQjhLeW9RZ2R3aHpOMzRXY1cyZWJwZWRKSXY2RkRHRndmL2JIUXFQckVHdVYzUEh6VA,,", AuctionState::Ongoing)];
 
-        assert_eq!(links, parse_result_page(INPUT));
+        assert_eq!(links, parse_result_page(INPUT).unwrap());
+    }
+
+    #[test]
+    fn parse_malformed_pages_test() {
+        let error = parse_extra_pages("<body><p>Sin resultados</p></body>").unwrap_err();
+        assert_eq!(PageKind::Results, error.page);
+        assert_eq!("div.paginar", error.selector);
+
+        let error =
+            parse_extra_pages(r#"<div class="paginar"><p>Resultados</p></div>"#).unwrap_err();
+        assert_eq!("no results count found", error.reason);
+        assert_eq!("Resultados", error.snippet);
+
+        let error = parse_result_page(
+            r#"<ul><li class="resultado-busqueda"><h3>SUBASTA SUB-JA-2020-146153</h3></li></ul>"#,
+        )
+        .unwrap_err();
+        assert_eq!("a.resultado-busqueda-link-otro", error.selector);
+        assert!(error.snippet.contains("SUB-JA-2020-146153"));
+
+        let error = parse_result_page(
+            r#"<ul><li class="resultado-busqueda">
+            <a class="resultado-busqueda-link-otro" href="">Más...</a></li></ul>"#,
+        )
+        .unwrap_err();
+        assert_eq!("empty auction link", error.reason);
+
+        assert_eq!(
+            vec![(
+                BASE_BOE_URL.to_owned() + "./detalleSubasta.php?idSub=SUB-1",
+                AuctionState::Unknown
+            )],
+            parse_result_page(
+                r#"<ul><li class="resultado-busqueda"><p>Estado: Desconocido</p>
+            <a class="resultado-busqueda-link-otro" href="./detalleSubasta.php?idSub=SUB-1">Más...</a></li></ul>"#,
+            )
+            .unwrap()
+        );
+
+        let error = parse_main_auction_links(
+            r#"<ul class="navlist"><li><a href="./1">Información general</a></li>
+            <li><a>Autoridad gestora</a></li></ul>"#,
+        )
+        .unwrap_err();
+        assert_eq!(PageKind::Auction, error.page);
+        assert_eq!("no management link href found", error.reason);
+
+        let error = parse_main_auction_page(
+            r#"<div id="idBloqueDatos1"><table><tr><th>Color</th><td>Rojo</td></tr></table></div>"#,
+        )
+        .unwrap_err();
+        assert_eq!("div[id=idBloqueDatos1]", error.selector);
+        assert!(error.snippet.contains("Color"));
+
+        let error = parse_lot_auction_page(r#"<div id="idBloqueLote1"></div>"#, "1").unwrap_err();
+        assert_eq!(PageKind::Lot, error.page);
+        assert_eq!("h4", error.selector);
+
+        let error = parse_coordinates_from_catastro_cpmrc_response(
+            "<geo><xcen>1.5</xcen><ycen>N/A</ycen></geo>",
+        )
+        .unwrap_err();
+        assert_eq!(
+            "catastro response: invalid coordinate with ycen: ->N/A<-",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn parse_auction_state_test() {
+        assert_eq!(
+            Some((AuctionState::Ongoing, None)),
+            parse_auction_state(
+                r#"<div class="caja">Estado: Celebrándose - [Conclusión prevista: 03/08/2020 a las 18:00:00]</div>"#
+            )
+        );
+        assert_eq!(
+            Some((
+                AuctionState::Suspended,
+                Some("Suspendida por resolución judicial".to_owned())
+            )),
+            parse_auction_state(
+                r#"<div class="caja">Estado: Suspendida - [Suspendida por resolución judicial]</div>"#
+            )
+        );
+        assert_eq!(
+            Some((AuctionState::Suspended, None)),
+            parse_auction_state(r#"<p>Estado: Suspendida</p>"#)
+        );
+        assert_eq!(None, parse_auction_state("<p>Subasta SUB-1</p>"));
     }
 
     #[test]
@@ -1,19 +1,58 @@
-use crate::db::DbClient;
-use crate::http::UrlFetcher;
+use crate::attachments::{
+    content_type, extract_pdf_text_blocking, AttachmentStore, HTML_CONTENT_TYPE,
+};
+use crate::changes::{carry_over_assets, diff_assets, diff_auction};
+use crate::db::{DbClient, FailedAuction, ScrapeStatus};
+use crate::http::{
+    BoeSearchQuery, HttpClient, RecordingClient, MAIN_ALL_AUCTIONS_BOE_POST_URL,
+    ONE_AUCTION_BOE_URL,
+};
 use crate::parser::*;
 use crate::util::{extract_auction_id_from_link, extract_auction_lot_number_from_link};
-use shylock_data::types::{Asset, Auction, LotAuctionKind, Management};
-use shylock_data::AuctionState;
+use futures::{future, stream, StreamExt};
+use shylock_data::types::{Asset, Auction, DataError, Diagnostics, LotAuctionKind, Management};
+use shylock_data::{
+    Attachment, AttachmentKind, AuctionOutcome, AuctionState, DateTime, FixedOffset,
+};
+use std::collections::HashSet;
+use std::time::Duration;
 
 /// Default country to solve geographic information.
 pub const DEFAULT_COUNTRY: &str = "Spain";
 
-/// Retrieve an auction information from an auction link.
-pub async fn process_auction_link(
-    url_fetcher: &UrlFetcher,
+/// Name of the incremental scrape in the scrape state.
+pub const INCREMENTAL_SCRAPE: &str = "incremental";
+
+/// Name of the init scrape runs.
+pub const INIT_SCRAPE: &str = "init";
+
+/// Search auctions started since `last_scrape` minus the `overlap` window,
+/// all auctions if there was no previous scrape.
+///
+/// The BOE search can't filter by publication date, so stored ongoing
+/// auctions are checked again for changes in the incremental run, but an
+/// auction published after it started and before this window is still
+/// missed until the next init scrape.
+pub fn incremental_search_query(
+    last_scrape: Option<DateTime<FixedOffset>>,
+    overlap: Duration,
+) -> BoeSearchQuery {
+    let overlap = chrono::Duration::from_std(overlap).unwrap_or_else(|_| chrono::Duration::zero());
+
+    match last_scrape.and_then(|last_scrape| last_scrape.checked_sub_signed(overlap)) {
+        Some(since) => BoeSearchQuery::new().start_date(Some(since.date_naive()), None),
+        None => BoeSearchQuery::new(),
+    }
+}
+
+/// Retrieve an auction information from an auction link,
+/// returning also the malformed data found.
+pub async fn process_auction_link<C: HttpClient>(
+    url_fetcher: &C,
     link: &(String, AuctionState),
-) -> Result<(Auction, Vec<Asset>), Box<dyn std::error::Error>> {
+) -> Result<(Auction, Vec<Asset>, Vec<DataError>), Box<dyn std::error::Error>> {
     let mut assets = Vec::new();
+    let mut diagnostics = Diagnostics::lenient();
 
     let auction_page = url_fetcher.get_url(&link.0).await?;
 
@@ -22,14 +61,28 @@ pub async fn process_auction_link(
     let management = Management::new(&parse_management_auction_page(&management_page)?);
     log::info!("Created management: {}", management.code);
 
-    let auction = Auction::new(&parse_main_auction_page(&auction_page)?, management, link.1);
+    // The auction page shows the current state, the link one may be outdated.
+    let (auction_state, suspension_reason) = parse_auction_state(&auction_page)
+        .filter(|(state, _)| *state != AuctionState::Unknown)
+        .unwrap_or((link.1, None));
+    let mut auction = Auction::try_new(
+        &parse_main_auction_page(&auction_page)?,
+        management,
+        auction_state,
+        &mut diagnostics,
+    )?;
+    auction.suspension_reason = suspension_reason;
     log::info!("Created auction: {}", auction.id);
 
     let asset_page = url_fetcher.get_url(&asset_link).await?;
     match auction.lot_kind {
         LotAuctionKind::NotApplicable => {
             log::info!("Parsing auction without lots link");
-            let asset = Asset::new(&auction.id, &parse_asset_auction_page(&asset_page)?);
+            let asset = Asset::try_new(
+                &auction.id,
+                &parse_asset_auction_page(&asset_page)?,
+                &mut diagnostics,
+            )?;
 
             assets.push(asset);
         }
@@ -41,20 +94,101 @@ pub async fn process_auction_link(
 
                 let lot_id = extract_auction_lot_number_from_link(lot_link)?;
 
-                let asset = Asset::new(&auction.id, &parse_lot_auction_page(&lot_page, lot_id)?);
+                let asset = Asset::try_new(
+                    &auction.id,
+                    &parse_lot_auction_page(&lot_page, lot_id)?,
+                    &mut diagnostics,
+                )?
+                .with_lot(Some(lot_id.parse::<u32>()?));
                 assets.push(asset);
             }
         }
     }
 
-    Ok((auction, assets))
+    Ok((auction, assets, diagnostics.into_warnings()))
+}
+
+/// Retrieve and store the auction of `auction_link`, if it fails the error and
+/// the pages fetched are stored as failed auction to be retried.
+pub async fn auction_link_scraper<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    auction_link: &(String, AuctionState),
+) -> Result<(), Box<dyn std::error::Error>> {
+    let auction_id = extract_auction_id_from_link(&auction_link.0)?;
+    let recorder = RecordingClient::new(http_client);
+
+    match process_auction_link(&recorder, auction_link).await {
+        Ok((auction, auction_assets, auction_diagnostics)) => {
+            let tx = db_client.pool.begin().await?;
+
+            db_client.insert_management(&auction.management).await;
+
+            db_client.insert_auction(&auction).await;
+
+            db_client.insert_assets(&auction, &auction_assets).await;
+
+            db_client.insert_diagnostics(&auction_diagnostics).await;
+
+            tx.commit().await?;
+
+            db_client.delete_failed_auction(auction_id).await?;
+
+            Ok(())
+        }
+        Err(error) => {
+            db_client
+                .record_failed_auction(
+                    auction_id,
+                    auction_link,
+                    &error.to_string(),
+                    &recorder.into_pages(),
+                )
+                .await?;
+
+            Err(error)
+        }
+    }
+}
+
+/// Retry the `failed` auction from its stored link, returns an error if it fails again.
+pub async fn failed_auction_scraper<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    failed: &FailedAuction,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if db_client.auction_exists(&failed.auction_id).await? {
+        return db_client.delete_failed_auction(&failed.auction_id).await;
+    }
+
+    auction_link_scraper(http_client, db_client, &failed.link).await
 }
 
 /// Scrape all links of a page.
-pub async fn page_scraper(
-    http_client: &UrlFetcher,
+pub async fn page_scraper<C: HttpClient>(
+    http_client: &C,
     db_client: &DbClient,
     result_page_url: &str,
+) -> Result<(u32, u32, u32), Box<dyn std::error::Error>> {
+    result_page_scraper(http_client, db_client, result_page_url, None).await
+}
+
+// Scrape run and result page where the status of the links is recorded.
+#[derive(Clone, Copy)]
+struct Checkpoint<'a> {
+    run_id: i64,
+    page: u32,
+    // Auctions already done in the scrape run.
+    done_links: &'a HashSet<String>,
+}
+
+// Scrape all links of a result page recording their status in `checkpoint`
+// scrape run and page, if any.
+async fn result_page_scraper<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    result_page_url: &str,
+    checkpoint: Option<Checkpoint<'_>>,
 ) -> Result<(u32, u32, u32), Box<dyn std::error::Error>> {
     let mut auction_ok: u32 = 0;
     let mut auction_err: u32 = 0;
@@ -62,32 +196,56 @@ pub async fn page_scraper(
 
     log::info!("page url to process: {}", result_page_url);
     let result_page = http_client.get_url(result_page_url).await?;
-    let auction_links = parse_result_page(&result_page);
+    let auction_links = parse_result_page(&result_page)?;
     log::info!("processing {} links", auction_links.len());
     let number_auctions = auction_links.len();
 
     for auction_link in auction_links {
         let auction_id = extract_auction_id_from_link(&auction_link.0)?;
 
-        if let Ok(true) = db_client.auction_exists(auction_id).await {
-            log::info!("Auction ->{}<- previously processed", auction_id);
+        if checkpoint.is_some_and(|checkpoint| checkpoint.done_links.contains(auction_id)) {
+            log::info!("Auction ->{}<- already done in scrape run", auction_id);
             auction_already_process += 1;
             continue;
         }
-        match process_auction_link(http_client, &auction_link).await {
-            Ok((auction, auction_assets)) => {
-                let tx = db_client.pool.begin().await?;
-
-                db_client.insert_management(&auction.management).await;
-
-                db_client.insert_auction(&auction).await;
 
-                db_client.insert_assets(&auction, &auction_assets).await;
-
-                tx.commit().await?;
-
-                auction_ok += 1;
+        if let Ok(true) = db_client.auction_exists(auction_id).await {
+            log::info!("Auction ->{}<- previously processed", auction_id);
+            auction_already_process += 1;
+            if let Some(checkpoint) = checkpoint {
+                db_client
+                    .set_scrape_link(
+                        checkpoint.run_id,
+                        checkpoint.page,
+                        auction_id,
+                        &auction_link.0,
+                        ScrapeStatus::Done,
+                        None,
+                    )
+                    .await?;
             }
+            continue;
+        }
+        let result = auction_link_scraper(http_client, db_client, &auction_link).await;
+        if let Some(checkpoint) = checkpoint {
+            let error = result.as_ref().err().map(|error| error.to_string());
+            db_client
+                .set_scrape_link(
+                    checkpoint.run_id,
+                    checkpoint.page,
+                    auction_id,
+                    &auction_link.0,
+                    if error.is_some() {
+                        ScrapeStatus::Failed
+                    } else {
+                        ScrapeStatus::Done
+                    },
+                    error.as_deref(),
+                )
+                .await?;
+        }
+        match result {
+            Ok(()) => auction_ok += 1,
             Err(err) => {
                 auction_err += 1;
                 log::warn!("Unable to process: {}", err)
@@ -105,9 +263,322 @@ pub async fn page_scraper(
     Ok((auction_ok, auction_err, auction_already_process))
 }
 
+/// Scrape the result pages of scrape run `run_id`, `concurrency` at once, skipping
+/// the auction links already done and checkpointing every page and auction link,
+/// returns the number of failed pages.
+pub async fn scrape_run_scraper<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    run_id: i64,
+    concurrency: usize,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let run = db_client
+        .get_scrape_run(run_id)
+        .await?
+        .ok_or_else(|| format!("scrape run {} not found", run_id))?;
+
+    // Result page urls belong to the search session, search again to get valid ones.
+    let main_page = http_client
+        .post_url(&MAIN_ALL_AUCTIONS_BOE_POST_URL, &run.query)
+        .await?;
+    let mut pages_url = parse_extra_pages(&main_page)?;
+    pages_url.insert(0, MAIN_ALL_AUCTIONS_BOE_POST_URL.to_string());
+
+    // New auctions shift the results between searches, so every page is scanned
+    // again and only the links are skipped.
+    let done_links: HashSet<String> = db_client
+        .get_scrape_links(run_id)
+        .await?
+        .into_iter()
+        .filter(|(_, status)| *status == ScrapeStatus::Done)
+        .map(|(auction_id, _)| auction_id)
+        .collect();
+    let mut pending_pages = Vec::new();
+    for (page, url) in pages_url.iter().enumerate() {
+        let page = page as u32;
+        db_client
+            .set_scrape_page(run_id, page, url, ScrapeStatus::Pending, None)
+            .await?;
+        pending_pages.push((page, url));
+    }
+    log::info!(
+        "Scrape run {}: {} pages to process, {} auctions already done",
+        run_id,
+        pending_pages.len(),
+        done_links.len()
+    );
+
+    let done_links = &done_links;
+    let failed_pages = stream::iter(pending_pages)
+        .map(|(page, url)| async move {
+            let checkpoint = Checkpoint {
+                run_id,
+                page,
+                done_links,
+            };
+            let (status, error) =
+                match result_page_scraper(http_client, db_client, url, Some(checkpoint)).await {
+                    Ok((ok, 0, already_processed)) => {
+                        log::info!(
+                            "Page {} ended succesfully ok {}/previously processed {}",
+                            page,
+                            ok,
+                            already_processed
+                        );
+                        (ScrapeStatus::Done, None)
+                    }
+                    Ok((_, err, _)) => (
+                        ScrapeStatus::Failed,
+                        Some(format!("{} auctions failed", err)),
+                    ),
+                    Err(error) => (ScrapeStatus::Failed, Some(error.to_string())),
+                };
+            if let Some(error) = &error {
+                log::warn!("Page {} failed: {}", page, error);
+            }
+
+            match db_client
+                .set_scrape_page(run_id, page, url, status, error.as_deref())
+                .await
+            {
+                Ok(()) => status,
+                Err(error) => {
+                    log::warn!("Unable to checkpoint page {}: {}", page, error);
+                    ScrapeStatus::Failed
+                }
+            }
+        })
+        .buffer_unordered(concurrency)
+        .filter(|status| future::ready(*status != ScrapeStatus::Done))
+        .count()
+        .await;
+
+    db_client
+        .finish_scrape_run(
+            run_id,
+            if failed_pages == 0 {
+                ScrapeStatus::Done
+            } else {
+                ScrapeStatus::Failed
+            },
+        )
+        .await?;
+
+    Ok(failed_pages)
+}
+
+/// Fetch again a stored `auction` with its `assets`, updating in db the fields
+/// changed since it was stored and recording them, returns the number of changes.
+pub async fn auction_changes_scraper<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    auction: &Auction,
+    assets: &[Asset],
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let link = (
+        format!("{}{}", *ONE_AUCTION_BOE_URL, auction.id),
+        auction.auction_state,
+    );
+    let (fresh_auction, mut fresh_assets, _) = process_auction_link(http_client, &link).await?;
+    carry_over_assets(assets, &mut fresh_assets);
+
+    let mut changes = diff_auction(auction, &fresh_auction);
+    changes.extend(diff_assets(&auction.id, assets, &fresh_assets));
+    if changes.is_empty() {
+        return Ok(0);
+    }
+
+    db_client
+        .apply_auction_changes(auction, assets, &fresh_auction, &fresh_assets, &changes)
+        .await?;
+
+    Ok(changes.len())
+}
+
+async fn save_attachment(
+    db_client: &DbClient,
+    store: &AttachmentStore,
+    auction_id: &str,
+    lot: Option<u32>,
+    (url, title): (&str, &str),
+    content: &[u8],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let attachment = Attachment {
+        kind: AttachmentKind::from_link(url, title),
+        title: title.to_owned(),
+        source_url: url.to_owned(),
+        hash: store.store(content)?,
+        content_type: content_type(content).to_owned(),
+        size: content.len() as u64,
+    };
+    log::info!(
+        "Stored attachment {} of auction {}: {}",
+        attachment.hash,
+        auction_id,
+        url
+    );
+
+    let text = extract_pdf_text_blocking(content.to_vec()).await;
+    db_client
+        .insert_attachment(auction_id, lot, &attachment, text.as_deref())
+        .await
+}
+
+// Download and store the attachment linked from `url` with `title`, html pages
+// like photo galleries are not stored but the attachments they link are.
+async fn store_attachment<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    store: &AttachmentStore,
+    auction_id: &str,
+    lot: Option<u32>,
+    (url, title): (&str, &str),
+) -> Result<usize, Box<dyn std::error::Error>> {
+    if db_client.attachment_exists(auction_id, url).await? {
+        return Ok(0);
+    }
+
+    let content = http_client.get_bytes(url, store.max_size()).await?;
+    if content_type(&content) != HTML_CONTENT_TYPE {
+        save_attachment(db_client, store, auction_id, lot, (url, title), &content).await?;
+        return Ok(1);
+    }
+
+    let mut stored = 0;
+    for (link, link_title) in parse_attachment_links(&String::from_utf8_lossy(&content))? {
+        if db_client.attachment_exists(auction_id, &link).await? {
+            continue;
+        }
+
+        let link_title = if link_title.is_empty() {
+            title
+        } else {
+            &link_title
+        };
+        match http_client.get_bytes(&link, store.max_size()).await {
+            Ok(content) if content_type(&content) != HTML_CONTENT_TYPE => {
+                save_attachment(
+                    db_client,
+                    store,
+                    auction_id,
+                    lot,
+                    (&link, link_title),
+                    &content,
+                )
+                .await?;
+                stored += 1;
+            }
+            Ok(_) => log::debug!("Skipping linked page {}", link),
+            Err(error) => log::warn!("Unable to download attachment {}: {}", link, error),
+        }
+    }
+
+    Ok(stored)
+}
+
+/// Download into `store` the documents and photos linked from `auction` pages
+/// not stored yet, returns the number of attachments stored.
+pub async fn auction_attachments_scraper<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    store: &AttachmentStore,
+    auction: &Auction,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let auction_page = http_client
+        .get_url(&format!("{}{}", *ONE_AUCTION_BOE_URL, auction.id))
+        .await?;
+    let (_, asset_link) = parse_main_auction_links(&auction_page)?;
+    let asset_page = http_client.get_url(&asset_link).await?;
+
+    let mut pages = vec![(None, auction_page)];
+    match auction.lot_kind {
+        LotAuctionKind::NotApplicable => pages.push((None, asset_page)),
+        LotAuctionKind::Joined | LotAuctionKind::Splitted => {
+            for lot_link in parse_lot_auction_page_links(&asset_page)?.iter() {
+                let lot_id = extract_auction_lot_number_from_link(lot_link)?;
+                pages.push((
+                    Some(lot_id.parse::<u32>()?),
+                    http_client.get_url(lot_link).await?,
+                ));
+            }
+        }
+    }
+
+    let mut stored = 0;
+    for (lot, page) in pages.iter() {
+        for (url, title) in parse_attachment_links(page)? {
+            match store_attachment(
+                http_client,
+                db_client,
+                store,
+                &auction.id,
+                *lot,
+                (&url, &title),
+            )
+            .await
+            {
+                Ok(count) => stored += count,
+                Err(error) => log::warn!("Unable to store attachment {}: {}", url, error),
+            }
+        }
+    }
+
+    Ok(stored)
+}
+
+fn bids_link(auction_id: &str, lot: Option<u32>) -> String {
+    match lot {
+        Some(lot) => format!(
+            "{}{}&ver=5&idLote={}",
+            *ONE_AUCTION_BOE_URL, auction_id, lot
+        ),
+        None => format!("{}{}&ver=5", *ONE_AUCTION_BOE_URL, auction_id),
+    }
+}
+
+/// Retrieve and store the outcome of a concluded `auction` for each of its lots,
+/// returns the number of outcomes stored.
+pub async fn auction_outcome_scraper<C: HttpClient>(
+    http_client: &C,
+    db_client: &DbClient,
+    auction: &Auction,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let lots: Vec<Option<u32>> = if auction.lots == 0 {
+        vec![None]
+    } else {
+        (1..=auction.lots).map(Some).collect()
+    };
+    let mut diagnostics = Diagnostics::lenient();
+
+    for lot in lots.iter() {
+        let bids_page = http_client.get_url(&bids_link(&auction.id, *lot)).await?;
+        let outcome = AuctionOutcome::try_new(
+            &auction.id,
+            *lot,
+            auction.end_date,
+            &parse_bids_auction_page(&bids_page, *lot)?,
+            &mut diagnostics,
+        )?;
+        log::info!(
+            "Auction {} lot {:?} concluded: {}",
+            auction.id,
+            lot,
+            outcome.kind.name()
+        );
+
+        db_client.insert_auction_outcome(&outcome).await?;
+    }
+
+    db_client
+        .insert_diagnostics(&diagnostics.into_warnings())
+        .await;
+
+    Ok(lots.len())
+}
+
 /// Scrape auction page
-pub async fn auction_state_page_scraper(
-    http_client: &UrlFetcher,
+pub async fn auction_state_page_scraper<C: HttpClient>(
+    http_client: &C,
     db_client: &DbClient,
     auction_ids: &[String],
     result_page_url: &str,
@@ -116,7 +587,7 @@ pub async fn auction_state_page_scraper(
 
     log::info!("page url to process: {}", result_page_url);
     let result_page = http_client.get_url(result_page_url).await?;
-    let auction_links = parse_result_page(&result_page);
+    let auction_links = parse_result_page(&result_page)?;
 
     for auction_link in auction_links {
         let auction_id = extract_auction_id_from_link(&auction_link.0)?;
@@ -132,3 +603,512 @@ pub async fn auction_state_page_scraper(
 
     Ok(auction_ok)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::FixtureClient;
+    use crate::test_util::{fixtures_without, test_db, TempPath, FIXTURES_DIR, RESULT_PAGE_URL};
+    use shylock_data::{price_statistics, provinces::Province, Decimal, NaiveDate, OutcomeKind};
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn process_auction_link_fixture_test() {
+        let client = FixtureClient::new(FIXTURES_DIR);
+        let links = parse_result_page(&client.get_url(RESULT_PAGE_URL).await.unwrap()).unwrap();
+        assert_eq!(1, links.len());
+
+        let (auction, assets, _) = process_auction_link(&client, &links[0]).await.unwrap();
+        assert_eq!("SUB-NE-2020-465937", auction.id);
+        assert_eq!(AuctionState::Ongoing, auction.auction_state);
+        assert_eq!("3003000230", auction.management.code);
+        assert_eq!(1, assets.len());
+        match &assets[0] {
+            Asset::Property(property) => {
+                assert_eq!(Province::Valladolid, property.province);
+                assert_eq!("VALLADOLID", property.city);
+            }
+            asset => panic!("expected a property: {:?}", asset),
+        }
+    }
+
+    #[test]
+    fn incremental_search_query_test() {
+        let overlap = Duration::from_secs(24 * 60 * 60);
+        assert_eq!(
+            BoeSearchQuery::new(),
+            incremental_search_query(None, overlap)
+        );
+
+        let last_scrape = DateTime::parse_from_rfc3339("2022-11-07T01:00:00+01:00").unwrap();
+        assert_eq!(
+            BoeSearchQuery::new().start_date(NaiveDate::from_ymd_opt(2022, 11, 6), None),
+            incremental_search_query(Some(last_scrape), overlap)
+        );
+    }
+
+    #[tokio::test]
+    async fn page_scraper_fixture_test() {
+        let (db_client, _db_path) = test_db("scraper-test").await;
+        let client = FixtureClient::new(FIXTURES_DIR);
+
+        assert_eq!(
+            (1, 0, 0),
+            page_scraper(&client, &db_client, RESULT_PAGE_URL)
+                .await
+                .unwrap()
+        );
+        assert!(db_client
+            .auction_exists("SUB-NE-2020-465937")
+            .await
+            .unwrap());
+        assert_eq!(
+            (0, 0, 1),
+            page_scraper(&client, &db_client, RESULT_PAGE_URL)
+                .await
+                .unwrap()
+        );
+
+        let last_scrape = DateTime::parse_from_rfc3339("2022-11-07T18:00:00+01:00").unwrap();
+        assert_eq!(
+            None,
+            db_client.get_last_scrape(INCREMENTAL_SCRAPE).await.unwrap()
+        );
+        db_client
+            .set_last_scrape(INCREMENTAL_SCRAPE, &last_scrape)
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(last_scrape),
+            db_client.get_last_scrape(INCREMENTAL_SCRAPE).await.unwrap()
+        );
+
+        db_client.pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn auction_state_history_test() {
+        let (db_client, _db_path) = test_db("history-test").await;
+        let client = FixtureClient::new(FIXTURES_DIR);
+        page_scraper(&client, &db_client, RESULT_PAGE_URL)
+            .await
+            .unwrap();
+        let started_at: DateTime<FixedOffset> = chrono::Utc::now().into();
+        let auction_id = "SUB-NE-2020-465937";
+
+        for state in [
+            AuctionState::Suspended,
+            AuctionState::Suspended,
+            AuctionState::Ongoing,
+        ] {
+            db_client
+                .update_auction_state(auction_id, state)
+                .await
+                .unwrap();
+        }
+
+        let auction = &db_client
+            .get_auctions_with_states(&[AuctionState::Ongoing])
+            .await
+            .unwrap()[0];
+        let history: Vec<(Option<AuctionState>, AuctionState)> = auction
+            .state_history
+            .iter()
+            .map(|change| (change.previous, change.state))
+            .collect();
+        assert_eq!(
+            vec![
+                (None, AuctionState::Ongoing),
+                (Some(AuctionState::Ongoing), AuctionState::Suspended),
+                (Some(AuctionState::Suspended), AuctionState::Ongoing),
+            ],
+            history
+        );
+        assert_eq!(
+            Some(&1),
+            db_client
+                .get_suspension_counts(&[AuctionState::Ongoing])
+                .await
+                .unwrap()
+                .get(auction_id)
+        );
+        assert_eq!(
+            3,
+            db_client
+                .get_state_changes_since(&(started_at - chrono::Duration::hours(1)))
+                .await
+                .unwrap()
+                .len()
+        );
+
+        let times = db_client
+            .get_time_in_states(auction_id, &(started_at + chrono::Duration::hours(1)))
+            .await
+            .unwrap();
+        let time_in = |state| {
+            times
+                .iter()
+                .find(|(time_state, _)| *time_state == state)
+                .map(|(_, time)| time.num_minutes())
+        };
+        assert_eq!(Some(0), time_in(AuctionState::Suspended));
+        assert!(time_in(AuctionState::Ongoing).unwrap() >= 59);
+
+        db_client.pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn auction_outcome_scraper_fixture_test() {
+        let (db_client, _db_path) = test_db("outcome-test").await;
+        let client = FixtureClient::new(FIXTURES_DIR);
+        page_scraper(&client, &db_client, RESULT_PAGE_URL)
+            .await
+            .unwrap();
+
+        let auction_id = db_client
+            .get_auctions_with_states(&[AuctionState::Ongoing])
+            .await
+            .unwrap()[0]
+            .id
+            .clone();
+        db_client
+            .update_auction_state(&auction_id, AuctionState::Finished)
+            .await
+            .unwrap();
+
+        // Outcomes are only scraped for concluded auctions, as the results mode does.
+        let auction = &db_client
+            .get_auctions_with_states(&[AuctionState::Finished])
+            .await
+            .unwrap()[0];
+        assert_eq!(auction_id, auction.id);
+        assert!(!db_client.auction_outcome_exists(&auction.id).await.unwrap());
+        assert_eq!(
+            1,
+            auction_outcome_scraper(&client, &db_client, auction)
+                .await
+                .unwrap()
+        );
+        assert!(db_client.auction_outcome_exists(&auction.id).await.unwrap());
+
+        let outcomes = db_client.get_property_outcomes().await.unwrap();
+        assert_eq!(1, outcomes.len());
+        let (province, category, outcome, _) = &outcomes[0];
+        assert_eq!(Province::Valladolid, *province);
+        assert_eq!(OutcomeKind::WithBids, outcome.kind);
+
+        let statistics = price_statistics(outcomes.iter().map(
+            |(province, category, outcome, bidinfo)| (*province, *category, outcome, *bidinfo),
+        ));
+        assert_eq!(1, statistics.len());
+        assert_eq!(*category, statistics[0].category);
+        assert_eq!(
+            Some(Decimal::new(8, 1)),
+            statistics[0].average_ratio.map(|ratio| ratio.round_dp(2))
+        );
+
+        sqlx::query("UPDATE auctions SET bidinfo = 'invalid' WHERE id = ?")
+            .bind(&auction.id)
+            .execute(&db_client.pool)
+            .await
+            .unwrap();
+        assert!(db_client.get_property_outcomes().await.is_err());
+
+        db_client.pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn auction_attachments_scraper_fixture_test() {
+        let store_dir = TempPath::new("attachments-store");
+        let (db_client, _db_path) = test_db("attachments-test").await;
+        let client = FixtureClient::new(FIXTURES_DIR);
+        let store = AttachmentStore::new(&store_dir, 4096);
+        page_scraper(&client, &db_client, RESULT_PAGE_URL)
+            .await
+            .unwrap();
+
+        let auction = &db_client
+            .get_auctions_with_states(&[AuctionState::Ongoing])
+            .await
+            .unwrap()[0];
+        assert_eq!(
+            2,
+            auction_attachments_scraper(&client, &db_client, &store, auction)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            0,
+            auction_attachments_scraper(&client, &db_client, &store, auction)
+                .await
+                .unwrap()
+        );
+
+        let properties = db_client
+            .get_properties_with_auction_states(&[AuctionState::Ongoing])
+            .await
+            .unwrap();
+        let attachments = &properties[0].attachments;
+        assert_eq!(2, attachments.len());
+        assert_eq!(AttachmentKind::Edict, attachments[0].kind);
+        assert_eq!("application/pdf", attachments[0].content_type);
+        assert!(store.path(&attachments[0].hash).exists());
+        assert_eq!(AttachmentKind::Photo, attachments[1].kind);
+        assert_eq!("VER FOTOGRAFÍAS ANEXAS", attachments[1].title);
+        assert!(attachments[1].is_image());
+
+        let found = db_client.search_attachments("17228").await.unwrap();
+        assert_eq!(1, found.len());
+        assert_eq!(auction.id, found[0].0);
+        assert_eq!(attachments[0], found[0].2);
+        assert!(db_client
+            .search_attachments("MADRID")
+            .await
+            .unwrap()
+            .is_empty());
+
+        db_client.pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn scrape_run_scraper_fixture_test() {
+        const RUN_QUERY: &str = "accion=Mas&id_busqueda=fixture";
+        const AUCTION_FIXTURE: &str =
+            "subastas.boe.es_._detalleSubasta.php_idSub_SUB-NE-2020-465937_idBus_fixture.html";
+        let fixtures_dir = fixtures_without("run", AUCTION_FIXTURE);
+        let (db_client, _db_path) = test_db("run-test").await;
+        let client = FixtureClient::new(&fixtures_dir);
+
+        let run_id = db_client
+            .create_scrape_run(INIT_SCRAPE, RUN_QUERY)
+            .await
+            .unwrap();
+        assert_eq!(
+            1,
+            scrape_run_scraper(&client, &db_client, run_id, 2)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            vec![(0, ScrapeStatus::Failed)],
+            db_client.get_scrape_pages(run_id).await.unwrap()
+        );
+        assert_eq!(
+            vec![("SUB-NE-2020-465937".to_owned(), ScrapeStatus::Failed)],
+            db_client.get_scrape_links(run_id).await.unwrap()
+        );
+        let run = db_client
+            .get_last_unfinished_scrape_run(INIT_SCRAPE)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(run_id, run.id);
+        assert_eq!(ScrapeStatus::Failed, run.status);
+        assert_eq!(RUN_QUERY, run.query);
+        assert_eq!(None, db_client.get_last_done_scrape_run_id().await.unwrap());
+
+        std::fs::copy(
+            Path::new(FIXTURES_DIR).join(AUCTION_FIXTURE),
+            fixtures_dir.join(AUCTION_FIXTURE),
+        )
+        .unwrap();
+        // Results shift between searches, a page done may hold links not done yet.
+        db_client
+            .set_scrape_page(run_id, 0, RESULT_PAGE_URL, ScrapeStatus::Done, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            0,
+            scrape_run_scraper(&client, &db_client, run.id, 2)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            vec![(0, ScrapeStatus::Done)],
+            db_client.get_scrape_pages(run_id).await.unwrap()
+        );
+        assert_eq!(
+            vec![("SUB-NE-2020-465937".to_owned(), ScrapeStatus::Done)],
+            db_client.get_scrape_links(run_id).await.unwrap()
+        );
+        assert!(db_client
+            .auction_exists("SUB-NE-2020-465937")
+            .await
+            .unwrap());
+        assert_eq!(
+            None,
+            db_client
+                .get_last_unfinished_scrape_run(INIT_SCRAPE)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Some(run_id),
+            db_client.get_last_done_scrape_run_id().await.unwrap()
+        );
+
+        // Links done are not scraped again even if their auction is not stored.
+        sqlx::query("DELETE FROM auctions")
+            .execute(&db_client.pool)
+            .await
+            .unwrap();
+        std::fs::remove_file(fixtures_dir.join(AUCTION_FIXTURE)).unwrap();
+        assert_eq!(
+            0,
+            scrape_run_scraper(&client, &db_client, run_id, 2)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            vec![("SUB-NE-2020-465937".to_owned(), ScrapeStatus::Done)],
+            db_client.get_scrape_links(run_id).await.unwrap()
+        );
+
+        db_client.pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn page_scraper_malformed_page_test() {
+        const RESULT_FIXTURE: &str =
+            "subastas.boe.es_subastas_ava.php_accion_Mas_id_busqueda_fixture.html";
+        let fixtures_dir = fixtures_without("malformed", RESULT_FIXTURE);
+        std::fs::write(
+            fixtures_dir.join(RESULT_FIXTURE),
+            r#"<ul><li class="resultado-busqueda"><h3>SUBASTA SUB-NE-2020-465937</h3></li></ul>"#,
+        )
+        .unwrap();
+        let (db_client, _db_path) = test_db("malformed-test").await;
+        let client = FixtureClient::new(&fixtures_dir);
+
+        let error = page_scraper(&client, &db_client, RESULT_PAGE_URL)
+            .await
+            .unwrap_err();
+        let error = error.downcast_ref::<ParseError>().unwrap();
+        assert_eq!(PageKind::Results, error.page);
+        assert_eq!("a.resultado-busqueda-link-otro", error.selector);
+
+        db_client.pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn failed_auction_scraper_fixture_test() {
+        const ASSET_FIXTURE: &str =
+            "subastas.boe.es_._detalleSubasta.php_idSub_SUB-NE-2020-465937_ver_3.html";
+        let fixtures_dir = fixtures_without("failed", ASSET_FIXTURE);
+        let (db_client, _db_path) = test_db("failed-test").await;
+        let client = FixtureClient::new(&fixtures_dir);
+
+        assert_eq!(
+            (0, 1, 0),
+            page_scraper(&client, &db_client, RESULT_PAGE_URL)
+                .await
+                .unwrap()
+        );
+        let failed = db_client.get_failed_auctions().await.unwrap();
+        assert_eq!(1, failed.len());
+        assert_eq!("SUB-NE-2020-465937", failed[0].auction_id);
+        assert_eq!(AuctionState::Ongoing, failed[0].link.1);
+        assert_eq!(1, failed[0].attempts);
+        assert!(failed[0].error.contains(ASSET_FIXTURE));
+        let pages = db_client
+            .get_failed_auction_pages(&failed[0].auction_id)
+            .await
+            .unwrap();
+        assert_eq!(2, pages.len());
+        assert!(pages[1].0.ends_with("ver=2"));
+
+        let backoff = Duration::from_secs(3600);
+        let last_attempt_at = failed[0].last_attempt_at;
+        assert!(!failed[0].is_due(&last_attempt_at, backoff));
+        assert!(failed[0].is_due(&(last_attempt_at + chrono::Duration::hours(1)), backoff));
+
+        assert!(failed_auction_scraper(&client, &db_client, &failed[0])
+            .await
+            .is_err());
+        let failed = db_client.get_failed_auctions().await.unwrap();
+        assert_eq!(2, failed[0].attempts);
+        let last_attempt_at = failed[0].last_attempt_at;
+        assert!(!failed[0].is_due(&(last_attempt_at + chrono::Duration::hours(1)), backoff));
+        assert!(failed[0].is_due(&(last_attempt_at + chrono::Duration::hours(2)), backoff));
+
+        std::fs::copy(
+            Path::new(FIXTURES_DIR).join(ASSET_FIXTURE),
+            fixtures_dir.join(ASSET_FIXTURE),
+        )
+        .unwrap();
+        failed_auction_scraper(&client, &db_client, &failed[0])
+            .await
+            .unwrap();
+        assert!(db_client.get_failed_auctions().await.unwrap().is_empty());
+        assert!(db_client
+            .get_failed_auction_pages("SUB-NE-2020-465937")
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(db_client
+            .auction_exists("SUB-NE-2020-465937")
+            .await
+            .unwrap());
+
+        db_client.pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn auction_changes_scraper_fixture_test() {
+        let (db_client, _db_path) = test_db("changes-test").await;
+        let client = FixtureClient::new(FIXTURES_DIR);
+        page_scraper(&client, &db_client, RESULT_PAGE_URL)
+            .await
+            .unwrap();
+
+        let states = &[AuctionState::Ongoing];
+        let mut auction = db_client.get_auctions_with_states(states).await.unwrap()[0].clone();
+        let mut assets = db_client
+            .get_assets_by_auction_with_states(states)
+            .await
+            .unwrap()
+            .remove(&auction.id)
+            .unwrap();
+        assert_eq!(
+            0,
+            auction_changes_scraper(&client, &db_client, &auction, &assets)
+                .await
+                .unwrap()
+        );
+
+        auction.notice = "BOE-B-2020-00000".to_owned();
+        auction.auction_state = AuctionState::ToBeOpened;
+        db_client.update_auction(&auction).await.unwrap();
+        if let Asset::Property(property) = &mut assets[0] {
+            property.lot = Some(2);
+        }
+        db_client.insert_assets(&auction, &assets).await;
+        assert_eq!(
+            4,
+            auction_changes_scraper(&client, &db_client, &auction, &assets)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            4,
+            db_client.count_auction_changes(&auction.id).await.unwrap()
+        );
+
+        let stored = db_client.get_auctions_with_states(states).await.unwrap();
+        assert_ne!(auction.notice, stored[0].notice);
+        assert_eq!(
+            Some(AuctionState::ToBeOpened),
+            stored[0].state_history.last().unwrap().previous
+        );
+        let stored = db_client
+            .get_assets_by_auction_with_states(states)
+            .await
+            .unwrap()
+            .remove(&auction.id)
+            .unwrap();
+        assert_eq!(1, stored.len());
+        assert_eq!(None, stored[0].lot());
+
+        db_client.pool.close().await;
+    }
+}
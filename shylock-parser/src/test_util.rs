@@ -0,0 +1,77 @@
+use crate::db::DbClient;
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// Directory with the saved BOE pages served by `FixtureClient`.
+pub(crate) const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/fixtures/boe");
+
+/// Results page of the fixtures search.
+pub(crate) const RESULT_PAGE_URL: &str =
+    "https://subastas.boe.es/subastas_ava.php?accion=Mas&id_busqueda=fixture";
+
+/// Temporary file or directory removed when dropped, also when a test fails.
+#[derive(Debug)]
+pub(crate) struct TempPath(PathBuf);
+
+impl TempPath {
+    /// Path named after `name` in the temporary directory, unique for each test process.
+    pub(crate) fn new(name: &str) -> Self {
+        TempPath(std::env::temp_dir().join(format!("shylock-{}-{}", name, std::process::id())))
+    }
+}
+
+impl Deref for TempPath {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempPath {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+// SQLite keeps its write-ahead log next to test databases.
+const SQLITE_SIDE_FILES: [&str; 2] = ["-wal", "-shm"];
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        if self.0.is_dir() {
+            let _ = std::fs::remove_dir_all(&self.0);
+        } else {
+            let _ = std::fs::remove_file(&self.0);
+            for suffix in SQLITE_SIDE_FILES {
+                let mut side_file = self.0.clone().into_os_string();
+                side_file.push(suffix);
+                let _ = std::fs::remove_file(side_file);
+            }
+        }
+    }
+}
+
+/// Migrated database named after `name`, removed when the returned path is dropped.
+pub(crate) async fn test_db(name: &str) -> (DbClient, TempPath) {
+    let db_path = TempPath::new(&format!("{}.db", name));
+    let db_client = DbClient::new(db_path.to_str().unwrap()).await.unwrap();
+    db_client.migrate().await.unwrap();
+
+    (db_client, db_path)
+}
+
+/// Copy of the fixtures directory without `fixture`, to make requests for it fail.
+pub(crate) fn fixtures_without(name: &str, fixture: &str) -> TempPath {
+    let fixtures_dir = TempPath::new(&format!("{}-fixtures", name));
+    std::fs::create_dir_all(&fixtures_dir).unwrap();
+    for entry in std::fs::read_dir(FIXTURES_DIR).unwrap() {
+        let path = entry.unwrap().path();
+        if !path.ends_with(fixture) {
+            std::fs::copy(&path, fixtures_dir.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+
+    fixtures_dir
+}
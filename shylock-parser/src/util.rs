@@ -1,9 +1,11 @@
-use miniz_oxide::deflate::compress_to_vec;
+use crate::parser::{PageKind, ParseError};
 use serde::Serialize;
+use shylock_data::{Export, ExportHeader};
 use std::fs::File;
 use std::io;
 use std::io::Write;
 use std::str;
+use std::time::Duration;
 
 // Constant for parsing lot number.
 const AUCTION_LOT_NUMBER_STR: &str = "idLote=";
@@ -11,23 +13,28 @@ const AUCTION_LOT_NUMBER_STR: &str = "idLote=";
 // Constant for parsing auction identifier.
 const AUCTION_ID_LINK_STR: &str = "?idSub=";
 
-fn extract_field_value_from_link<'l>(
-    link: &'l str,
-    tag: &str,
-) -> Result<&'l str, Box<dyn std::error::Error>> {
-    let id_begin = link.find(tag).unwrap() + tag.len();
-    let id_end = link[id_begin..].find('&').unwrap() + id_begin;
+fn extract_field_value_from_link<'l>(link: &'l str, tag: &str) -> Result<&'l str, ParseError> {
+    let id_begin = link
+        .find(tag)
+        .ok_or_else(|| ParseError::new(PageKind::Link, tag, "no field found", link))?
+        + tag.len();
+    let id_end = link[id_begin..]
+        .find('&')
+        .map_or(link.len(), |end| end + id_begin);
+    if id_begin == id_end {
+        return Err(ParseError::new(PageKind::Link, tag, "empty field", link));
+    }
+
     Ok(&link[id_begin..id_end])
 }
+
 /// Extract auction id from a `link`.
-pub fn extract_auction_id_from_link(link: &str) -> Result<&str, Box<dyn std::error::Error>> {
+pub fn extract_auction_id_from_link(link: &str) -> Result<&str, ParseError> {
     extract_field_value_from_link(link, AUCTION_ID_LINK_STR)
 }
 
 /// Extract auction lot number from a `link`.
-pub fn extract_auction_lot_number_from_link(
-    link: &str,
-) -> Result<&str, Box<dyn std::error::Error>> {
+pub fn extract_auction_lot_number_from_link(link: &str) -> Result<&str, ParseError> {
     extract_field_value_from_link(link, AUCTION_LOT_NUMBER_STR)
 }
 
@@ -36,7 +43,7 @@ pub fn dump_to_json_file<T>(dst_path: &str, data: &T) -> Result<(), Box<dyn std:
 where
     T: Serialize,
 {
-    let json = serde_json::to_string(&data).unwrap();
+    let json = serde_json::to_string(&data)?;
 
     let mut dest = {
         log::info!("data json file will be located under: '{:?}'", dst_path);
@@ -54,7 +61,7 @@ pub fn dump_to_rmp_file<T>(dst_path: &str, data: &T) -> Result<(), Box<dyn std::
 where
     T: Serialize,
 {
-    let rmp = rmp_serde::to_vec(&data).unwrap();
+    let rmp = rmp_serde::to_vec(&data)?;
 
     let mut dest = {
         log::info!("data rmp file will be located under: '{:?}'", dst_path);
@@ -67,27 +74,32 @@ where
     Ok(())
 }
 
-/// Serialize  `data` to cbor files in given `dst_path`.
+/// Serialize `data` with its `header` to a compressed cbor file in given `dst_path`.
 pub fn dump_to_cbor_compressed_file<T>(
     dst_path: &str,
+    header: &ExportHeader,
     data: &T,
 ) -> Result<(), Box<dyn std::error::Error>>
 where
     T: Serialize,
 {
-    let mut tmp_buffer = vec![];
-    ciborium::ser::into_writer(&data, &mut tmp_buffer)?;
+    let export = Export {
+        header: header.clone(),
+        data,
+    };
+    let compressed = export.encode()?;
 
     let mut file = {
         log::info!("data cbor file will be located under: '{:?}'", dst_path);
         File::create(dst_path)?
     };
 
-    let compressed = compress_to_vec(&tmp_buffer, 7);
-
     file.write_all(compressed.as_slice())?;
 
-    log::info!("data cbor file created");
+    log::info!(
+        "data cbor file created with schema version {}",
+        header.schema_version
+    );
 
     Ok(())
 }
@@ -108,7 +120,71 @@ pub fn normalize(str: &str) -> String {
         .collect()
 }
 
-/// Check if string it's a valid catastro reference
-pub fn valid_catastro_reference(reference: &str) -> bool {
-    reference.len() == 20 && !reference.contains(' ')
+/// Parse a `duration` made of a number and a unit: `s`, `m`, `h` or `d`, e.g. `12h`.
+pub fn parse_duration(duration: &str) -> Result<Duration, Box<dyn std::error::Error>> {
+    let duration = duration.trim();
+    let unit_index = duration
+        .find(|x: char| !x.is_ascii_digit())
+        .ok_or("duration without unit")?;
+    let quantity = duration[..unit_index].parse::<u64>()?;
+
+    let seconds = match &duration[unit_index..] {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        unit => return Err(format!("unknown duration unit: {}", unit).into()),
+    };
+
+    Ok(Duration::from_secs(quantity * seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extract_field_value_from_link_test() {
+        const LINK: &str =
+            "https://subastas.boe.es/./detalleSubasta.php?idSub=SUB-JA-2020-158475&ver=3&idLote=2";
+        assert_eq!(
+            "SUB-JA-2020-158475",
+            extract_auction_id_from_link(LINK).unwrap()
+        );
+        assert_eq!("2", extract_auction_lot_number_from_link(LINK).unwrap());
+        assert_eq!(
+            "SUB-NE-2020-465937",
+            extract_auction_id_from_link(
+                "https://subastas.boe.es/detalleSubasta.php?idSub=SUB-NE-2020-465937"
+            )
+            .unwrap()
+        );
+
+        let error = extract_auction_lot_number_from_link(
+            "https://subastas.boe.es/detalleSubasta.php?idSub=SUB-NE-2020-465937&ver=3",
+        )
+        .unwrap_err();
+        assert_eq!(PageKind::Link, error.page);
+        assert_eq!(AUCTION_LOT_NUMBER_STR, error.selector);
+        assert!(extract_auction_id_from_link(
+            "https://subastas.boe.es/detalleSubasta.php?idSub=&ver=3"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn parse_duration_test() {
+        assert_eq!(Duration::from_secs(90), parse_duration("90s").unwrap());
+        assert_eq!(
+            Duration::from_secs(12 * 3600),
+            parse_duration("12h").unwrap()
+        );
+        assert_eq!(
+            Duration::from_secs(7 * 86400),
+            parse_duration(" 7d ").unwrap()
+        );
+        assert!(parse_duration("12").is_err());
+        assert!(parse_duration("h").is_err());
+        assert!(parse_duration("3w").is_err());
+    }
 }